@@ -0,0 +1,25 @@
+// Declarations prepended to user post-processing shaders.
+
+#if defined(GLES2_RENDERER)
+precision mediump float;
+
+#define texture texture2D
+#define FRAG_COLOR gl_FragColor
+
+varying vec2 uv;
+#else
+out vec4 FragColor;
+#define FRAG_COLOR FragColor
+
+in vec2 uv;
+#endif
+
+// Rendered terminal frame, with premultiplied alpha.
+uniform sampler2D frame;
+
+// Window size in pixels.
+uniform vec2 resolution;
+
+// Seconds since the shader was loaded.
+uniform float time;
+
@@ -0,0 +1,14 @@
+#if defined(GLES2_RENDERER)
+attribute vec2 aPos;
+
+varying mediump vec2 uv;
+#else
+layout (location = 0) in vec2 aPos;
+
+out vec2 uv;
+#endif
+
+void main() {
+    uv = (aPos + 1.0) / 2.0;
+    gl_Position = vec4(aPos.x, aPos.y, 0.0, 1.0);
+}
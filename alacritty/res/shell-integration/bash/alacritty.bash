@@ -0,0 +1,68 @@
+# Alacritty shell integration for bash.
+#
+# Reports prompt and command boundaries with OSC 133, the working directory with OSC 7, and the
+# exit status and duration in milliseconds of every command.
+
+# Restore regular startup when injected by Alacritty through POSIX mode's ENV file.
+if [[ -n "$ALACRITTY_BASH_INJECT" ]]; then
+    builtin unset ENV ALACRITTY_BASH_INJECT
+    builtin set +o posix
+
+    # POSIX mode skips all startup files, including the system-wide ones.
+    if [[ -f /etc/bash.bashrc ]]; then
+        builtin source /etc/bash.bashrc
+    elif [[ -f /etc/bash/bashrc ]]; then
+        builtin source /etc/bash/bashrc
+    fi
+
+    if [[ -f ~/.bashrc ]]; then
+        builtin source ~/.bashrc
+    fi
+fi
+
+# Only integrate with interactive shells, and only once.
+if [[ $- != *i* || -n "$_alacritty_integration" ]]; then
+    builtin return 0
+fi
+_alacritty_integration=1
+
+_alacritty_prompt() {
+    local status=$?
+
+    # Report the end of the previous command, if there was one.
+    if [[ -n "$_alacritty_command_start" ]]; then
+        local duration=$(( (${EPOCHREALTIME//[!0-9]/} - _alacritty_command_start) / 1000 ))
+        builtin printf '\e]133;D;%s;duration=%s\a' "$status" "$duration"
+        _alacritty_command_start=""
+    elif [[ -n "$_alacritty_prompted" ]]; then
+        builtin printf '\e]133;D;%s\a' "$status"
+    fi
+    _alacritty_prompted=1
+
+    builtin printf '\e]7;file://%s%s\a' "$HOSTNAME" "$PWD"
+
+    # Mark the prompt, unless it was already marked previously.
+    if [[ "$PS1" != *'133;A'* ]]; then
+        PS1='\[\e]133;A\a\]'"$PS1"'\[\e]133;B\a\]'
+    fi
+
+    return $status
+}
+
+# Mark the start of the command output.
+PS0+='\e]133;C\a'
+
+# Record the start time of the command, which requires bash 5 for `EPOCHREALTIME`.
+#
+# Since PS0 cannot run commands in the current shell, the assignment is done through the arithmetic
+# subscript of an array, which expands to nothing.
+if [[ -n "$EPOCHREALTIME" ]]; then
+    PS0+='${_alacritty_noop[_alacritty_command_start=${EPOCHREALTIME//[!0-9]/}]}'
+fi
+
+# Run before other prompt commands, to observe the exit status of the last command.
+if [[ "$(builtin declare -p PROMPT_COMMAND 2>/dev/null)" == "declare -a"* ]]; then
+    PROMPT_COMMAND=(_alacritty_prompt "${PROMPT_COMMAND[@]}")
+else
+    PROMPT_COMMAND="_alacritty_prompt${PROMPT_COMMAND:+; $PROMPT_COMMAND}"
+fi
@@ -0,0 +1,33 @@
+# Alacritty shell integration for fish.
+#
+# Reports prompt and command boundaries with OSC 133, the working directory with OSC 7, and the
+# exit status and duration in milliseconds of every command.
+
+# Remove the integration from the data directories, to avoid leaking it to child processes.
+if set -q ALACRITTY_SHELL_INTEGRATION_DIR
+    set -gx XDG_DATA_DIRS (string replace -- "$ALACRITTY_SHELL_INTEGRATION_DIR:" "" $XDG_DATA_DIRS)
+    set -e ALACRITTY_SHELL_INTEGRATION_DIR
+end
+
+# Only integrate with interactive shells, and only once.
+status is-interactive; or exit 0
+set -q __alacritty_integration; and exit 0
+set -g __alacritty_integration 1
+
+function __alacritty_prompt --on-event fish_prompt
+    set -l last_status $status
+
+    # Report the end of the previous command.
+    if set -q __alacritty_command_running
+        printf '\e]133;D;%s;duration=%s\a' $last_status $CMD_DURATION
+        set -e __alacritty_command_running
+    end
+
+    printf '\e]7;file://%s%s\a' $hostname $PWD
+    printf '\e]133;A\a'
+end
+
+function __alacritty_preexec --on-event fish_preexec
+    set -g __alacritty_command_running 1
+    printf '\e]133;C\a'
+end
@@ -0,0 +1,73 @@
+//! System accessibility preferences.
+
+/// Check if the system asks applications to reduce motion.
+#[cfg(target_os = "macos")]
+pub fn reduce_motion() -> bool {
+    use objc2_app_kit::NSWorkspace;
+
+    // SAFETY: The shared workspace is always available and the property is read-only.
+    unsafe { NSWorkspace::sharedWorkspace().accessibilityDisplayShouldReduceMotion() }
+}
+
+/// Check if the system asks applications to increase contrast.
+#[cfg(target_os = "macos")]
+pub fn increase_contrast() -> bool {
+    use objc2_app_kit::NSWorkspace;
+
+    // SAFETY: The shared workspace is always available and the property is read-only.
+    unsafe { NSWorkspace::sharedWorkspace().accessibilityDisplayShouldIncreaseContrast() }
+}
+
+/// Check if the system asks applications to reduce motion.
+#[cfg(windows)]
+pub fn reduce_motion() -> bool {
+    use std::ffi::c_void;
+
+    use windows_sys::Win32::Foundation::BOOL;
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION,
+    };
+
+    let mut animations: BOOL = 1;
+    let pointer = &mut animations as *mut BOOL as *mut c_void;
+    // SAFETY: `SPI_GETCLIENTAREAANIMATION` writes a single `BOOL` to the valid pointer.
+    let success = unsafe { SystemParametersInfoW(SPI_GETCLIENTAREAANIMATION, 0, pointer, 0) };
+
+    success != 0 && animations == 0
+}
+
+/// Check if the system asks applications to increase contrast.
+#[cfg(windows)]
+pub fn increase_contrast() -> bool {
+    use std::ffi::c_void;
+    use std::mem;
+
+    use windows_sys::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{SystemParametersInfoW, SPI_GETHIGHCONTRAST};
+
+    // SAFETY: `HIGHCONTRASTW` only contains integers and a nullable string pointer.
+    let mut high_contrast: HIGHCONTRASTW = unsafe { mem::zeroed() };
+    high_contrast.cbSize = mem::size_of::<HIGHCONTRASTW>() as u32;
+    let size = high_contrast.cbSize;
+    let pointer = &mut high_contrast as *mut HIGHCONTRASTW as *mut c_void;
+    // SAFETY: The pointer references a `HIGHCONTRASTW` with its size set in `cbSize`.
+    let success = unsafe { SystemParametersInfoW(SPI_GETHIGHCONTRAST, size, pointer, 0) };
+
+    success != 0 && high_contrast.dwFlags & HCF_HIGHCONTRASTON != 0
+}
+
+/// Check if the system asks applications to reduce motion.
+///
+/// There is no common API for this on other platforms, so it is never requested.
+#[cfg(not(any(target_os = "macos", windows)))]
+pub fn reduce_motion() -> bool {
+    false
+}
+
+/// Check if the system asks applications to increase contrast.
+///
+/// There is no common API for this on other platforms, so it is never requested.
+#[cfg(not(any(target_os = "macos", windows)))]
+pub fn increase_contrast() -> bool {
+    false
+}
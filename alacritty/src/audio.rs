@@ -0,0 +1,142 @@
+//! Audible bell playback using the platform's audio facilities.
+//!
+//! No audio library is linked. Sounds are played by launching `paplay` or `canberra-gtk-play` on
+//! Linux/BSD and `afplay` on macOS, which must be installed separately, while Windows uses the
+//! system's sound APIs.
+
+use std::path::Path;
+#[cfg(not(windows))]
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+#[cfg(not(windows))]
+use std::{io, thread};
+
+use log::warn;
+
+use crate::config::bell::{Audible, BellConfig};
+
+/// Minimum time between two bell sounds.
+const MIN_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Audible bell with rate limiting.
+#[derive(Default)]
+pub struct AudibleBell {
+    /// The last time a sound was played, if at all.
+    last_played: Option<Instant>,
+}
+
+impl AudibleBell {
+    /// Play the configured bell sound.
+    pub fn ring(&mut self, config: &BellConfig, focused: bool) {
+        let audible = match config.audible {
+            Audible::Never => false,
+            Audible::Focused => focused,
+            Audible::Unfocused => !focused,
+            Audible::Always => true,
+        };
+
+        if audible && self.try_acquire(Instant::now()) {
+            play(config.sound.as_deref(), config.volume.as_f32());
+        }
+    }
+
+    /// Check the rate limit, marking the bell as played if it passed.
+    fn try_acquire(&mut self, now: Instant) -> bool {
+        if self.last_played.is_some_and(|last| now.saturating_duration_since(last) < MIN_INTERVAL) {
+            return false;
+        }
+
+        self.last_played = Some(now);
+        true
+    }
+}
+
+/// Play a sound file, falling back to the system alert without one.
+#[cfg(not(any(target_os = "macos", windows)))]
+fn play(sound: Option<&Path>, volume: f32) {
+    let result = match sound {
+        Some(sound) => {
+            let volume = format!("--volume={}", (volume * 65536.) as u32);
+            spawn_player(Command::new("paplay").arg(volume).arg(sound))
+        },
+        None => spawn_player(Command::new("canberra-gtk-play").arg("--id=bell")),
+    };
+
+    if let Err(err) = result {
+        warn!("Unable to play bell sound: {err}");
+    }
+}
+
+/// Play a sound file, falling back to the system alert without one.
+#[cfg(target_os = "macos")]
+fn play(sound: Option<&Path>, volume: f32) {
+    let sound = match sound {
+        Some(sound) => sound,
+        None => {
+            // SAFETY: `NSBeep` takes no arguments and has no preconditions.
+            unsafe { objc2_app_kit::NSBeep() };
+            return;
+        },
+    };
+
+    let result = spawn_player(Command::new("afplay").arg("-v").arg(volume.to_string()).arg(sound));
+    if let Err(err) = result {
+        warn!("Unable to play bell sound: {err}");
+    }
+}
+
+/// Play a sound file, falling back to the system alert without one.
+///
+/// The volume is not supported on Windows.
+#[cfg(windows)]
+fn play(sound: Option<&Path>, _volume: f32) {
+    use std::os::windows::ffi::OsStrExt;
+
+    use windows_sys::Win32::Media::Audio::{PlaySoundW, SND_ASYNC, SND_FILENAME, SND_NODEFAULT};
+    use windows_sys::Win32::System::Diagnostics::Debug::MessageBeep;
+    use windows_sys::Win32::UI::WindowsAndMessaging::MB_OK;
+
+    let played = match sound {
+        Some(sound) => {
+            let sound: Vec<u16> = sound.as_os_str().encode_wide().chain(Some(0)).collect();
+            let flags = SND_ASYNC | SND_FILENAME | SND_NODEFAULT;
+            // SAFETY: The file name is a NUL-terminated wide string which outlives the call, and
+            // no module handle is required without `SND_RESOURCE`.
+            unsafe { PlaySoundW(sound.as_ptr(), 0, flags) }
+        },
+        // SAFETY: `MessageBeep` only takes the sound type, which is a valid constant.
+        None => unsafe { MessageBeep(MB_OK) },
+    };
+
+    if played == 0 {
+        warn!("Unable to play bell sound: {}", std::io::Error::last_os_error());
+    }
+}
+
+/// Launch an audio player in the background.
+#[cfg(not(windows))]
+fn spawn_player(command: &mut Command) -> io::Result<()> {
+    let mut child =
+        command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).spawn()?;
+
+    // Reap the player once it's done.
+    thread::spawn(move || child.wait());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limit() {
+        let mut bell = AudibleBell::default();
+        let now = Instant::now();
+
+        assert!(bell.try_acquire(now));
+        assert!(!bell.try_acquire(now + MIN_INTERVAL / 2));
+        assert!(bell.try_acquire(now + MIN_INTERVAL));
+        assert!(!bell.try_acquire(now + MIN_INTERVAL));
+    }
+}
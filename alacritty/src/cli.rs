@@ -1,11 +1,20 @@
 use std::cmp::max;
 use std::collections::HashMap;
+#[cfg(unix)]
+use std::ffi::OsString;
 use std::ops::{Deref, DerefMut};
+#[cfg(unix)]
+use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
+#[cfg(unix)]
+use std::time::Duration;
+#[cfg(unix)]
+use std::{fs, io};
 
 use alacritty_config::SerdeReplace;
 use clap::{ArgAction, Args, Parser, Subcommand, ValueHint};
+use clap_complete::Shell;
 use log::{error, LevelFilter};
 use serde::{Deserialize, Serialize};
 use toml::Value;
@@ -14,7 +23,7 @@ use alacritty_terminal::tty::Options as PtyOptions;
 
 use crate::config::ui_config::Program;
 use crate::config::window::{Class, Identity};
-use crate::config::UiConfig;
+use crate::config::{serde_utils, UiConfig};
 use crate::logging::LOG_TARGET_IPC_CONFIG;
 
 /// CLI options for the main Alacritty executable.
@@ -25,6 +34,17 @@ pub struct Options {
     #[clap(long)]
     pub print_events: bool,
 
+    /// Write all events with timestamps to a JSON lines file.
+    #[clap(long, value_hint = ValueHint::FilePath)]
+    pub event_trace: Option<PathBuf>,
+
+    /// Replay the window events of a trace written with `--event-trace`.
+    ///
+    /// This is always accepted to keep the completions independent of the enabled features, but
+    /// requires the `event-replay` feature to have any effect.
+    #[clap(long, hide = true, value_hint = ValueHint::FilePath)]
+    pub replay_events: Option<PathBuf>,
+
     /// Generates ref test.
     #[clap(long, conflicts_with("daemon"))]
     pub ref_test: bool,
@@ -66,6 +86,11 @@ pub struct Options {
     #[clap(long)]
     pub daemon: bool,
 
+    /// IPC socket of the instance taken over by this one.
+    #[cfg(unix)]
+    #[clap(long, hide = true, value_hint = ValueHint::FilePath)]
+    pub restart_from: Option<PathBuf>,
+
     /// CLI options for config overrides.
     #[clap(skip)]
     pub config_options: ParsedOptions,
@@ -144,6 +169,18 @@ fn parse_class(input: &str) -> Result<Class, String> {
     Ok(Class::new(general, instance))
 }
 
+/// Parse a duration like `10s` or `500ms`, defaulting to seconds.
+#[cfg(unix)]
+fn parse_duration(input: &str) -> Result<Duration, String> {
+    let (value, millis) = match input.strip_suffix("ms") {
+        Some(value) => (value, true),
+        None => (input.strip_suffix('s').unwrap_or(input), false),
+    };
+
+    let value: u64 = value.trim().parse().map_err(|_| format!("invalid duration `{input}`"))?;
+    Ok(if millis { Duration::from_millis(value) } else { Duration::from_secs(value) })
+}
+
 /// Convert to hex if possible, else decimal
 fn parse_hex_or_decimal(input: &str) -> Option<u32> {
     input
@@ -200,6 +237,7 @@ impl From<TerminalOptions> for PtyOptions {
             shell: options.command().map(Into::into),
             drain_on_exit: options.hold,
             env: HashMap::new(),
+            env_remove: Vec::new(),
         }
     }
 }
@@ -234,6 +272,8 @@ pub enum Subcommands {
     #[cfg(unix)]
     Msg(MessageOptions),
     Migrate(MigrateOptions),
+    Terminfo(TerminfoOptions),
+    Completions(CompletionsOptions),
 }
 
 /// Send a message to the Alacritty socket.
@@ -258,6 +298,25 @@ pub enum SocketMessage {
 
     /// Update the Alacritty configuration.
     Config(IpcConfig),
+
+    /// Stream terminal content to STDOUT.
+    Subscribe(IpcSubscribe),
+
+    /// Temporarily apply the colors of a theme file.
+    PreviewTheme(IpcPreviewTheme),
+
+    /// Print the state of running windows as JSON.
+    Get(IpcGet),
+
+    /// Display text as if it was written by the shell.
+    Inject(IpcInject),
+
+    /// Move windows to another Alacritty instance.
+    Detach(IpcDetach),
+
+    /// Take over a window detached from another Alacritty instance.
+    #[clap(skip)]
+    Attach(IpcAttach),
 }
 
 /// Migrate the configuration file.
@@ -284,6 +343,26 @@ pub struct MigrateOptions {
     pub silent: bool,
 }
 
+/// Install the bundled terminfo entries.
+#[derive(Args, Clone, Debug)]
+pub struct TerminfoOptions {
+    /// Only output the terminfo source to STDOUT.
+    #[clap(short, long)]
+    pub print: bool,
+
+    /// Installation directory [default: $TERMINFO or ~/.terminfo].
+    #[clap(short, long, value_hint = ValueHint::DirPath)]
+    pub output: Option<PathBuf>,
+}
+
+/// Generate shell completions.
+#[derive(Args, Clone, Debug)]
+pub struct CompletionsOptions {
+    /// Shell to generate completions for.
+    #[clap(value_enum)]
+    pub shell: Shell,
+}
+
 /// Subset of options that we pass to 'create-window' IPC subcommand.
 #[derive(Serialize, Deserialize, Args, Default, Clone, Debug, PartialEq, Eq)]
 pub struct WindowOptions {
@@ -336,8 +415,176 @@ pub struct IpcConfig {
     pub reset: bool,
 }
 
+/// Parameters to the `preview-theme` IPC subcommand.
+#[cfg(unix)]
+#[derive(Args, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+pub struct IpcPreviewTheme {
+    /// Path to the theme's configuration file.
+    #[clap(required_unless_present_any = ["confirm", "revert"], value_hint = ValueHint::FilePath)]
+    pub path: Option<PathBuf>,
+
+    /// Time until the theme is reverted unless it was confirmed.
+    #[clap(short, long, default_value = "10s", value_parser = parse_duration)]
+    pub duration: Duration,
+
+    /// Keep the currently previewed theme.
+    #[clap(long, conflicts_with_all = ["path", "revert"])]
+    pub confirm: bool,
+
+    /// Revert the currently previewed theme.
+    #[clap(long, conflicts_with = "path")]
+    pub revert: bool,
+
+    /// Window ID for the theme preview.
+    ///
+    /// Use `-1` to preview the theme in all windows.
+    #[clap(short, long, allow_hyphen_values = true, env = "ALACRITTY_WINDOW_ID")]
+    pub window_id: Option<i128>,
+
+    /// Color section of the theme file as configuration option.
+    #[clap(skip)]
+    pub colors: Option<String>,
+}
+
+#[cfg(unix)]
+impl IpcPreviewTheme {
+    /// Load the color section from the theme file.
+    pub fn load_colors(&mut self) -> io::Result<()> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        let invalid_data = |err: String| io::Error::new(io::ErrorKind::InvalidData, err);
+
+        let theme = fs::read_to_string(path)?;
+        let mut theme: toml::Table =
+            toml::from_str(&theme).map_err(|err| invalid_data(err.to_string()))?;
+        let colors = theme
+            .remove("colors")
+            .ok_or_else(|| invalid_data(format!("no colors in {}", path.display())))?;
+
+        let option = toml::Table::from_iter([(String::from("colors"), colors)]);
+        self.colors = Some(toml::to_string(&option).map_err(|err| invalid_data(err.to_string()))?);
+
+        Ok(())
+    }
+}
+
+/// Parameters to the `subscribe` IPC subcommand.
+#[cfg(unix)]
+#[derive(Args, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+pub struct IpcSubscribe {
+    /// Terminal content which should be streamed.
+    #[clap(long, value_enum, default_value_t)]
+    pub stream: SubscribeStream,
+
+    /// Window ID of the subscribed terminal [default: all].
+    #[clap(short, long, allow_hyphen_values = true)]
+    pub window_id: Option<i128>,
+}
+
+/// Parameters to the `get` IPC subcommand.
+#[cfg(unix)]
+#[derive(Args, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+pub struct IpcGet {
+    /// Window state which should be reported [default: all].
+    #[clap(value_enum)]
+    pub fields: Vec<IpcGetField>,
+
+    /// Window ID of the queried terminal.
+    ///
+    /// Use `-1` to query all windows.
+    #[clap(short, long, allow_hyphen_values = true, env = "ALACRITTY_WINDOW_ID")]
+    pub window_id: Option<i128>,
+}
+
+#[cfg(unix)]
+impl IpcGet {
+    /// Check if a field should be reported.
+    pub fn wants(&self, field: IpcGetField) -> bool {
+        self.fields.is_empty() || self.fields.contains(&field)
+    }
+}
+
+/// Parameters to the `inject` IPC subcommand.
+#[cfg(unix)]
+#[derive(Args, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+pub struct IpcInject {
+    /// Text processed by the terminal, including escape sequences.
+    pub text: String,
+
+    /// Window ID of the terminal displaying the text.
+    ///
+    /// Use `-1` to inject into all windows.
+    #[clap(short, long, allow_hyphen_values = true, env = "ALACRITTY_WINDOW_ID")]
+    pub window_id: Option<i128>,
+}
+
+/// Parameters to the `detach` IPC subcommand.
+#[cfg(unix)]
+#[derive(Args, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+pub struct IpcDetach {
+    /// IPC socket of the Alacritty instance receiving the windows.
+    #[clap(value_hint = ValueHint::FilePath)]
+    pub target: PathBuf,
+
+    /// Window ID of the detached terminal.
+    ///
+    /// Use `-1` to detach all windows.
+    #[clap(short, long, allow_hyphen_values = true, env = "ALACRITTY_WINDOW_ID")]
+    pub window_id: Option<i128>,
+
+    /// Quit once all windows are detached, even in daemon mode.
+    #[clap(long)]
+    #[serde(default)]
+    pub quit: bool,
+}
+
+/// Window detached from another Alacritty instance.
+///
+/// The PTY is passed alongside this message, followed by the serialized terminal content.
+#[cfg(unix)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+pub struct IpcAttach {
+    /// Process ID of the terminal's shell.
+    pub pid: u32,
+}
+
+/// Window state available to IPC queries.
+#[cfg(unix)]
+#[derive(clap::ValueEnum, Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum IpcGetField {
+    /// Working directory of the foreground process.
+    WorkingDirectory,
+
+    /// Terminal size in cells and pixels.
+    Dimensions,
+
+    /// Window title.
+    Title,
+
+    /// Options set by the config files and runtime overrides.
+    Config,
+}
+
+/// Terminal content available for subscriptions.
+#[cfg(unix)]
+#[derive(clap::ValueEnum, Serialize, Deserialize, Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SubscribeStream {
+    /// Plain text of every line terminated by the application.
+    #[default]
+    Output,
+
+    /// JSON objects for window events, like title changes and bells.
+    Events,
+
+    /// JSON objects with the cells updated by every drawn frame.
+    Frames,
+}
+
 /// Parsed CLI config overrides.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct ParsedOptions {
     config_options: Vec<(String, Value)>,
 }
@@ -374,7 +621,12 @@ impl ParsedOptions {
                     );
                     self.config_options.swap_remove(i);
                 },
-                Ok(_) => i += 1,
+                Ok(_) => {
+                    if let Value::Table(table) = parsed {
+                        serde_utils::replace(&mut config.config_value, table.clone());
+                    }
+                    i += 1;
+                },
             }
         }
     }
@@ -408,6 +660,38 @@ impl DerefMut for ParsedOptions {
     }
 }
 
+/// Arguments for a new instance taking over all windows of the current instance.
+///
+/// The command of the initial window is dropped, since no initial window is created on restart.
+#[cfg(unix)]
+pub fn restart_args<I>(mut args: I, socket_path: &Path) -> Vec<OsString>
+where
+    I: Iterator<Item = OsString>,
+{
+    let mut restart_args = Vec::new();
+
+    while let Some(arg) = args.next() {
+        if arg == "-e" || arg == "--command" {
+            break;
+        }
+
+        // Replace the instance this one was restarted from.
+        if arg == "--restart-from" {
+            let _ = args.next();
+            continue;
+        } else if arg.to_str().is_some_and(|arg| arg.starts_with("--restart-from=")) {
+            continue;
+        }
+
+        restart_args.push(arg);
+    }
+
+    restart_args.push("--restart-from".into());
+    restart_args.push(socket_path.into());
+
+    restart_args
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -480,6 +764,16 @@ mod tests {
         assert_eq!(value, Value::Table(expected));
     }
 
+    #[test]
+    fn overrides_in_config_value() {
+        let mut config = UiConfig::default();
+        let options = [String::from("font.size=12.0"), String::from("font.offset.x=1")];
+        ParsedOptions::from_options(&options).override_config(&mut config);
+
+        let expected: Table = toml::from_str("font = { size = 12.0, offset = { x = 1 } }").unwrap();
+        assert_eq!(config.config_value, expected);
+    }
+
     #[test]
     fn parse_instance_class() {
         let class = parse_class("one").unwrap();
@@ -500,6 +794,15 @@ mod tests {
         assert!(class.is_err());
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn parse_preview_duration() {
+        assert_eq!(parse_duration("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("3"), Ok(Duration::from_secs(3)));
+        assert!(parse_duration("10m").is_err());
+    }
+
     #[test]
     fn valid_decimal() {
         let value = parse_hex_or_decimal("10485773");
@@ -518,6 +821,18 @@ mod tests {
         assert_eq!(value, None);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn restart_args_replace_previous_instance() {
+        let args = ["--daemon", "--restart-from", "/old.sock", "-o", "font.size=12", "-e", "ls"];
+        let args = restart_args(args.into_iter().map(OsString::from), Path::new("/new.sock"));
+        assert_eq!(args, ["--daemon", "-o", "font.size=12", "--restart-from", "/new.sock"]);
+
+        let args = ["--restart-from=/old.sock", "--hold"].into_iter().map(OsString::from);
+        let args = restart_args(args, Path::new("/new.sock"));
+        assert_eq!(args, ["--hold", "--restart-from", "/new.sock"]);
+    }
+
     #[cfg(target_os = "linux")]
     #[test]
     fn completions() {
@@ -0,0 +1,447 @@
+use std::collections::{HashMap, VecDeque};
+use std::error::Error as StdError;
+use std::path::Path;
+
+use log::{debug, warn};
+use winit::raw_window_handle::RawDisplayHandle;
+
+use alacritty_terminal::term::ClipboardType;
+
+use copypasta::nop_clipboard::NopClipboardContext;
+#[cfg(all(feature = "wayland", not(any(target_os = "macos", windows))))]
+use copypasta::wayland_clipboard;
+#[cfg(any(target_os = "macos", windows))]
+use copypasta::ClipboardContext;
+use copypasta::ClipboardProvider;
+
+#[cfg(all(feature = "wayland", not(any(target_os = "macos", windows))))]
+use crate::clipboard::wlr::DataControlContext;
+#[cfg(all(feature = "x11", not(any(target_os = "macos", windows))))]
+use crate::clipboard::x11::{SelectionKind, X11SelectionContext};
+use crate::config::clipboard::{ClipboardBackend, ClipboardConfig};
+
+#[cfg(all(feature = "wayland", not(any(target_os = "macos", windows))))]
+mod wlr;
+#[cfg(all(feature = "x11", not(any(target_os = "macos", windows))))]
+mod x11;
+
+/// Maximum number of paths in text offered as a list of files.
+const MAX_FILE_LIST_PATHS: usize = 64;
+
+/// Maximum length of text offered as a list of files.
+const MAX_FILE_LIST_LEN: usize = 16 * 1024;
+
+/// Result of clipboard provider operations.
+type ProviderResult<T> = Result<T, Box<dyn StdError + Send + Sync>>;
+
+pub struct Clipboard {
+    clipboard: Box<dyn Provider>,
+    selection: Option<Box<dyn Provider>>,
+
+    /// Named registers shared by all windows.
+    registers: HashMap<char, String>,
+
+    /// Recently copied text shared by all windows.
+    history: ClipboardHistory,
+}
+
+impl Clipboard {
+    pub unsafe fn new(display: RawDisplayHandle, config: &ClipboardConfig) -> Self {
+        let mut clipboard = match Self::with_backend(display, config.backend) {
+            Ok(clipboard) => clipboard,
+            Err(err) => {
+                warn!("Unable to use {:?} clipboard backend: {}", config.backend, err);
+                Self::with_backend(display, ClipboardBackend::Auto).unwrap_or_default()
+            },
+        };
+
+        clipboard.history.set_size(config.history);
+
+        // Emulate the primary selection for backends without one.
+        if clipboard.selection.is_none() && config.emulate_selection {
+            clipboard.selection = Some(Box::<InternalProvider>::default());
+        }
+
+        clipboard
+    }
+
+    /// Create a clipboard using a specific backend.
+    #[allow(unreachable_patterns)]
+    unsafe fn with_backend(
+        display: RawDisplayHandle,
+        backend: ClipboardBackend,
+    ) -> ProviderResult<Self> {
+        let (clipboard, selection): (Box<dyn Provider>, Option<Box<dyn Provider>>) =
+            match (backend, display) {
+                #[cfg(all(feature = "wayland", not(any(target_os = "macos", windows))))]
+                (
+                    ClipboardBackend::Auto | ClipboardBackend::Wayland,
+                    RawDisplayHandle::Wayland(display),
+                ) => {
+                    let (selection, clipboard) = wayland_clipboard::create_clipboards_from_external(
+                        display.display.as_ptr(),
+                    );
+                    (Box::new(TextProvider(clipboard)), Some(Box::new(TextProvider(selection))))
+                },
+                (ClipboardBackend::Auto, _) => return Ok(Self::default()),
+                #[cfg(all(feature = "x11", not(any(target_os = "macos", windows))))]
+                (ClipboardBackend::X11, _) => (
+                    Box::new(X11SelectionContext::new(SelectionKind::Clipboard)?),
+                    Some(Box::new(X11SelectionContext::new(SelectionKind::Primary)?)),
+                ),
+                #[cfg(all(feature = "wayland", not(any(target_os = "macos", windows))))]
+                (ClipboardBackend::WlrDataControl, _) => {
+                    let (clipboard, selection) = DataControlContext::new()?;
+                    (Box::new(clipboard), selection.map(|selection| Box::new(selection) as _))
+                },
+                (ClipboardBackend::Internal, _) => {
+                    (Box::<InternalProvider>::default(), Some(Box::<InternalProvider>::default()))
+                },
+                (ClipboardBackend::Wayland, _) => return Err("Wayland is not in use".into()),
+                (backend, _) => return Err(format!("{backend:?} is not supported").into()),
+            };
+
+        Ok(Self {
+            clipboard,
+            selection,
+            registers: Default::default(),
+            history: Default::default(),
+        })
+    }
+
+    /// Used for tests, to handle missing clipboard provider when built without the `x11`
+    /// feature, and as default clipboard value.
+    pub fn new_nop() -> Self {
+        let clipboard = NopClipboardContext::new().unwrap();
+        Self {
+            clipboard: Box::new(TextProvider(clipboard)),
+            selection: None,
+            registers: Default::default(),
+            history: Default::default(),
+        }
+    }
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        #[cfg(any(target_os = "macos", windows))]
+        return Self {
+            clipboard: Box::new(TextProvider(ClipboardContext::new().unwrap())),
+            selection: None,
+            registers: Default::default(),
+            history: Default::default(),
+        };
+
+        #[cfg(all(feature = "x11", not(any(target_os = "macos", windows))))]
+        return Self {
+            clipboard: Box::new(X11SelectionContext::new(SelectionKind::Clipboard).unwrap()),
+            selection: Some(Box::new(X11SelectionContext::new(SelectionKind::Primary).unwrap())),
+            registers: Default::default(),
+            history: Default::default(),
+        };
+
+        #[cfg(not(any(feature = "x11", target_os = "macos", windows)))]
+        return Self::new_nop();
+    }
+}
+
+impl Clipboard {
+    pub fn store(&mut self, ty: ClipboardType, text: impl Into<String>) {
+        self.store_content(ty, ClipboardContent::new(text.into()));
+    }
+
+    /// Store content which might be offered in multiple formats.
+    pub fn store_content(&mut self, ty: ClipboardType, content: ClipboardContent) {
+        if ty == ClipboardType::Clipboard {
+            self.history.push(&content.text);
+        }
+
+        let clipboard = match (ty, &mut self.selection) {
+            (ClipboardType::Selection, Some(provider)) => provider,
+            (ClipboardType::Selection, None) => return,
+            _ => &mut self.clipboard,
+        };
+
+        clipboard.set_contents(content).unwrap_or_else(|err| {
+            warn!("Unable to store text in clipboard: {}", err);
+        });
+    }
+
+    pub fn load(&mut self, ty: ClipboardType) -> String {
+        let clipboard = match (ty, &mut self.selection) {
+            (ClipboardType::Selection, Some(provider)) => provider,
+            _ => &mut self.clipboard,
+        };
+
+        match clipboard.get_contents() {
+            Err(err) => {
+                debug!("Unable to load text from clipboard: {}", err);
+                String::new()
+            },
+            Ok(text) => text,
+        }
+    }
+
+    /// Store text in a named register.
+    pub fn store_register(&mut self, register: char, text: String) {
+        self.registers.insert(register, text);
+    }
+
+    /// Load the text of a named register.
+    pub fn load_register(&self, register: char) -> String {
+        self.registers.get(&register).cloned().unwrap_or_default()
+    }
+
+    /// Change the maximum number of entries in the clipboard history.
+    pub fn set_history_size(&mut self, size: usize) {
+        self.history.set_size(size);
+    }
+
+    /// Load text from the clipboard history, starting at `0` for the most recent copy.
+    pub fn load_history(&self, index: usize) -> Option<&str> {
+        self.history.entries.get(index).map(String::as_str)
+    }
+
+    /// Entries of the clipboard history, most recent first.
+    pub fn history(&self) -> impl Iterator<Item = &str> {
+        self.history.entries.iter().map(String::as_str)
+    }
+}
+
+/// Recently copied text, most recent first.
+#[derive(Default)]
+struct ClipboardHistory {
+    entries: VecDeque<String>,
+
+    /// Maximum number of entries, with `0` disabling the history.
+    size: usize,
+}
+
+impl ClipboardHistory {
+    /// Add text to the history, moving it to the front if it was copied before.
+    fn push(&mut self, text: &str) {
+        if self.size == 0 || text.is_empty() {
+            return;
+        }
+
+        match self.entries.iter().position(|entry| entry == text) {
+            Some(index) => {
+                let entry = self.entries.remove(index).unwrap();
+                self.entries.push_front(entry);
+            },
+            None => self.entries.push_front(text.to_owned()),
+        }
+
+        self.entries.truncate(self.size);
+    }
+
+    fn set_size(&mut self, size: usize) {
+        self.size = size;
+        self.entries.truncate(size);
+    }
+}
+
+/// Content stored in the clipboard.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClipboardContent {
+    /// Plain text representation.
+    pub text: String,
+
+    /// `file://` URIs for text referencing existing files.
+    pub file_uris: Vec<String>,
+}
+
+impl ClipboardContent {
+    pub fn new(text: String) -> Self {
+        Self { text, file_uris: Vec::new() }
+    }
+
+    /// Create clipboard content, offering it as a list of files if every line of the text is an
+    /// absolute path to an existing file.
+    ///
+    /// Only short texts are checked, to avoid stalling on large selections.
+    pub fn with_file_detection(text: String) -> Self {
+        let file_uris = file_uris(&text).unwrap_or_default();
+        Self { text, file_uris }
+    }
+}
+
+/// Clipboard backend.
+trait Provider: Send {
+    /// Load the text content of the clipboard.
+    fn get_contents(&mut self) -> ProviderResult<String>;
+
+    /// Store content in the clipboard.
+    fn set_contents(&mut self, content: ClipboardContent) -> ProviderResult<()>;
+}
+
+/// Adapter for providers which can only offer plain text.
+struct TextProvider<T>(T);
+
+impl<T: ClipboardProvider> Provider for TextProvider<T> {
+    fn get_contents(&mut self) -> ProviderResult<String> {
+        self.0.get_contents()
+    }
+
+    fn set_contents(&mut self, content: ClipboardContent) -> ProviderResult<()> {
+        self.0.set_contents(content.text)
+    }
+}
+
+/// Content in the `text/uri-list` format.
+#[cfg(not(any(target_os = "macos", windows)))]
+fn uri_list(content: &ClipboardContent) -> Option<String> {
+    if content.file_uris.is_empty() {
+        return None;
+    }
+
+    let mut uri_list = content.file_uris.join("\r\n");
+    uri_list.push_str("\r\n");
+    Some(uri_list)
+}
+
+/// Content in the `x-special/gnome-copied-files` format.
+#[cfg(not(any(target_os = "macos", windows)))]
+fn gnome_copied_files(content: &ClipboardContent) -> Option<String> {
+    if content.file_uris.is_empty() {
+        return None;
+    }
+
+    Some(format!("copy\n{}", content.file_uris.join("\n")))
+}
+
+/// Clipboard which is only accessible within Alacritty.
+#[derive(Default)]
+struct InternalProvider {
+    content: Option<String>,
+}
+
+impl Provider for InternalProvider {
+    fn get_contents(&mut self) -> ProviderResult<String> {
+        Ok(self.content.clone().unwrap_or_default())
+    }
+
+    fn set_contents(&mut self, content: ClipboardContent) -> ProviderResult<()> {
+        self.content = Some(content.text);
+        Ok(())
+    }
+}
+
+/// Convert text with one existing absolute path per line to `file://` URIs.
+fn file_uris(text: &str) -> Option<Vec<String>> {
+    if text.len() > MAX_FILE_LIST_LEN {
+        return None;
+    }
+
+    let lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    let paths: Vec<_> = lines.map(Path::new).take(MAX_FILE_LIST_PATHS + 1).collect();
+
+    // Only access the filesystem once the entire text looks like a short list of paths.
+    if paths.is_empty()
+        || paths.len() > MAX_FILE_LIST_PATHS
+        || !paths.iter().all(|path| path.is_absolute())
+    {
+        return None;
+    }
+
+    paths.into_iter().map(|path| path.exists().then(|| file_uri(path)).flatten()).collect()
+}
+
+/// Create a percent-encoded `file://` URI for an absolute path.
+fn file_uri(path: &Path) -> Option<String> {
+    let path = path.to_str()?;
+
+    let mut uri = String::from("file://");
+
+    // Windows paths are rooted at their drive letter.
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+
+    for byte in path.bytes() {
+        match byte {
+            b'\\' if cfg!(windows) => uri.push('/'),
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' | b':' => {
+                uri.push(byte as char)
+            },
+            _ => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+
+    Some(uri)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs::File;
+
+    #[test]
+    fn existing_files_are_detected() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("first");
+        let second = dir.path().join("with space");
+        File::create(&first).unwrap();
+        File::create(&second).unwrap();
+
+        let text = format!("{}\n{}\n", first.display(), second.display());
+        let uris = file_uris(&text).unwrap();
+
+        assert_eq!(uris.len(), 2);
+        assert_eq!(uris[0], format!("file://{}", first.display()));
+        assert!(uris[1].ends_with("/with%20space"));
+    }
+
+    #[test]
+    fn missing_files_are_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("existing");
+        File::create(&existing).unwrap();
+
+        let text = format!("{}\n{}", existing.display(), dir.path().join("missing").display());
+
+        assert_eq!(file_uris(&text), None);
+    }
+
+    #[test]
+    fn relative_and_long_paths_are_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("file");
+        File::create(&file).unwrap();
+
+        assert_eq!(file_uris("~"), None);
+        assert_eq!(file_uris("file"), None);
+
+        let line = format!("{}\n", file.display());
+        assert!(file_uris(&line.repeat(MAX_FILE_LIST_PATHS)).is_some());
+        assert_eq!(file_uris(&line.repeat(MAX_FILE_LIST_PATHS + 1)), None);
+    }
+
+    #[test]
+    fn history() {
+        let mut clipboard = Clipboard::new_nop();
+        clipboard.store(ClipboardType::Clipboard, "ignored");
+        assert_eq!(clipboard.load_history(0), None);
+
+        clipboard.set_history_size(3);
+        for text in ["first", "second", "third", "first", "fourth"] {
+            clipboard.store(ClipboardType::Clipboard, text);
+        }
+        clipboard.store(ClipboardType::Selection, "selection");
+
+        assert_eq!(clipboard.history().collect::<Vec<_>>(), ["fourth", "first", "third"]);
+        assert_eq!(clipboard.load_history(2), Some("third"));
+        assert_eq!(clipboard.load_history(3), None);
+
+        clipboard.set_history_size(1);
+        assert_eq!(clipboard.history().collect::<Vec<_>>(), ["fourth"]);
+    }
+
+    #[test]
+    fn text_is_not_files() {
+        assert_eq!(file_uris("hello world"), None);
+        assert_eq!(file_uris("relative/path"), None);
+        assert_eq!(file_uris(""), None);
+    }
+}
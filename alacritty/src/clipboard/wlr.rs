@@ -0,0 +1,270 @@
+//! Wayland clipboard provider using the wlr data control protocol.
+//!
+//! Unlike the regular Wayland clipboard, data control does not require the window to be focused.
+//! This allows clipboard managers to pick up the content, even after Alacritty is closed.
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::os::fd::AsFd;
+use std::os::unix::net::UnixStream;
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::debug;
+use parking_lot::Mutex;
+use wayland_client::globals::{registry_queue_init, GlobalListContents};
+use wayland_client::protocol::wl_registry::WlRegistry;
+use wayland_client::protocol::wl_seat::WlSeat;
+use wayland_client::{
+    delegate_noop, event_created_child, Connection, Dispatch, Proxy, QueueHandle,
+};
+use wayland_protocols_wlr::data_control::v1::client::zwlr_data_control_device_v1::{
+    self, ZwlrDataControlDeviceV1,
+};
+use wayland_protocols_wlr::data_control::v1::client::zwlr_data_control_manager_v1::ZwlrDataControlManagerV1;
+use wayland_protocols_wlr::data_control::v1::client::zwlr_data_control_offer_v1::{
+    self, ZwlrDataControlOfferV1,
+};
+use wayland_protocols_wlr::data_control::v1::client::zwlr_data_control_source_v1::{
+    self, ZwlrDataControlSourceV1,
+};
+
+use alacritty_terminal::thread as alacritty_thread;
+
+use crate::clipboard::{gnome_copied_files, uri_list, ClipboardContent, Provider, ProviderResult};
+
+/// Maximum time without progress before a selection transfer is aborted.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// MIME types for plain text, in order of preference.
+const TEXT_MIME_TYPES: [&str; 5] =
+    ["text/plain;charset=utf-8", "UTF8_STRING", "text/plain", "STRING", "TEXT"];
+
+/// Wayland selection buffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SelectionKind {
+    Primary,
+    Clipboard,
+}
+
+/// Clipboard provider for a single selection using the wlr data control protocol.
+pub struct DataControlContext {
+    shared: Arc<Shared>,
+    kind: SelectionKind,
+}
+
+impl DataControlContext {
+    /// Create providers for the clipboard and the primary selection.
+    ///
+    /// The primary selection is only available if the compositor supports it.
+    pub fn new() -> ProviderResult<(Self, Option<Self>)> {
+        let connection = Connection::connect_to_env()?;
+        let (globals, mut queue) = registry_queue_init::<State>(&connection)?;
+        let queue_handle = queue.handle();
+
+        let manager: ZwlrDataControlManagerV1 = globals.bind(&queue_handle, 1..=2, ())?;
+        let seat: WlSeat = globals.bind(&queue_handle, 1..=1, ())?;
+        let device = manager.get_data_device(&seat, &queue_handle, ());
+
+        // Receive the current selections before the providers are used.
+        let mut state = State::default();
+        queue.roundtrip(&mut state)?;
+        let offers = state.offers.clone();
+
+        alacritty_thread::spawn_named("wlr data control", move || loop {
+            if let Err(err) = queue.blocking_dispatch(&mut state) {
+                debug!("Stopped wlr data control dispatch: {err}");
+                break;
+            }
+        });
+
+        let has_primary = manager.version() >= 2;
+        let shared = Arc::new(Shared { connection, queue_handle, manager, device, offers });
+
+        let selection =
+            has_primary.then(|| Self { shared: shared.clone(), kind: SelectionKind::Primary });
+        Ok((Self { shared, kind: SelectionKind::Clipboard }, selection))
+    }
+}
+
+impl Provider for DataControlContext {
+    fn get_contents(&mut self) -> ProviderResult<String> {
+        let offers = self.shared.offers.lock();
+        let offer = match self.kind {
+            SelectionKind::Clipboard => offers.clipboard.clone(),
+            SelectionKind::Primary => offers.primary.clone(),
+        };
+        drop(offers);
+
+        let (offer, mime_types) = offer.ok_or("selection is empty")?;
+        let mime_type = TEXT_MIME_TYPES
+            .into_iter()
+            .find(|text| mime_types.lock().iter().any(|mime_type| mime_type == text))
+            .ok_or("selection contains no text")?;
+
+        let (mut reader, writer) = UnixStream::pair()?;
+        offer.receive(mime_type.into(), writer.as_fd());
+        self.shared.connection.flush()?;
+
+        // Close our end of the pipe, so the transfer ends once the selection owner is done.
+        drop(writer);
+
+        let mut content = Vec::new();
+        reader.set_read_timeout(Some(IDLE_TIMEOUT))?;
+        reader.read_to_end(&mut content)?;
+
+        Ok(String::from_utf8(content)?)
+    }
+
+    fn set_contents(&mut self, content: ClipboardContent) -> ProviderResult<()> {
+        let shared = &self.shared;
+        let offers_files = !content.file_uris.is_empty();
+        let source = shared.manager.create_data_source(&shared.queue_handle, content);
+
+        for mime_type in TEXT_MIME_TYPES {
+            source.offer(mime_type.into());
+        }
+        if offers_files {
+            source.offer("text/uri-list".into());
+            source.offer("x-special/gnome-copied-files".into());
+        }
+
+        match self.kind {
+            SelectionKind::Clipboard => shared.device.set_selection(Some(&source)),
+            SelectionKind::Primary => shared.device.set_primary_selection(Some(&source)),
+        }
+
+        Ok(shared.connection.flush()?)
+    }
+}
+
+/// Wayland objects shared by the clipboard and primary selection providers.
+struct Shared {
+    connection: Connection,
+    queue_handle: QueueHandle<State>,
+    manager: ZwlrDataControlManagerV1,
+    device: ZwlrDataControlDeviceV1,
+    offers: Arc<Mutex<Offers>>,
+}
+
+/// Selection offer with its MIME types.
+type Offer = (ZwlrDataControlOfferV1, Arc<Mutex<Vec<String>>>);
+
+/// Current selections of the seat.
+#[derive(Default)]
+struct Offers {
+    clipboard: Option<Offer>,
+    primary: Option<Offer>,
+}
+
+/// State of the Wayland event dispatch thread.
+#[derive(Default)]
+struct State {
+    offers: Arc<Mutex<Offers>>,
+}
+
+/// Replace the offer of a selection, destroying the previous one.
+fn replace_offer(selection: &mut Option<Offer>, offer: Option<ZwlrDataControlOfferV1>) {
+    let offer = offer.map(|offer| {
+        let mime_types = offer.data::<Arc<Mutex<Vec<String>>>>().cloned().unwrap_or_default();
+        (offer, mime_types)
+    });
+
+    if let Some((previous, _)) = std::mem::replace(selection, offer) {
+        previous.destroy();
+    }
+}
+
+impl Dispatch<ZwlrDataControlDeviceV1, ()> for State {
+    event_created_child!(State, ZwlrDataControlDeviceV1, [
+        zwlr_data_control_device_v1::EVT_DATA_OFFER_OPCODE => (
+            ZwlrDataControlOfferV1,
+            Arc::<Mutex<Vec<String>>>::default()
+        )
+    ]);
+
+    fn event(
+        state: &mut Self,
+        _device: &ZwlrDataControlDeviceV1,
+        event: zwlr_data_control_device_v1::Event,
+        _data: &(),
+        _connection: &Connection,
+        _queue_handle: &QueueHandle<Self>,
+    ) {
+        let mut offers = state.offers.lock();
+        match event {
+            zwlr_data_control_device_v1::Event::Selection { id } => {
+                replace_offer(&mut offers.clipboard, id);
+            },
+            zwlr_data_control_device_v1::Event::PrimarySelection { id } => {
+                replace_offer(&mut offers.primary, id);
+            },
+            zwlr_data_control_device_v1::Event::Finished => {
+                replace_offer(&mut offers.clipboard, None);
+                replace_offer(&mut offers.primary, None);
+            },
+            _ => (),
+        }
+    }
+}
+
+impl Dispatch<ZwlrDataControlOfferV1, Arc<Mutex<Vec<String>>>> for State {
+    fn event(
+        _state: &mut Self,
+        _offer: &ZwlrDataControlOfferV1,
+        event: zwlr_data_control_offer_v1::Event,
+        mime_types: &Arc<Mutex<Vec<String>>>,
+        _connection: &Connection,
+        _queue_handle: &QueueHandle<Self>,
+    ) {
+        if let zwlr_data_control_offer_v1::Event::Offer { mime_type } = event {
+            mime_types.lock().push(mime_type);
+        }
+    }
+}
+
+impl Dispatch<ZwlrDataControlSourceV1, ClipboardContent> for State {
+    fn event(
+        _state: &mut Self,
+        source: &ZwlrDataControlSourceV1,
+        event: zwlr_data_control_source_v1::Event,
+        content: &ClipboardContent,
+        _connection: &Connection,
+        _queue_handle: &QueueHandle<Self>,
+    ) {
+        match event {
+            zwlr_data_control_source_v1::Event::Send { mime_type, fd } => {
+                let data = match mime_type.as_str() {
+                    "text/uri-list" => uri_list(content),
+                    "x-special/gnome-copied-files" => gnome_copied_files(content),
+                    _ => Some(content.text.clone()),
+                };
+
+                // Write in the background, to avoid blocking on slow readers.
+                let data = data.unwrap_or_default();
+                alacritty_thread::spawn_named("wlr data control transfer", move || {
+                    if let Err(err) = File::from(fd).write_all(data.as_bytes()) {
+                        debug!("Unable to send selection: {err}");
+                    }
+                });
+            },
+            zwlr_data_control_source_v1::Event::Cancelled => source.destroy(),
+            _ => (),
+        }
+    }
+}
+
+impl Dispatch<WlRegistry, GlobalListContents> for State {
+    fn event(
+        _state: &mut Self,
+        _registry: &WlRegistry,
+        _event: <WlRegistry as Proxy>::Event,
+        _data: &GlobalListContents,
+        _connection: &Connection,
+        _queue_handle: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+delegate_noop!(State: ignore WlSeat);
+delegate_noop!(State: ZwlrDataControlManagerV1);
@@ -0,0 +1,912 @@
+//! X11 selection provider with incremental transfer support.
+//!
+//! Both directions of the INCR protocol are implemented here, allowing large selections to be
+//! transferred as long as the other client keeps making progress, while still aborting transfers
+//! with stalled clients.
+
+use std::cmp;
+use std::error::Error as StdError;
+use std::fmt::{self, Display, Formatter};
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+use log::debug;
+use parking_lot::Mutex;
+use x11_clipboard::error::Error as X11Error;
+use x11_clipboard::{Atom, Context};
+use x11rb::connection::{Connection, RequestConnection};
+use x11rb::protocol::xproto::{
+    AtomEnum, ChangeWindowAttributesAux, ConnectionExt, EventMask, PropMode, Property,
+    SelectionNotifyEvent, SelectionRequestEvent, SELECTION_NOTIFY_EVENT,
+};
+use x11rb::protocol::Event;
+use x11rb::wrapper::ConnectionExt as _;
+use x11rb::CURRENT_TIME;
+
+use alacritty_terminal::thread as alacritty_thread;
+
+use crate::clipboard::{gnome_copied_files, uri_list, ClipboardContent, Provider, ProviderResult};
+
+/// Maximum time without progress before a selection transfer is aborted.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Maximum total duration of a selection transfer.
+const TRANSFER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Maximum time the selection owner thread waits for new events.
+const OWNER_POLL_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// Size of the chunks sent to other clients using the INCR protocol.
+const INCR_CHUNK_SIZE: usize = 4096;
+
+/// X11 selection buffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SelectionKind {
+    Primary,
+    Clipboard,
+}
+
+/// Clipboard provider for a single X11 selection.
+pub struct X11SelectionContext {
+    getter: Context,
+    owner: Arc<Owner>,
+    kind: SelectionKind,
+}
+
+impl X11SelectionContext {
+    pub fn new(kind: SelectionKind) -> Result<Self, X11Error> {
+        Ok(Self { getter: Context::new(None)?, owner: Owner::new()?, kind })
+    }
+
+    fn selection_atom(&self) -> Atom {
+        let atoms = &self.getter.atoms;
+        match self.kind {
+            SelectionKind::Primary => atoms.primary,
+            SelectionKind::Clipboard => atoms.clipboard,
+        }
+    }
+}
+
+impl Provider for X11SelectionContext {
+    fn get_contents(&mut self) -> ProviderResult<String> {
+        let atoms = &self.getter.atoms;
+        let (target, incr, property) = (atoms.utf8_string, atoms.incr, atoms.property);
+        let data = load(&self.getter, self.selection_atom(), target, incr, property)?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn set_contents(&mut self, content: ClipboardContent) -> ProviderResult<()> {
+        let mut offer = Vec::new();
+
+        let context = &self.owner.context;
+        if let Some(uri_list) = uri_list(&content) {
+            offer.push((context.get_atom("text/uri-list")?, uri_list.into_bytes().into()));
+        }
+        if let Some(files) = gnome_copied_files(&content) {
+            let target = context.get_atom("x-special/gnome-copied-files")?;
+            offer.push((target, files.into_bytes().into()));
+        }
+
+        let text: Arc<[u8]> = content.text.into_bytes().into();
+        offer.push((context.atoms.utf8_string, text.clone()));
+        offer.push((context.get_atom("text/plain;charset=utf-8")?, text));
+
+        Ok(self.owner.store(self.selection_atom(), offer)?)
+    }
+}
+
+/// X11 requests used to load a selection.
+trait Requestor {
+    /// Ask the selection owner to store the selection in `property`.
+    ///
+    /// Returns the sequence number of the request.
+    fn convert_selection(
+        &self,
+        selection: Atom,
+        target: Atom,
+        property: Atom,
+    ) -> Result<u64, X11Error>;
+
+    /// Wait for the next event received after the request with the `sequence` number.
+    ///
+    /// Returns `None` if no event was received within the `timeout`.
+    fn next_event(&self, sequence: u64, timeout: Duration) -> Result<Option<Event>, X11Error>;
+
+    /// Get the type and value of a property, optionally deleting it.
+    fn get_property(&self, property: Atom, delete: bool) -> Result<(Atom, Vec<u8>), X11Error>;
+
+    /// Delete a property.
+    fn delete_property(&self, property: Atom) -> Result<(), X11Error>;
+}
+
+impl Requestor for Context {
+    fn convert_selection(
+        &self,
+        selection: Atom,
+        target: Atom,
+        property: Atom,
+    ) -> Result<u64, X11Error> {
+        let cookie = self.connection.convert_selection(
+            self.window,
+            selection,
+            target,
+            property,
+            CURRENT_TIME,
+        )?;
+        let sequence_number = cookie.sequence_number();
+        cookie.check()?;
+        Ok(sequence_number)
+    }
+
+    fn next_event(&self, sequence: u64, timeout: Duration) -> Result<Option<Event>, X11Error> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.connection.poll_for_event_with_sequence()? {
+                // Ignore events which were queued before our request.
+                Some((_, seq)) if seq < sequence => continue,
+                Some((event, _)) => return Ok(Some(event)),
+                None => {
+                    let timeout = deadline.saturating_duration_since(Instant::now());
+                    if !wait_readable(self.connection.stream().as_fd(), timeout) {
+                        return Ok(None);
+                    }
+                },
+            }
+        }
+    }
+
+    fn get_property(&self, property: Atom, delete: bool) -> Result<(Atom, Vec<u8>), X11Error> {
+        let reply = self
+            .connection
+            .get_property(delete, self.window, property, AtomEnum::ANY, 0, u32::MAX)?
+            .reply()?;
+        Ok((reply.type_, reply.value))
+    }
+
+    fn delete_property(&self, property: Atom) -> Result<(), X11Error> {
+        self.connection.delete_property(self.window, property)?.check()?;
+        Ok(())
+    }
+}
+
+/// Request the selection content from its current owner.
+fn load<R: Requestor>(
+    requestor: &R,
+    selection: Atom,
+    target: Atom,
+    incr: Atom,
+    property: Atom,
+) -> Result<Vec<u8>, TransferError> {
+    let sequence = requestor.convert_selection(selection, target, property)?;
+
+    let mut transfer = Transfer::new(target, incr, Instant::now());
+    loop {
+        let now = Instant::now();
+        transfer.check_timeout(now)?;
+
+        let event = match requestor.next_event(sequence, transfer.remaining(now))? {
+            Some(event) => event,
+            None => continue,
+        };
+
+        let step = match event {
+            Event::SelectionNotify(event) if event.selection == selection => {
+                // A property of `None` indicates that the owner refused the conversion.
+                if event.property == Atom::from(AtomEnum::NONE) {
+                    return Err(TransferError::Refused);
+                }
+
+                let (ty, value) = requestor.get_property(property, false)?;
+                let step = transfer.start(ty, value, Instant::now())?;
+
+                // Deleting the property signals the owner to send the first INCR chunk.
+                requestor.delete_property(property)?;
+
+                step
+            },
+            Event::PropertyNotify(event)
+                if transfer.is_incremental()
+                    && event.atom == property
+                    && event.state == Property::NEW_VALUE =>
+            {
+                // Deleting the property signals the owner to send the next INCR chunk.
+                let (ty, value) = requestor.get_property(property, true)?;
+                transfer.chunk(ty, &value, Instant::now())
+            },
+            _ => TransferStep::Pending,
+        };
+
+        if step == TransferStep::Done {
+            return Ok(transfer.into_data());
+        }
+    }
+}
+
+/// Wait until a file descriptor is readable.
+///
+/// Returns `false` if the `timeout` expired first.
+fn wait_readable(fd: BorrowedFd<'_>, timeout: Duration) -> bool {
+    let mut pollfd = libc::pollfd { fd: fd.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+    let timeout = timeout.as_millis().min(libc::c_int::MAX as u128) as libc::c_int;
+
+    // SAFETY: The pollfd is valid for the duration of the call.
+    unsafe { libc::poll(&mut pollfd, 1, timeout) > 0 }
+}
+
+/// Data offered for a selection, in every supported target format.
+type Offer = Vec<(Atom, Arc<[u8]>)>;
+
+/// Owner of a selection, serving its content to other clients.
+struct Owner {
+    context: Context,
+    offer: Mutex<Offer>,
+}
+
+impl Owner {
+    fn new() -> Result<Arc<Self>, X11Error> {
+        let owner = Arc::new(Self { context: Context::new(None)?, offer: Default::default() });
+
+        let weak = Arc::downgrade(&owner);
+        alacritty_thread::spawn_named("X11 selection owner", move || serve(weak));
+
+        Ok(owner)
+    }
+
+    /// Take ownership of a selection.
+    fn store(&self, selection: Atom, offer: Offer) -> Result<(), X11Error> {
+        *self.offer.lock() = offer;
+
+        let connection = &self.context.connection;
+        connection.set_selection_owner(self.context.window, selection, CURRENT_TIME)?.check()?;
+
+        if connection.get_selection_owner(selection)?.reply()?.owner == self.context.window {
+            Ok(())
+        } else {
+            Err(X11Error::Owner)
+        }
+    }
+
+    /// Answer another client's request for the selection content.
+    fn handle_request(
+        &self,
+        event: SelectionRequestEvent,
+        transfers: &mut Vec<OutgoingTransfer>,
+    ) -> Result<(), X11Error> {
+        let connection = &self.context.connection;
+        let atoms = &self.context.atoms;
+
+        // Obsolete clients might not specify a property.
+        let property = if event.property == Atom::from(AtomEnum::NONE) {
+            event.target
+        } else {
+            event.property
+        };
+
+        let offer = self.offer.lock();
+        let converted = if offer.is_empty() {
+            false
+        } else if event.target == atoms.targets {
+            let mut targets = vec![atoms.targets];
+            targets.extend(offer.iter().map(|(target, _)| *target));
+            connection.change_property32(
+                PropMode::REPLACE,
+                event.requestor,
+                property,
+                AtomEnum::ATOM,
+                &targets,
+            )?;
+            true
+        } else if let Some((target, data)) = offer.iter().find(|(ty, _)| *ty == event.target) {
+            // Use the INCR protocol for data which does not fit into a single request.
+            let max_length = connection.maximum_request_bytes().saturating_sub(24);
+            if data.len() < max_length {
+                connection.change_property8(
+                    PropMode::REPLACE,
+                    event.requestor,
+                    property,
+                    *target,
+                    data,
+                )?;
+            } else {
+                let aux = ChangeWindowAttributesAux::new().event_mask(EventMask::PROPERTY_CHANGE);
+                connection.change_window_attributes(event.requestor, &aux)?;
+                connection.change_property32(
+                    PropMode::REPLACE,
+                    event.requestor,
+                    property,
+                    atoms.incr,
+                    &[data.len() as u32],
+                )?;
+
+                let transfer =
+                    OutgoingTransfer::new(event.requestor, property, *target, data.clone());
+                transfers.push(transfer);
+            }
+            true
+        } else {
+            false
+        };
+
+        let property = if converted { property } else { Atom::from(AtomEnum::NONE) };
+        let notify = SelectionNotifyEvent {
+            response_type: SELECTION_NOTIFY_EVENT,
+            sequence: 0,
+            time: event.time,
+            requestor: event.requestor,
+            selection: event.selection,
+            target: event.target,
+            property,
+        };
+        connection.send_event(false, event.requestor, EventMask::NO_EVENT, notify)?;
+
+        Ok(())
+    }
+
+    /// Send the next chunk of an incremental transfer.
+    fn send_chunk(&self, transfer: &mut OutgoingTransfer) -> Result<(), X11Error> {
+        let (requestor, property, target) =
+            (transfer.requestor, transfer.property, transfer.target);
+        let chunk = transfer.next_chunk(Instant::now());
+        self.context.connection.change_property8(
+            PropMode::REPLACE,
+            requestor,
+            property,
+            target,
+            chunk,
+        )?;
+        Ok(())
+    }
+
+    /// Handle a single X11 event.
+    fn handle_event(&self, event: Event, transfers: &mut Vec<OutgoingTransfer>) {
+        let result = match event {
+            Event::SelectionRequest(event) => self.handle_request(event, transfers),
+            // Deleting the property signals that the requestor is ready for the next chunk.
+            Event::PropertyNotify(event) if event.state == Property::DELETE => {
+                let index = transfers.iter().position(|transfer| {
+                    transfer.requestor == event.window && transfer.property == event.atom
+                });
+
+                match index {
+                    Some(index) => {
+                        let result = self.send_chunk(&mut transfers[index]);
+                        if transfers[index].is_done() {
+                            transfers.swap_remove(index);
+                        }
+                        result
+                    },
+                    None => Ok(()),
+                }
+            },
+            Event::SelectionClear(_) => {
+                self.offer.lock().clear();
+                Ok(())
+            },
+            _ => Ok(()),
+        };
+
+        if let Err(err) = result {
+            debug!("Unable to serve X11 selection: {err}");
+        }
+    }
+}
+
+/// Serve selection requests until the owner is dropped.
+fn serve(owner: Weak<Owner>) {
+    let fd = match owner.upgrade() {
+        Some(owner) => owner.context.connection.stream().as_fd().as_raw_fd(),
+        None => return,
+    };
+
+    let mut transfers = Vec::new();
+    loop {
+        // SAFETY: The connection is only closed once the owner is dropped, which is checked
+        // before the file descriptor is used again.
+        wait_readable(unsafe { BorrowedFd::borrow_raw(fd) }, OWNER_POLL_TIMEOUT);
+
+        // Stop once the owner is gone, before the file descriptor is accessed again.
+        let owner = match owner.upgrade() {
+            Some(owner) => owner,
+            None => return,
+        };
+
+        // Abort transfers to clients which stopped reading.
+        let now = Instant::now();
+        transfers.retain(|transfer: &OutgoingTransfer| !transfer.timed_out(now));
+
+        loop {
+            match owner.context.connection.poll_for_event() {
+                Ok(Some(event)) => owner.handle_event(event, &mut transfers),
+                Ok(None) => break,
+                Err(_) => return,
+            }
+        }
+
+        let _ = owner.context.connection.flush();
+    }
+}
+
+/// State of an incremental transfer to another client.
+#[derive(Debug)]
+struct OutgoingTransfer {
+    requestor: u32,
+    property: Atom,
+    target: Atom,
+    data: Arc<[u8]>,
+    position: usize,
+    done: bool,
+    started: Instant,
+    last_progress: Instant,
+}
+
+impl OutgoingTransfer {
+    fn new(requestor: u32, property: Atom, target: Atom, data: Arc<[u8]>) -> Self {
+        let now = Instant::now();
+        Self {
+            requestor,
+            property,
+            target,
+            data,
+            position: 0,
+            done: false,
+            started: now,
+            last_progress: now,
+        }
+    }
+
+    /// Get the next chunk, which is empty once all data has been sent.
+    fn next_chunk(&mut self, now: Instant) -> &[u8] {
+        let len = cmp::min(INCR_CHUNK_SIZE, self.data.len() - self.position);
+        let chunk = &self.data[self.position..self.position + len];
+
+        self.done = len == 0;
+        self.position += len;
+        self.last_progress = now;
+
+        chunk
+    }
+
+    /// Whether the terminating empty chunk has been sent.
+    fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Check whether the transfer should be aborted.
+    fn timed_out(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.last_progress) >= IDLE_TIMEOUT
+            || now.saturating_duration_since(self.started) >= TRANSFER_TIMEOUT
+    }
+}
+
+/// Progress of a selection transfer after handling an event.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum TransferStep {
+    /// More data is expected from the selection owner.
+    Pending,
+
+    /// The transfer is complete.
+    Done,
+}
+
+/// State of an in-flight selection transfer.
+#[derive(Debug)]
+struct Transfer {
+    data: Vec<u8>,
+    target: Atom,
+    incr: Atom,
+    incremental: bool,
+    started: Instant,
+    last_progress: Instant,
+}
+
+impl Transfer {
+    fn new(target: Atom, incr: Atom, now: Instant) -> Self {
+        Self {
+            target,
+            incr,
+            data: Vec::new(),
+            incremental: false,
+            started: now,
+            last_progress: now,
+        }
+    }
+
+    /// Whether the owner is sending the selection using the INCR protocol.
+    fn is_incremental(&self) -> bool {
+        self.incremental
+    }
+
+    /// Handle the owner's initial reply to the conversion request.
+    fn start(
+        &mut self,
+        ty: Atom,
+        value: Vec<u8>,
+        now: Instant,
+    ) -> Result<TransferStep, TransferError> {
+        self.last_progress = now;
+
+        if ty == self.incr {
+            // The INCR property holds a lower bound for the total transfer size.
+            if let Some(size) = value.get(..4) {
+                let size = u32::from_ne_bytes([size[0], size[1], size[2], size[3]]);
+                self.data.reserve(size as usize);
+            }
+
+            self.incremental = true;
+            Ok(TransferStep::Pending)
+        } else if ty == self.target {
+            self.data = value;
+            Ok(TransferStep::Done)
+        } else {
+            Err(TransferError::UnexpectedType(ty))
+        }
+    }
+
+    /// Handle a single chunk of an incremental transfer.
+    fn chunk(&mut self, ty: Atom, value: &[u8], now: Instant) -> TransferStep {
+        // A zero-length chunk terminates the transfer.
+        if value.is_empty() {
+            return TransferStep::Done;
+        }
+
+        // Skip property changes which are not part of this transfer.
+        if ty != self.target {
+            return TransferStep::Pending;
+        }
+
+        self.data.extend_from_slice(value);
+        self.last_progress = now;
+
+        TransferStep::Pending
+    }
+
+    /// Time until the transfer is aborted, unless the owner makes progress.
+    fn remaining(&self, now: Instant) -> Duration {
+        let deadline = cmp::min(self.last_progress + IDLE_TIMEOUT, self.started + TRANSFER_TIMEOUT);
+        deadline.saturating_duration_since(now)
+    }
+
+    /// Check whether the transfer should be aborted.
+    fn check_timeout(&self, now: Instant) -> Result<(), TransferError> {
+        if now.saturating_duration_since(self.last_progress) >= IDLE_TIMEOUT
+            || now.saturating_duration_since(self.started) >= TRANSFER_TIMEOUT
+        {
+            Err(TransferError::Timeout { received: self.data.len() })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+/// Errors occurring while loading an X11 selection.
+#[derive(Debug)]
+pub enum TransferError {
+    /// Selection owner stopped responding.
+    Timeout { received: usize },
+
+    /// Selection owner could not convert the selection to text.
+    Refused,
+
+    /// Selection owner replied with an unexpected property type.
+    UnexpectedType(Atom),
+
+    /// Error communicating with the X server.
+    X11(X11Error),
+}
+
+impl StdError for TransferError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::X11(err) => err.source(),
+            Self::Timeout { .. } | Self::Refused | Self::UnexpectedType(_) => None,
+        }
+    }
+}
+
+impl Display for TransferError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout { received } => {
+                write!(f, "Selection transfer timed out after {received} bytes")
+            },
+            Self::Refused => write!(f, "Selection owner refused the conversion to text"),
+            Self::UnexpectedType(ty) => write!(f, "Unexpected selection property type {ty}"),
+            Self::X11(err) => write!(f, "X11 error: {err}"),
+        }
+    }
+}
+
+impl<T: Into<X11Error>> From<T> for TransferError {
+    fn from(val: T) -> Self {
+        Self::X11(val.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+
+    use x11rb::protocol::xproto::{PropertyNotifyEvent, PROPERTY_NOTIFY_EVENT};
+
+    const TARGET: Atom = 1;
+    const INCR: Atom = 2;
+    const OTHER: Atom = 3;
+    const SELECTION: Atom = 4;
+    const PROPERTY: Atom = 5;
+
+    /// Selection owner answering a requestor without an X server.
+    struct MockOwner {
+        data: Option<Arc<[u8]>>,
+        max_length: usize,
+        events: RefCell<VecDeque<Event>>,
+        property: RefCell<Option<(Atom, Vec<u8>)>>,
+        transfer: RefCell<Option<OutgoingTransfer>>,
+    }
+
+    impl MockOwner {
+        fn new(data: Option<&[u8]>, max_length: usize) -> Self {
+            Self {
+                data: data.map(Arc::from),
+                max_length,
+                events: Default::default(),
+                property: Default::default(),
+                transfer: Default::default(),
+            }
+        }
+
+        /// Store a new property value, notifying the requestor.
+        fn set_property(&self, ty: Atom, value: Vec<u8>) {
+            *self.property.borrow_mut() = Some((ty, value));
+            self.events.borrow_mut().push_back(Event::PropertyNotify(PropertyNotifyEvent {
+                response_type: PROPERTY_NOTIFY_EVENT,
+                sequence: 0,
+                window: 0,
+                atom: PROPERTY,
+                time: 0,
+                state: Property::NEW_VALUE,
+            }));
+        }
+
+        /// Send the next INCR chunk once the requestor deleted the property.
+        fn on_delete(&self) {
+            let mut transfer = self.transfer.borrow_mut();
+            if let Some(transfer) = transfer.as_mut().filter(|transfer| !transfer.is_done()) {
+                let chunk = transfer.next_chunk(Instant::now()).to_vec();
+                self.set_property(TARGET, chunk);
+            }
+        }
+    }
+
+    impl Requestor for MockOwner {
+        fn convert_selection(
+            &self,
+            selection: Atom,
+            target: Atom,
+            property: Atom,
+        ) -> Result<u64, X11Error> {
+            assert_eq!((selection, target, property), (SELECTION, TARGET, PROPERTY));
+
+            let property = match &self.data {
+                Some(data) if data.len() < self.max_length => {
+                    *self.property.borrow_mut() = Some((TARGET, data.to_vec()));
+                    PROPERTY
+                },
+                Some(data) => {
+                    let size = (data.len() as u32).to_ne_bytes().to_vec();
+                    *self.property.borrow_mut() = Some((INCR, size));
+                    let transfer = OutgoingTransfer::new(0, PROPERTY, TARGET, data.clone());
+                    *self.transfer.borrow_mut() = Some(transfer);
+                    PROPERTY
+                },
+                None => Atom::from(AtomEnum::NONE),
+            };
+
+            self.events.borrow_mut().push_back(Event::SelectionNotify(SelectionNotifyEvent {
+                response_type: SELECTION_NOTIFY_EVENT,
+                sequence: 0,
+                time: 0,
+                requestor: 0,
+                selection,
+                target,
+                property,
+            }));
+
+            Ok(0)
+        }
+
+        fn next_event(
+            &self,
+            _sequence: u64,
+            _timeout: Duration,
+        ) -> Result<Option<Event>, X11Error> {
+            Ok(self.events.borrow_mut().pop_front())
+        }
+
+        fn get_property(&self, property: Atom, delete: bool) -> Result<(Atom, Vec<u8>), X11Error> {
+            assert_eq!(property, PROPERTY);
+
+            let value = self.property.borrow().clone().unwrap_or((0, Vec::new()));
+            if delete {
+                self.delete_property(property)?;
+            }
+
+            Ok(value)
+        }
+
+        fn delete_property(&self, property: Atom) -> Result<(), X11Error> {
+            assert_eq!(property, PROPERTY);
+
+            self.property.borrow_mut().take();
+            self.on_delete();
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn load_direct() {
+        let owner = MockOwner::new(Some(b"hello"), 1024);
+        let data = load(&owner, SELECTION, TARGET, INCR, PROPERTY).unwrap();
+        assert_eq!(data, b"hello");
+    }
+
+    #[test]
+    fn load_incremental() {
+        let text: Vec<u8> = (0..INCR_CHUNK_SIZE * 3 + 10).map(|i| b'a' + (i % 26) as u8).collect();
+        let owner = MockOwner::new(Some(&text), 1024);
+        let data = load(&owner, SELECTION, TARGET, INCR, PROPERTY).unwrap();
+        assert_eq!(data, text);
+        assert!(owner.transfer.borrow().as_ref().unwrap().is_done());
+    }
+
+    #[test]
+    fn load_refused() {
+        let owner = MockOwner::new(None, 1024);
+        let result = load(&owner, SELECTION, TARGET, INCR, PROPERTY);
+        assert!(matches!(result, Err(TransferError::Refused)));
+    }
+
+    #[test]
+    fn direct_transfer() {
+        let now = Instant::now();
+        let mut transfer = Transfer::new(TARGET, INCR, now);
+
+        let step = transfer.start(TARGET, b"hello".to_vec(), now).unwrap();
+
+        assert_eq!(step, TransferStep::Done);
+        assert!(!transfer.is_incremental());
+        assert_eq!(transfer.into_data(), b"hello");
+    }
+
+    #[test]
+    fn incremental_transfer() {
+        let now = Instant::now();
+        let mut transfer = Transfer::new(TARGET, INCR, now);
+
+        let size = 8_000_000u32.to_ne_bytes().to_vec();
+        assert_eq!(transfer.start(INCR, size, now).unwrap(), TransferStep::Pending);
+        assert!(transfer.is_incremental());
+
+        let chunk = vec![b'x'; 4000];
+        for _ in 0..2000 {
+            assert_eq!(transfer.chunk(TARGET, &chunk, now), TransferStep::Pending);
+        }
+        assert_eq!(transfer.chunk(TARGET, &[], now), TransferStep::Done);
+
+        assert_eq!(transfer.into_data().len(), 8_000_000);
+    }
+
+    #[test]
+    fn incremental_ignores_foreign_chunks() {
+        let now = Instant::now();
+        let mut transfer = Transfer::new(TARGET, INCR, now);
+
+        transfer.start(INCR, Vec::new(), now).unwrap();
+        transfer.chunk(TARGET, b"abc", now);
+        transfer.chunk(OTHER, b"def", now);
+        transfer.chunk(TARGET, b"ghi", now);
+        transfer.chunk(TARGET, &[], now);
+
+        assert_eq!(transfer.into_data(), b"abcghi");
+    }
+
+    #[test]
+    fn unexpected_type() {
+        let now = Instant::now();
+        let mut transfer = Transfer::new(TARGET, INCR, now);
+
+        let result = transfer.start(OTHER, b"hello".to_vec(), now);
+
+        assert!(matches!(result, Err(TransferError::UnexpectedType(OTHER))));
+    }
+
+    #[test]
+    fn idle_timeout() {
+        let start = Instant::now();
+        let mut transfer = Transfer::new(TARGET, INCR, start);
+        transfer.start(INCR, Vec::new(), start).unwrap();
+
+        let now = start + IDLE_TIMEOUT / 2;
+        transfer.chunk(TARGET, b"abc", now);
+        assert!(transfer.check_timeout(now + IDLE_TIMEOUT / 2).is_ok());
+
+        let result = transfer.check_timeout(now + IDLE_TIMEOUT);
+        assert!(matches!(result, Err(TransferError::Timeout { received: 3 })));
+    }
+
+    #[test]
+    fn progress_extends_idle_timeout() {
+        let start = Instant::now();
+        let mut transfer = Transfer::new(TARGET, INCR, start);
+        transfer.start(INCR, Vec::new(), start).unwrap();
+
+        let mut now = start;
+        while now < start + IDLE_TIMEOUT * 3 {
+            now += IDLE_TIMEOUT / 2;
+            transfer.chunk(TARGET, b"abc", now);
+            assert!(transfer.check_timeout(now).is_ok());
+        }
+    }
+
+    #[test]
+    fn file_formats() {
+        let content = ClipboardContent {
+            text: String::from("/a\n/b"),
+            file_uris: vec![String::from("file:///a"), String::from("file:///b")],
+        };
+
+        assert_eq!(uri_list(&content).unwrap(), "file:///a\r\nfile:///b\r\n");
+        assert_eq!(gnome_copied_files(&content).unwrap(), "copy\nfile:///a\nfile:///b");
+
+        let content = ClipboardContent::new(String::from("text"));
+        assert_eq!(uri_list(&content), None);
+        assert_eq!(gnome_copied_files(&content), None);
+    }
+
+    #[test]
+    fn outgoing_chunks() {
+        let data: Arc<[u8]> = vec![b'x'; INCR_CHUNK_SIZE * 2 + 10].into();
+        let mut transfer = OutgoingTransfer::new(0, 0, TARGET, data);
+        let now = Instant::now();
+
+        assert_eq!(transfer.next_chunk(now).len(), INCR_CHUNK_SIZE);
+        assert_eq!(transfer.next_chunk(now).len(), INCR_CHUNK_SIZE);
+        assert_eq!(transfer.next_chunk(now).len(), 10);
+        assert!(!transfer.is_done());
+
+        assert!(transfer.next_chunk(now).is_empty());
+        assert!(transfer.is_done());
+    }
+
+    #[test]
+    fn outgoing_timeout() {
+        let data: Arc<[u8]> = vec![b'x'; INCR_CHUNK_SIZE * 2].into();
+        let mut transfer = OutgoingTransfer::new(0, 0, TARGET, data);
+        let start = transfer.started;
+
+        transfer.next_chunk(start + IDLE_TIMEOUT / 2);
+        assert!(!transfer.timed_out(start + IDLE_TIMEOUT));
+        assert!(transfer.timed_out(start + IDLE_TIMEOUT / 2 + IDLE_TIMEOUT));
+        assert!(transfer.timed_out(start + TRANSFER_TIMEOUT));
+    }
+
+    #[test]
+    fn total_timeout() {
+        let start = Instant::now();
+        let mut transfer = Transfer::new(TARGET, INCR, start);
+        transfer.start(INCR, Vec::new(), start).unwrap();
+
+        let now = start + TRANSFER_TIMEOUT;
+        transfer.chunk(TARGET, b"abc", now);
+
+        assert!(transfer.check_timeout(now).is_err());
+    }
+}
@@ -0,0 +1,32 @@
+//! Generation of shell completions.
+
+use std::io;
+
+use clap::builder::PossibleValuesParser;
+use clap::CommandFactory;
+
+use alacritty_config::SerdeReplace;
+
+use crate::cli::{CompletionsOptions, Options};
+use crate::config::UiConfig;
+
+/// Handle the completions subcommand.
+pub fn completions(options: CompletionsOptions) {
+    let mut command = Options::command();
+
+    // Complete the dotted keys of runtime configuration changes.
+    if cfg!(unix) {
+        command = command.mut_subcommand("msg", |msg| {
+            msg.mut_subcommand("config", |config| {
+                config.mut_arg("options", |arg| arg.value_parser(config_options()))
+            })
+        });
+    }
+
+    clap_complete::generate(options.shell, &mut command, "alacritty", &mut io::stdout());
+}
+
+/// Possible values for the `msg config` options, without the value itself.
+fn config_options() -> PossibleValuesParser {
+    PossibleValuesParser::new(UiConfig::keys().into_iter().map(|key| format!("{key}=")))
+}
@@ -1,11 +1,12 @@
+use std::path::PathBuf;
 use std::time::Duration;
 
 use alacritty_config_derive::ConfigDeserialize;
 
-use crate::config::ui_config::Program;
+use crate::config::ui_config::{Percentage, Program};
 use crate::display::color::Rgb;
 
-#[derive(ConfigDeserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(ConfigDeserialize, Clone, Debug, PartialEq)]
 pub struct BellConfig {
     /// Visual bell animation function.
     pub animation: BellAnimation,
@@ -18,6 +19,15 @@ pub struct BellConfig {
 
     /// Visual bell duration in milliseconds.
     duration: u16,
+
+    /// Window focus states in which the bell is audible.
+    pub audible: Audible,
+
+    /// Sound file played by the audible bell.
+    pub sound: Option<PathBuf>,
+
+    /// Audible bell volume.
+    pub volume: Percentage,
 }
 
 impl Default for BellConfig {
@@ -27,6 +37,9 @@ impl Default for BellConfig {
             animation: Default::default(),
             command: Default::default(),
             duration: Default::default(),
+            audible: Default::default(),
+            sound: Default::default(),
+            volume: Default::default(),
         }
     }
 }
@@ -63,3 +76,17 @@ pub enum BellAnimation {
     #[default]
     Linear,
 }
+
+/// Window focus states in which the bell plays a sound.
+#[derive(ConfigDeserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Audible {
+    /// Never play a sound.
+    #[default]
+    Never,
+    /// Only play a sound while the window is focused.
+    Focused,
+    /// Only play a sound while the window is unfocused.
+    Unfocused,
+    /// Always play a sound.
+    Always,
+}
@@ -19,6 +19,7 @@ use alacritty_terminal::term::TermMode;
 use alacritty_terminal::vi_mode::ViMotion;
 
 use crate::config::ui_config::{Hint, Program, StringVisitor};
+use crate::lock_keys::LockKeys;
 
 /// Describes a state and action to take in that state.
 ///
@@ -124,9 +125,20 @@ pub enum Action {
     /// Store current selection into selection buffer.
     CopySelection,
 
+    /// Store the focused line and the given number of lines around it into clipboard.
+    #[config(skip)]
+    CopyLines(usize),
+
     /// Paste contents of selection buffer.
     PasteSelection,
 
+    /// Paste the given entry of the clipboard history, starting at `0` for the most recent copy.
+    #[config(skip)]
+    PasteFromHistory(usize),
+
+    /// Show the clipboard history to select the entry which should be pasted.
+    ShowClipboardHistory,
+
     /// Increase font size.
     IncreaseFontSize,
 
@@ -160,6 +172,15 @@ pub enum Action {
     /// Scroll all the way to the bottom.
     ScrollToBottom,
 
+    /// Scroll to the previous prompt.
+    JumpToPreviousPrompt,
+
+    /// Scroll to the next prompt.
+    JumpToNextPrompt,
+
+    /// Select the output of the last command.
+    SelectLastCommandOutput,
+
     /// Clear the display buffer(s) to remove history.
     ClearHistory,
 
@@ -181,6 +202,12 @@ pub enum Action {
     /// Spawn a new instance of Alacritty.
     SpawnNewInstance,
 
+    /// Restart Alacritty, moving all windows to the new instance.
+    RestartAlacritty,
+
+    /// Continue the stopped job of the shell.
+    ResumeJob,
+
     /// Select next tab.
     SelectNextTab,
 
@@ -229,18 +256,30 @@ pub enum Action {
     /// Toggle maximized.
     ToggleMaximized,
 
+    /// Toggle the presentation mode.
+    TogglePresentationMode,
+
     /// Toggle simple fullscreen on macOS.
     ToggleSimpleFullscreen,
 
     /// Clear active selection.
     ClearSelection,
 
+    /// Reveal concealed text while the binding is held.
+    RevealHidden,
+
     /// Toggle vi mode.
     ToggleViMode,
 
+    /// Toggle vi mode with frozen terminal output.
+    ToggleCopyMode,
+
     /// Allow receiving char input.
     ReceiveChar,
 
+    /// Send the key using the CSI u encoding, even without the kitty keyboard protocol.
+    SendCsiU,
+
     /// Start a forward buffer search.
     SearchForward,
 
@@ -314,6 +353,8 @@ pub enum ViAction {
     SearchEnd,
     /// Launch the URL below the vi mode cursor.
     Open,
+    /// Trigger the hint closest to the vi mode cursor on its line.
+    OpenUnderCursor,
     /// Centers the screen around the vi mode cursor.
     CenterAroundViCursor,
     /// Search forward within the current line.
@@ -328,6 +369,10 @@ pub enum ViAction {
     InlineSearchNext,
     /// Jump to the previous inline search match.
     InlineSearchPrevious,
+    /// Use the named register for the next copy or paste.
+    SelectRegister,
+    /// Paste the selected register.
+    PasteRegister,
 }
 
 /// Search mode specific actions.
@@ -488,6 +533,8 @@ pub fn default_key_bindings() -> Vec<KeyBinding> {
         "t",      ModifiersState::SHIFT,    +BindingMode::VI, ~BindingMode::SEARCH; ViAction::InlineSearchBackwardShort;
         ";",                                +BindingMode::VI, ~BindingMode::SEARCH; ViAction::InlineSearchNext;
         ",",                                +BindingMode::VI, ~BindingMode::SEARCH; ViAction::InlineSearchPrevious;
+        "\"",     ModifiersState::SHIFT,    +BindingMode::VI, ~BindingMode::SEARCH; ViAction::SelectRegister;
+        "p",                                +BindingMode::VI, ~BindingMode::SEARCH; ViAction::PasteRegister;
         "k",                                +BindingMode::VI, ~BindingMode::SEARCH; ViMotion::Up;
         "j",                                +BindingMode::VI, ~BindingMode::SEARCH; ViMotion::Down;
         "h",                                +BindingMode::VI, ~BindingMode::SEARCH; ViMotion::Left;
@@ -747,7 +794,7 @@ pub struct ModeWrapper {
 bitflags! {
     /// Modes available for key bindings.
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-    pub struct BindingMode: u8 {
+    pub struct BindingMode: u16 {
         const APP_CURSOR             = 0b0000_0001;
         const APP_KEYPAD             = 0b0000_0010;
         const ALT_SCREEN             = 0b0000_0100;
@@ -755,11 +802,13 @@ bitflags! {
         const SEARCH                 = 0b0001_0000;
         const DISAMBIGUATE_ESC_CODES = 0b0010_0000;
         const REPORT_ALL_KEYS_AS_ESC = 0b0100_0000;
+        const CAPS_LOCK              = 0b1000_0000;
+        const NUM_LOCK               = 0b1_0000_0000;
     }
 }
 
 impl BindingMode {
-    pub fn new(mode: &TermMode, search: bool) -> BindingMode {
+    pub fn new(mode: &TermMode, search: bool, lock_keys: LockKeys) -> BindingMode {
         let mut binding_mode = BindingMode::empty();
         binding_mode.set(BindingMode::APP_CURSOR, mode.contains(TermMode::APP_CURSOR));
         binding_mode.set(BindingMode::APP_KEYPAD, mode.contains(TermMode::APP_KEYPAD));
@@ -774,6 +823,8 @@ impl BindingMode {
             BindingMode::REPORT_ALL_KEYS_AS_ESC,
             mode.contains(TermMode::REPORT_ALL_KEYS_AS_ESC),
         );
+        binding_mode.set(BindingMode::CAPS_LOCK, lock_keys.contains(LockKeys::CAPS_LOCK));
+        binding_mode.set(BindingMode::NUM_LOCK, lock_keys.contains(LockKeys::NUM_LOCK));
         binding_mode
     }
 }
@@ -819,6 +870,10 @@ impl<'a> Deserialize<'a> for ModeWrapper {
                         "~vi" => res.not_mode |= BindingMode::VI,
                         "search" => res.mode |= BindingMode::SEARCH,
                         "~search" => res.not_mode |= BindingMode::SEARCH,
+                        "capslock" => res.mode |= BindingMode::CAPS_LOCK,
+                        "~capslock" => res.not_mode |= BindingMode::CAPS_LOCK,
+                        "numlock" => res.mode |= BindingMode::NUM_LOCK,
+                        "~numlock" => res.not_mode |= BindingMode::NUM_LOCK,
                         _ => return Err(E::invalid_value(Unexpected::Str(modifier), &self)),
                     }
                 }
@@ -1059,7 +1114,15 @@ impl<'a> Deserialize<'a> for RawBinding {
 
                             let value = map.next_value::<SerdeValue>()?;
 
-                            action = if let Ok(vi_action) = ViAction::deserialize(value.clone()) {
+                            action = if let Some(lines) = value.get("CopyLines") {
+                                let lines =
+                                    usize::deserialize(lines.clone()).map_err(V::Error::custom)?;
+                                Some(Action::CopyLines(lines))
+                            } else if let Some(index) = value.get("PasteFromHistory") {
+                                let index =
+                                    usize::deserialize(index.clone()).map_err(V::Error::custom)?;
+                                Some(Action::PasteFromHistory(index))
+                            } else if let Ok(vi_action) = ViAction::deserialize(value.clone()) {
                                 Some(vi_action.into())
                             } else if let Ok(vi_motion) = SerdeViMotion::deserialize(value.clone())
                             {
@@ -1441,4 +1504,21 @@ mod tests {
         assert!(!binding.is_triggered_by(BindingMode::ALT_SCREEN, mods, &t));
         assert!(!binding.is_triggered_by(BindingMode::ALT_SCREEN | BindingMode::VI, mods, &t));
     }
+
+    #[test]
+    fn deserialize_copy_lines() {
+        let binding: RawBinding =
+            toml::from_str("key = \"L\"\nmode = \"Vi\"\naction = { CopyLines = 3 }").unwrap();
+        assert_eq!(binding.action, Action::CopyLines(3));
+
+        let invalid = "key = \"L\"\naction = { CopyLines = -1 }";
+        assert!(toml::from_str::<RawBinding>(invalid).is_err());
+    }
+
+    #[test]
+    fn deserialize_paste_from_history() {
+        let binding: RawBinding =
+            toml::from_str("key = \"V\"\naction = { PasteFromHistory = 2 }").unwrap();
+        assert_eq!(binding.action, Action::PasteFromHistory(2));
+    }
 }
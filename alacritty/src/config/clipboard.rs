@@ -0,0 +1,33 @@
+use alacritty_config_derive::ConfigDeserialize;
+
+#[derive(ConfigDeserialize, Default, Clone, Debug, PartialEq, Eq)]
+pub struct ClipboardConfig {
+    /// Backend storing the clipboard and primary selection.
+    pub backend: ClipboardBackend,
+
+    /// Keep the primary selection within Alacritty for backends which don't support it.
+    pub emulate_selection: bool,
+
+    /// Number of copied texts kept in the clipboard history.
+    pub history: usize,
+}
+
+/// Clipboard backend.
+#[derive(ConfigDeserialize, Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ClipboardBackend {
+    /// Platform's default clipboard.
+    #[default]
+    Auto,
+
+    /// X11 selections.
+    X11,
+
+    /// Wayland clipboard of the focused window.
+    Wayland,
+
+    /// Wayland clipboard using the wlr data control protocol.
+    WlrDataControl,
+
+    /// Clipboard which is only accessible within Alacritty.
+    Internal,
+}
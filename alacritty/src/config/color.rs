@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use serde::de::Error as SerdeError;
 use serde::{Deserialize, Deserializer};
 
@@ -21,9 +23,16 @@ pub struct Colors {
     pub transparent_background_colors: bool,
     pub draw_bold_text_with_bright_colors: bool,
     footer_bar: BarColors,
+
+    /// Duration of the transition between palettes in milliseconds.
+    transition_duration: u16,
 }
 
 impl Colors {
+    pub fn transition_duration(&self) -> Duration {
+        Duration::from_millis(u64::from(self.transition_duration))
+    }
+
     pub fn footer_bar_foreground(&self) -> Rgb {
         self.footer_bar.foreground.unwrap_or(self.primary.background)
     }
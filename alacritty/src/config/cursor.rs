@@ -19,6 +19,7 @@ pub struct Cursor {
     pub style: ConfigCursorStyle,
     pub vi_mode_style: Option<ConfigCursorStyle>,
     pub unfocused_hollow: bool,
+    pub ensure_contrast: bool,
 
     thickness: Percentage,
     blink_interval: u64,
@@ -30,6 +31,7 @@ impl Default for Cursor {
         Self {
             thickness: Percentage::new(0.15),
             unfocused_hollow: true,
+            ensure_contrast: false,
             blink_interval: 750,
             blink_timeout: 5,
             style: Default::default(),
@@ -18,12 +18,21 @@ pub struct Debug {
     /// Highlight damage information produced by alacritty.
     pub highlight_damage: bool,
 
+    /// Highlight damage accumulated over all frames.
+    pub damage_heatmap: bool,
+
     /// The renderer alacritty should be using.
     pub renderer: Option<RendererPreference>,
 
     /// Use EGL as display API if the current platform allows it.
     pub prefer_egl: bool,
 
+    /// Serve performance metrics on a unix socket.
+    pub metrics_socket: bool,
+
+    /// Local port serving performance metrics.
+    pub metrics_port: Option<u16>,
+
     /// Record ref test.
     #[config(skip)]
     pub ref_test: bool,
@@ -37,9 +46,12 @@ impl Default for Debug {
             persistent_logging: Default::default(),
             render_timer: Default::default(),
             highlight_damage: Default::default(),
+            damage_heatmap: Default::default(),
             ref_test: Default::default(),
             renderer: Default::default(),
             prefer_egl: Default::default(),
+            metrics_socket: Default::default(),
+            metrics_port: Default::default(),
         }
     }
 }
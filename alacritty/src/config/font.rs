@@ -1,4 +1,5 @@
 use std::fmt;
+use std::ops::RangeInclusive;
 
 use crossfont::Size as FontSize;
 use serde::de::{self, Visitor};
@@ -22,6 +23,9 @@ pub struct Font {
     /// Glyph offset within character cell.
     pub glyph_offset: Delta<i8>,
 
+    /// Additional glyph offsets for specific codepoint ranges.
+    pub glyph_offset_ranges: Vec<GlyphOffsetRange>,
+
     #[config(removed = "set the AppleFontSmoothing user default instead")]
     pub use_thin_strokes: bool,
 
@@ -42,6 +46,12 @@ pub struct Font {
 
     /// Whether to use the built-in font for box drawing characters.
     pub builtin_box_drawing: bool,
+
+    /// Subpixel order of the display used for text antialiasing.
+    pub subpixel: Subpixel,
+
+    /// Codepoint ranges rendered across two cells when the following cell is empty.
+    double_width: Vec<CodepointRange>,
 }
 
 impl Font {
@@ -74,13 +84,20 @@ impl Font {
     pub fn bold_italic(&self) -> FontDescription {
         self.bold_italic.desc(&self.normal)
     }
+
+    /// Check whether a character's glyph should be rendered across two cells.
+    pub fn is_double_width(&self, c: char) -> bool {
+        self.double_width.iter().any(|range| range.0.contains(&c))
+    }
 }
 
 impl Default for Font {
     fn default() -> Font {
         Self {
             builtin_box_drawing: true,
+            subpixel: Default::default(),
             glyph_offset: Default::default(),
+            glyph_offset_ranges: Default::default(),
             use_thin_strokes: Default::default(),
             bold_italic: Default::default(),
             italic: Default::default(),
@@ -88,10 +105,26 @@ impl Default for Font {
             normal: Default::default(),
             bold: Default::default(),
             size: Default::default(),
+            double_width: Default::default(),
         }
     }
 }
 
+/// Subpixel layout used for LCD text antialiasing.
+#[derive(ConfigDeserialize, Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Subpixel {
+    /// Glyphs as rasterized according to the system font configuration.
+    #[default]
+    System,
+    /// Glyphs with the red and blue channels swapped.
+    ///
+    /// This reverses the subpixel order used by the system font configuration, so it fixes color
+    /// fringes on displays whose order is the reverse of the configured one.
+    Reverse,
+    /// Grayscale antialiasing without subpixel coverage.
+    None,
+}
+
 /// Description of the normal font.
 #[derive(ConfigDeserialize, Debug, Clone, PartialEq, Eq)]
 pub struct FontDescription {
@@ -163,3 +196,102 @@ impl<'de> Deserialize<'de> for Size {
         deserializer.deserialize_any(NumVisitor)
     }
 }
+
+/// Glyph offset applied to a range of codepoints.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct GlyphOffsetRange {
+    chars: CodepointRange,
+    #[serde(default)]
+    x: i8,
+    #[serde(default)]
+    y: i8,
+}
+
+impl GlyphOffsetRange {
+    /// Get the offset for a character, if it is part of this range.
+    pub fn offset(&self, character: char) -> Option<Delta<i8>> {
+        self.chars.0.contains(&character).then_some(Delta { x: self.x, y: self.y })
+    }
+}
+
+/// Inclusive range of unicode codepoints.
+///
+/// Ranges are written as `E000-F8FF`, single codepoints as `E0B0`. An optional `U+` prefix is
+/// accepted for both bounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CodepointRange(RangeInclusive<char>);
+
+impl<'de> Deserialize<'de> for CodepointRange {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+
+        let parse = |codepoint: &str| {
+            let codepoint = codepoint.trim();
+            let hex = codepoint
+                .strip_prefix("U+")
+                .or_else(|| codepoint.strip_prefix("u+"))
+                .unwrap_or(codepoint);
+            u32::from_str_radix(hex, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(|| de::Error::custom(format!("invalid codepoint \"{codepoint}\"")))
+        };
+
+        let (start, end) = match value.split_once('-') {
+            Some((start, end)) => (parse(start)?, parse(end)?),
+            None => {
+                let codepoint = parse(&value)?;
+                (codepoint, codepoint)
+            },
+        };
+
+        if start > end {
+            return Err(de::Error::custom(format!("invalid codepoint range \"{value}\"")));
+        }
+
+        Ok(Self(start..=end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use toml::Value;
+
+    fn parse(value: &str) -> Result<CodepointRange, toml::de::Error> {
+        CodepointRange::deserialize(Value::String(value.into()))
+    }
+
+    #[test]
+    fn codepoint_range() {
+        assert_eq!(parse("E000-F8FF").unwrap().0, '\u{e000}'..='\u{f8ff}');
+        assert_eq!(parse("U+E0B0").unwrap().0, '\u{e0b0}'..='\u{e0b0}');
+        assert_eq!(parse("U+23FB - U+23FE").unwrap().0, '\u{23fb}'..='\u{23fe}');
+
+        assert!(parse("F8FF-E000").is_err());
+        assert!(parse("D800").is_err());
+        assert!(parse("nerd").is_err());
+    }
+
+    #[test]
+    fn glyph_offset_range() {
+        let range: GlyphOffsetRange = toml::from_str("chars = \"3000-9FFF\"\ny = -2").unwrap();
+        assert_eq!(range.offset('\u{4e2d}'), Some(Delta { x: 0, y: -2 }));
+        assert_eq!(range.offset('a'), None);
+
+        assert!(toml::from_str::<GlyphOffsetRange>("y = -2").is_err());
+    }
+
+    #[test]
+    fn double_width() {
+        let font: Font = toml::from_str("double_width = [\"E000-F8FF\", \"U+23FB\"]").unwrap();
+        assert!(font.is_double_width('\u{e0b0}'));
+        assert!(font.is_double_width('\u{23fb}'));
+        assert!(!font.is_double_width('a'));
+    }
+}
@@ -4,6 +4,8 @@ use std::path::PathBuf;
 
 use alacritty_config_derive::ConfigDeserialize;
 
+use crate::accessibility;
+
 /// General config section.
 ///
 /// This section is for fields which can not be easily categorized,
@@ -25,6 +27,44 @@ pub struct General {
     /// Offer IPC through a unix socket.
     #[allow(unused)]
     pub ipc_socket: bool,
+
+    /// Disable animations, following the system preference when unset.
+    reduce_motion: Option<bool>,
+
+    /// Enforce cursor contrast, following the system preference when unset.
+    increase_contrast: Option<bool>,
+
+    /// System preference for reduced motion.
+    #[config(skip)]
+    system_reduce_motion: bool,
+
+    /// System preference for increased contrast.
+    #[config(skip)]
+    system_increase_contrast: bool,
+}
+
+impl General {
+    /// Check if animations like cursor blinking, fading overlays and shader time should be
+    /// disabled.
+    pub fn reduce_motion(&self) -> bool {
+        self.reduce_motion.unwrap_or(self.system_reduce_motion)
+    }
+
+    /// Check if the cursor should always contrast with the cell beneath it.
+    pub fn increase_contrast(&self) -> bool {
+        self.increase_contrast.unwrap_or(self.system_increase_contrast)
+    }
+
+    /// Read the system accessibility preferences which are not overridden by the config.
+    pub fn load_system_preferences(&mut self) {
+        if self.reduce_motion.is_none() {
+            self.system_reduce_motion = accessibility::reduce_motion();
+        }
+
+        if self.increase_contrast.is_none() {
+            self.system_increase_contrast = accessibility::increase_contrast();
+        }
+    }
 }
 
 impl Default for General {
@@ -34,6 +74,10 @@ impl Default for General {
             ipc_socket: true,
             working_directory: Default::default(),
             import: Default::default(),
+            reduce_motion: Default::default(),
+            increase_contrast: Default::default(),
+            system_reduce_motion: Default::default(),
+            system_increase_contrast: Default::default(),
         }
     }
 }
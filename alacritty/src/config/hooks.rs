@@ -0,0 +1,19 @@
+use alacritty_config_derive::ConfigDeserialize;
+
+use crate::config::ui_config::Program;
+
+/// Commands run on terminal events.
+#[derive(ConfigDeserialize, Default, Clone, Debug, PartialEq, Eq)]
+pub struct Hooks {
+    /// Command run when the bell is rung.
+    pub bell: Option<Program>,
+
+    /// Command run when the terminal changes its title.
+    pub title: Option<Program>,
+
+    /// Command run when the shell exits.
+    pub child_exit: Option<Program>,
+
+    /// Command run when the terminal requests a desktop notification.
+    pub notification: Option<Program>,
+}
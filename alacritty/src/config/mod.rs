@@ -1,3 +1,4 @@
+use std::ffi::{OsStr, OsString};
 use std::fmt::{self, Display, Formatter};
 use std::path::{Path, PathBuf};
 use std::result::Result as StdResult;
@@ -11,12 +12,16 @@ use toml::ser::Error as TomlSeError;
 use toml::{Table, Value};
 
 pub mod bell;
+pub mod clipboard;
 pub mod color;
 pub mod cursor;
 pub mod debug;
 pub mod font;
 pub mod general;
+pub mod hooks;
 pub mod monitor;
+pub mod presentation;
+pub mod renderer;
 pub mod scrolling;
 pub mod selection;
 pub mod serde_utils;
@@ -165,6 +170,36 @@ fn after_loading(config: &mut UiConfig, options: &mut Options) {
 
     // Create key bindings for regex hints.
     config.generate_hint_bindings();
+
+    // Apply accessibility preferences of the system.
+    config.general.load_system_preferences();
+
+    // Report missing shells before they're spawned.
+    if let Some(shell) = config.pty_config().shell {
+        let path = config.env.get("PATH").map(OsString::from).or_else(|| env::var_os("PATH"));
+        if !program_exists(shell.program(), path.as_deref()) {
+            warn!(
+                target: LOG_TARGET_CONFIG,
+                "Config error: terminal.shell: program {:?} not found; the default shell will be \
+                 used instead",
+                shell.program(),
+            );
+        }
+    }
+}
+
+/// Check if a program exists, searching `path` for programs without a directory.
+fn program_exists(program: &str, path: Option<&OsStr>) -> bool {
+    let program = Path::new(program);
+    if program.components().count() > 1 {
+        return program.is_file();
+    }
+
+    let executable = |dir: PathBuf| {
+        let file = dir.join(program);
+        file.is_file() || (cfg!(windows) && file.with_extension("exe").is_file())
+    };
+    path.is_some_and(|path| env::split_paths(path).any(executable))
 }
 
 /// Load configuration file and log errors.
@@ -188,8 +223,11 @@ fn read_config(path: &Path) -> Result<UiConfig> {
     let config_value = parse_config(path, &mut config_paths, IMPORT_RECURSION_LIMIT)?;
 
     // Deserialize to concrete type.
-    let mut config = UiConfig::deserialize(config_value)?;
+    let mut config = UiConfig::deserialize(config_value.clone())?;
     config.config_paths = config_paths;
+    if let Value::Table(table) = config_value {
+        config.config_value = table;
+    }
 
     Ok(config)
 }
@@ -417,6 +455,20 @@ mod tests {
         toml::from_str::<UiConfig>("").unwrap();
     }
 
+    #[test]
+    fn missing_program() {
+        let dir = env::temp_dir();
+        let program = env::current_exe().unwrap();
+        let name = program.file_name().unwrap().to_str().unwrap();
+        let path = env::join_paths([dir.as_path(), program.parent().unwrap()]).unwrap();
+
+        assert!(program_exists(program.to_str().unwrap(), None));
+        assert!(program_exists(name, Some(&path)));
+        assert!(!program_exists(name, Some(dir.as_os_str())));
+        assert!(!program_exists(name, None));
+        assert!(!program_exists("/alacritty-missing-program", Some(&path)));
+    }
+
     fn yaml_to_toml(contents: &str) -> String {
         let mut value: serde_yaml::Value = serde_yaml::from_str(contents).unwrap();
         prune_yaml_nulls(&mut value, false);
@@ -42,7 +42,7 @@ impl ConfigMonitor {
         // a regular file.
         paths.retain(|path| {
             // Call `metadata` to resolve symbolic links.
-            path.metadata().map_or(false, |metadata| metadata.file_type().is_file())
+            path.metadata().is_ok_and(|metadata| metadata.file_type().is_file())
         });
 
         // Canonicalize paths, keeping the base paths for symlinks.
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Deserializer};
 
 use alacritty_config_derive::{ConfigDeserialize, SerdeReplace};
@@ -8,9 +10,21 @@ use crate::config::ui_config;
 #[derive(ConfigDeserialize, Default, Clone, Debug, PartialEq, Eq)]
 pub struct Mouse {
     pub hide_when_typing: bool,
+
+    /// Seconds without mouse input before the pointer is hidden.
+    hide_when_idle: u8,
+
     pub bindings: MouseBindings,
 }
 
+impl Mouse {
+    /// Time without mouse input before the pointer is hidden.
+    #[inline]
+    pub fn hide_when_idle(&self) -> Option<Duration> {
+        (self.hide_when_idle != 0).then(|| Duration::from_secs(self.hide_when_idle as u64))
+    }
+}
+
 #[derive(SerdeReplace, Clone, Debug, PartialEq, Eq)]
 pub struct MouseBindings(pub Vec<MouseBinding>);
 
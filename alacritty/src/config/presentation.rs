@@ -0,0 +1,29 @@
+use alacritty_config_derive::ConfigDeserialize;
+
+/// Smallest font size multiplier, to keep text readable.
+const MIN_FONT_SCALE: f32 = 0.5;
+
+/// Presentation mode for screen sharing and screencasts.
+#[derive(ConfigDeserialize, Clone, Debug, PartialEq)]
+pub struct Presentation {
+    /// Multiplier applied to the font size while presenting.
+    font_scale: f32,
+
+    /// Highlight the cursor position with a ring.
+    pub cursor_ring: bool,
+
+    /// Show recently pressed key chords in the bottom right corner.
+    pub show_keys: bool,
+}
+
+impl Default for Presentation {
+    fn default() -> Self {
+        Self { font_scale: 1.5, cursor_ring: true, show_keys: false }
+    }
+}
+
+impl Presentation {
+    pub fn font_scale(&self) -> f32 {
+        self.font_scale.max(MIN_FONT_SCALE)
+    }
+}
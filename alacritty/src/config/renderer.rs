@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Deserializer};
+
+use alacritty_config_derive::{ConfigDeserialize, SerdeReplace};
+
+use crate::config::ui_config::StringVisitor;
+
+/// Renderer configuration.
+#[derive(ConfigDeserialize, Default, Clone, Debug, PartialEq, Eq)]
+pub struct Renderer {
+    /// GPU which should be used for rendering.
+    pub gpu_preference: Option<GpuPreference>,
+
+    /// Fragment shader applied to every rendered frame.
+    pub shader: Option<PathBuf>,
+}
+
+/// Preferred GPU for rendering.
+#[derive(SerdeReplace, Clone, Debug, PartialEq, Eq)]
+pub enum GpuPreference {
+    /// Prefer the integrated GPU.
+    LowPower,
+
+    /// Prefer the discrete GPU.
+    HighPerformance,
+
+    /// Use a specific adapter.
+    Adapter(String),
+}
+
+impl<'de> Deserialize<'de> for GpuPreference {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = deserializer.deserialize_str(StringVisitor)?;
+        Ok(match value.as_str() {
+            "low-power" => Self::LowPower,
+            "high-performance" => Self::HighPerformance,
+            _ => Self::Adapter(value),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use toml::Value;
+
+    #[test]
+    fn gpu_preference() {
+        let preference = GpuPreference::deserialize(Value::String("Low-Power".into())).unwrap();
+        assert_eq!(preference, GpuPreference::LowPower);
+
+        let preference = GpuPreference::deserialize(Value::String("high-performance".into()));
+        assert_eq!(preference.unwrap(), GpuPreference::HighPerformance);
+
+        let preference = GpuPreference::deserialize(Value::String("1002:73FF".into())).unwrap();
+        assert_eq!(preference, GpuPreference::Adapter("1002:73ff".into()));
+    }
+}
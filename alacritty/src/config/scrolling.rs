@@ -1,29 +1,129 @@
 use serde::de::Error as SerdeError;
 use serde::{Deserialize, Deserializer};
+use toml::Value;
 
+use alacritty_config::SerdeReplace as _;
 use alacritty_config_derive::{ConfigDeserialize, SerdeReplace};
+use alacritty_terminal::term::ScrollRegionHistory;
+
+use crate::config::bindings::{BindingKey, ModsWrapper};
+use crate::config::ui_config::StringVisitor;
 
 /// Maximum scrollback amount configurable.
 pub const MAX_SCROLLBACK_LINES: u32 = 100_000;
 
 /// Struct for scrolling related settings.
-#[derive(ConfigDeserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(ConfigDeserialize, Clone, Debug, PartialEq)]
 pub struct Scrolling {
-    pub multiplier: u8,
+    pub multiplier: ScrollMultiplier,
+
+    /// Keys which do not scroll the viewport to the bottom when sent to the terminal.
+    pub snap_exceptions: Vec<SnapException>,
+
+    /// Scrollback behavior for lines leaving the scrolling region.
+    pub region_history: SerdeScrollRegionHistory,
 
     history: ScrollingHistory,
+
+    alt_screen_history: ScrollingHistory,
 }
 
 impl Default for Scrolling {
     fn default() -> Self {
-        Self { multiplier: 3, history: Default::default() }
+        Self {
+            multiplier: Default::default(),
+            snap_exceptions: Default::default(),
+            region_history: Default::default(),
+            history: Default::default(),
+            alt_screen_history: ScrollingHistory(0),
+        }
     }
 }
 
 impl Scrolling {
-    pub fn history(self) -> u32 {
+    pub fn history(&self) -> u32 {
         self.history.0
     }
+
+    pub fn alt_screen_history(&self) -> u32 {
+        self.alt_screen_history.0
+    }
+}
+
+/// Scroll distance multipliers for each input device.
+#[derive(SerdeReplace, Copy, Clone, Debug, PartialEq)]
+pub struct ScrollMultiplier {
+    /// Lines scrolled per mouse wheel step.
+    pub wheel: ScrollFactor,
+
+    /// Multiplier for pixel-precise scrolling, like touchpads.
+    pub touchpad: ScrollFactor,
+
+    /// Screens scrolled by keyboard paging.
+    pub page: ScrollFactor,
+}
+
+impl Default for ScrollMultiplier {
+    fn default() -> Self {
+        Self { wheel: ScrollFactor(3.), touchpad: ScrollFactor(3.), page: ScrollFactor(1.) }
+    }
+}
+
+impl<'de> Deserialize<'de> for ScrollMultiplier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut multiplier = Self::default();
+
+        // A single value applies to all pointer devices.
+        match Value::deserialize(deserializer)? {
+            value @ Value::Table(_) => multiplier.replace(value).map_err(SerdeError::custom)?,
+            value => {
+                let value = ScrollFactor::deserialize(value).map_err(SerdeError::custom)?;
+                multiplier.wheel = value;
+                multiplier.touchpad = value;
+            },
+        }
+
+        Ok(multiplier)
+    }
+}
+
+/// Non-negative scroll distance multiplier.
+#[derive(SerdeReplace, Copy, Clone, Debug, PartialEq)]
+pub struct ScrollFactor(f32);
+
+impl ScrollFactor {
+    pub fn as_f32(self) -> f32 {
+        self.0
+    }
+}
+
+impl<'de> Deserialize<'de> for ScrollFactor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = f32::deserialize(deserializer)?;
+
+        if value.is_finite() && value >= 0. {
+            Ok(Self(value))
+        } else {
+            Err(SerdeError::custom(format!("scrolling multipliers must not be negative: {value}")))
+        }
+    }
+}
+
+/// Key input exempt from scrolling the viewport to the bottom.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SnapException {
+    pub key: BindingKey,
+
+    /// Required modifiers, matching any modifiers when omitted.
+    #[serde(default)]
+    pub mods: Option<ModsWrapper>,
 }
 
 #[derive(SerdeReplace, Copy, Clone, Debug, PartialEq, Eq)]
@@ -51,3 +151,55 @@ impl<'de> Deserialize<'de> for ScrollingHistory {
         }
     }
 }
+
+#[derive(SerdeReplace, Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SerdeScrollRegionHistory(pub ScrollRegionHistory);
+
+impl<'de> Deserialize<'de> for SerdeScrollRegionHistory {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = deserializer.deserialize_str(StringVisitor)?;
+        ScrollRegionHistory::deserialize(Value::String(value))
+            .map(SerdeScrollRegionHistory)
+            .map_err(SerdeError::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiplier_for_all_pointers() {
+        let multiplier = ScrollMultiplier::deserialize(Value::Integer(5)).unwrap();
+        assert_eq!(multiplier, multipliers(5., 5., 1.));
+
+        let multiplier = ScrollMultiplier::deserialize(Value::Float(0.5)).unwrap();
+        assert_eq!(multiplier, multipliers(0.5, 0.5, 1.));
+    }
+
+    #[test]
+    fn multiplier_per_device() {
+        let value = toml::from_str("wheel = 0.25\npage = 0.5").unwrap();
+        let multiplier = ScrollMultiplier::deserialize(Value::Table(value)).unwrap();
+        assert_eq!(multiplier, multipliers(0.25, 3., 0.5));
+
+        assert!(ScrollMultiplier::deserialize(Value::Integer(-1)).is_err());
+    }
+
+    #[test]
+    fn replace_negative_multiplier() {
+        let mut multiplier = ScrollMultiplier::default();
+        let value = toml::from_str("wheel = -1").unwrap();
+        assert!(multiplier.replace(Value::Table(value)).is_err());
+        assert_eq!(multiplier, ScrollMultiplier::default());
+    }
+
+    fn multipliers(wheel: f32, touchpad: f32, page: f32) -> ScrollMultiplier {
+        let (wheel, touchpad, page) =
+            (ScrollFactor(wheel), ScrollFactor(touchpad), ScrollFactor(page));
+        ScrollMultiplier { wheel, touchpad, page }
+    }
+}
@@ -5,6 +5,15 @@ use alacritty_terminal::term::SEMANTIC_ESCAPE_CHARS;
 pub struct Selection {
     pub semantic_escape_chars: String,
     pub save_to_clipboard: bool,
+
+    /// Copy text concealed by applications, instead of replacing it with spaces.
+    pub copy_hidden: bool,
+
+    /// Size in MiB above which copying a selection requires confirmation.
+    pub copy_confirmation_threshold: u32,
+
+    /// Regexes selecting the entire match on double-click, before falling back to escape chars.
+    pub semantic_patterns: Vec<String>,
 }
 
 impl Default for Selection {
@@ -12,6 +21,9 @@ impl Default for Selection {
         Self {
             semantic_escape_chars: SEMANTIC_ESCAPE_CHARS.to_owned(),
             save_to_clipboard: Default::default(),
+            copy_hidden: true,
+            copy_confirmation_threshold: 16,
+            semantic_patterns: Default::default(),
         }
     }
 }
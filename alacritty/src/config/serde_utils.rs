@@ -32,6 +32,20 @@ fn merge_tables(mut base: Table, replacement: Table) -> Table {
     base
 }
 
+/// Replace all values of `base` which are present in `replacement`.
+///
+/// Unlike [`merge`], arrays are replaced instead of appended, matching config overrides.
+pub fn replace(base: &mut Table, replacement: Table) {
+    for (key, value) in replacement {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(replacement)) => replace(base, replacement),
+            (_, value) => {
+                base.insert(key, value);
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,4 +103,15 @@ mod tests {
 
         assert_eq!(merged, expected);
     }
+
+    #[test]
+    fn replace_nested() {
+        let mut base: Table = toml::from_str("a = [1, 2]\nb = { c = true, d = 1 }").unwrap();
+        let replacement: Table = toml::from_str("a = [3]\nb = { c = false }").unwrap();
+
+        replace(&mut base, replacement);
+
+        let expected: Table = toml::from_str("a = [3]\nb = { c = false, d = 1 }").unwrap();
+        assert_eq!(base, expected);
+    }
 }
@@ -5,13 +5,107 @@ use alacritty_config_derive::{ConfigDeserialize, SerdeReplace};
 use alacritty_terminal::term::Osc52;
 
 use crate::config::ui_config::{Program, StringVisitor};
+use crate::display::color::Rgb;
 
-#[derive(ConfigDeserialize, Default, Clone, Debug, PartialEq)]
+#[derive(ConfigDeserialize, Clone, Debug, PartialEq)]
 pub struct Terminal {
     /// OSC52 support mode.
     pub osc52: SerdeOsc52,
     /// Path to a shell program to run on startup.
     pub shell: Option<Program>,
+    /// Automatic shell integration.
+    pub shell_integration: ShellIntegration,
+    /// Respond to requests for the window size.
+    pub window_reports: bool,
+    /// Report the window opacity with the background color.
+    pub report_opacity: bool,
+    /// Border indicating modes which change input behavior.
+    pub mode_indicator: ModeIndicator,
+    /// Ignore escape sequences which affect anything beyond the terminal content.
+    pub hardening: bool,
+    /// Show desktop notifications requested using OSC 9 and OSC 777.
+    pub notifications: bool,
+    /// Windows Subsystem for Linux integration.
+    pub wsl: Wsl,
+}
+
+impl Default for Terminal {
+    fn default() -> Self {
+        Self {
+            osc52: Default::default(),
+            shell: Default::default(),
+            shell_integration: Default::default(),
+            window_reports: true,
+            report_opacity: Default::default(),
+            mode_indicator: Default::default(),
+            hardening: Default::default(),
+            notifications: Default::default(),
+            wsl: Default::default(),
+        }
+    }
+}
+
+#[derive(ConfigDeserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ModeIndicator {
+    /// Indicate when the application enabled bracketed paste.
+    pub bracketed_paste: bool,
+    /// Indicate when the application enabled mouse reporting.
+    pub mouse: bool,
+    /// Indicate when caps lock is active.
+    pub caps_lock: bool,
+    /// Indicate when a job of the shell is stopped.
+    pub stopped_job: bool,
+    /// Indicator color.
+    pub color: Rgb,
+    /// Indicator width in pixels.
+    thickness: u8,
+}
+
+impl Default for ModeIndicator {
+    fn default() -> Self {
+        Self {
+            color: Rgb::new(0x66, 0x99, 0xcc),
+            bracketed_paste: Default::default(),
+            mouse: Default::default(),
+            caps_lock: Default::default(),
+            stopped_job: Default::default(),
+            thickness: 2,
+        }
+    }
+}
+
+impl ModeIndicator {
+    /// Indicator width in physical pixels.
+    pub fn thickness(&self, scale_factor: f32) -> f32 {
+        (self.thickness as f32 * scale_factor).floor()
+    }
+}
+
+/// Windows Subsystem for Linux integration.
+#[derive(ConfigDeserialize, Default, Clone, Debug, PartialEq, Eq)]
+pub struct Wsl {
+    /// Distribution launched when no shell is configured.
+    pub distribution: Option<String>,
+}
+
+impl Wsl {
+    /// Shell launching the configured distribution.
+    #[cfg(windows)]
+    pub fn shell(&self) -> Option<Program> {
+        let distribution = self.distribution.clone()?;
+        let args = vec![String::from("--distribution"), distribution];
+        Some(Program::WithArgs { program: String::from("wsl.exe"), args })
+    }
+}
+
+/// Shell integration mode.
+#[derive(ConfigDeserialize, Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ShellIntegration {
+    /// Shell integration scripts are never loaded automatically.
+    #[default]
+    Disabled,
+    /// Load shell integration scripts for supported shells.
+    Auto,
 }
 
 #[derive(SerdeReplace, Default, Copy, Clone, Debug, PartialEq)]
@@ -6,11 +6,12 @@ use std::path::PathBuf;
 use std::rc::Rc;
 
 use alacritty_config::SerdeReplace;
-use alacritty_terminal::term::Config as TermConfig;
+use alacritty_terminal::term::{Config as TermConfig, Osc52};
 use alacritty_terminal::tty::{Options as PtyOptions, Shell};
 use log::{error, warn};
 use serde::de::{Error as SerdeError, MapAccess, Visitor};
 use serde::{Deserialize, Deserializer};
+use toml::Table;
 use unicode_width::UnicodeWidthChar;
 use winit::keyboard::{Key, ModifiersState};
 
@@ -22,12 +23,16 @@ use crate::config::bindings::{
     self, Action, Binding, BindingKey, KeyBinding, KeyLocation, ModeWrapper, ModsWrapper,
     MouseBinding,
 };
+use crate::config::clipboard::ClipboardConfig;
 use crate::config::color::Colors;
 use crate::config::cursor::Cursor;
 use crate::config::debug::Debug;
 use crate::config::font::Font;
 use crate::config::general::General;
+use crate::config::hooks::Hooks;
 use crate::config::mouse::Mouse;
+use crate::config::presentation::Presentation;
+use crate::config::renderer::Renderer;
 use crate::config::scrolling::Scrolling;
 use crate::config::selection::Selection;
 use crate::config::terminal::Terminal;
@@ -56,6 +61,9 @@ pub struct UiConfig {
     /// Selection configuration.
     pub selection: Selection,
 
+    /// Clipboard configuration.
+    pub clipboard: ClipboardConfig,
+
     /// Font configuration.
     pub font: Font,
 
@@ -68,9 +76,18 @@ pub struct UiConfig {
     /// Debug options.
     pub debug: Debug,
 
+    /// Renderer configuration.
+    pub renderer: Renderer,
+
     /// Bell configuration.
     pub bell: BellConfig,
 
+    /// Commands run on terminal events.
+    pub hooks: Hooks,
+
+    /// Presentation mode configuration.
+    pub presentation: Presentation,
+
     /// RGB values for colors.
     pub colors: Colors,
 
@@ -78,6 +95,10 @@ pub struct UiConfig {
     #[config(skip)]
     pub config_paths: Vec<PathBuf>,
 
+    /// Options set by the config files and overrides.
+    #[config(skip)]
+    pub config_value: Table,
+
     /// Regex hints for interacting with terminal content.
     pub hints: Hints,
 
@@ -115,22 +136,45 @@ pub struct UiConfig {
 impl UiConfig {
     /// Derive [`TermConfig`] from the config.
     pub fn term_options(&self) -> TermConfig {
+        let hardening = self.terminal.hardening;
         TermConfig {
             semantic_escape_chars: self.selection.semantic_escape_chars.clone(),
+            semantic_patterns: self.selection.semantic_patterns.clone(),
             scrolling_history: self.scrolling.history() as usize,
+            alt_screen_history: self.scrolling.alt_screen_history() as usize,
             vi_mode_cursor_style: self.cursor.vi_mode_style(),
             default_cursor_style: self.cursor.style(),
-            osc52: self.terminal.osc52.0,
+            osc52: if hardening { Osc52::Disabled } else { self.terminal.osc52.0 },
+            scroll_region_history: self.scrolling.region_history.0,
+            copy_hidden: self.selection.copy_hidden,
+            window_reports: self.terminal.window_reports && !hardening,
+            titles: !hardening,
+            hyperlinks: !hardening,
+            dynamic_colors: !hardening,
+            notifications: !hardening,
+            mouse_cursor_icons: !hardening,
+            background_alpha: (self.terminal.report_opacity && !hardening).then(|| {
+                (self.window_opacity() * f32::from(u16::MAX)).round() as u16
+            }),
             kitty_keyboard: true,
         }
     }
 
     /// Derive [`PtyOptions`] from the config.
     pub fn pty_config(&self) -> PtyOptions {
-        let shell = self.terminal.shell.clone().or_else(|| self.shell.clone()).map(Into::into);
+        let shell = self.terminal.shell.clone().or_else(|| self.shell.clone());
+        #[cfg(windows)]
+        let shell = shell.or_else(|| self.terminal.wsl.shell());
+        let shell = shell.map(Into::into);
         let working_directory =
             self.working_directory.clone().or_else(|| self.general.working_directory.clone());
-        PtyOptions { working_directory, shell, drain_on_exit: false, env: HashMap::new() }
+        PtyOptions {
+            working_directory,
+            shell,
+            drain_on_exit: false,
+            env: HashMap::new(),
+            env_remove: Vec::new(),
+        }
     }
 
     /// Generate key bindings for all keyboard hints.
@@ -254,6 +298,9 @@ pub struct Hints {
 
     /// All configured terminal hints.
     pub enabled: Vec<Rc<Hint>>,
+
+    /// Programs used by the `Open` hint action.
+    pub opener: Opener,
 }
 
 impl Default for Hints {
@@ -263,20 +310,10 @@ impl Default for Hints {
         let regex = LazyRegex(Rc::new(RefCell::new(pattern)));
         let content = HintContent::new(Some(regex), true);
 
-        #[cfg(not(any(target_os = "macos", windows)))]
-        let action = HintAction::Command(Program::Just(String::from("xdg-open")));
-        #[cfg(target_os = "macos")]
-        let action = HintAction::Command(Program::Just(String::from("open")));
-        #[cfg(windows)]
-        let action = HintAction::Command(Program::WithArgs {
-            program: String::from("cmd"),
-            args: vec!["/c".to_string(), "start".to_string(), "".to_string()],
-        });
-
         Self {
             enabled: vec![Rc::new(Hint {
                 content,
-                action,
+                action: HintAction::Action(HintInternalAction::Open),
                 persist: false,
                 post_processing: true,
                 mouse: Some(HintMouse { enabled: true, mods: Default::default() }),
@@ -290,6 +327,43 @@ impl Default for Hints {
                 }),
             })],
             alphabet: Default::default(),
+            opener: Default::default(),
+        }
+    }
+}
+
+/// Programs for opening URLs and paths.
+#[derive(ConfigDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Opener {
+    /// Program used for schemes without a dedicated program.
+    pub program: Program,
+
+    /// URI schemes which are allowed to be opened.
+    pub schemes: Vec<String>,
+
+    /// Programs for specific URI schemes.
+    pub programs: HashMap<String, Program>,
+}
+
+impl Default for Opener {
+    fn default() -> Self {
+        #[cfg(not(any(target_os = "macos", windows)))]
+        let program = Program::Just(String::from("xdg-open"));
+        #[cfg(target_os = "macos")]
+        let program = Program::Just(String::from("open"));
+        // Unlike `cmd /c start`, explorer doesn't interpret its argument as a shell command.
+        #[cfg(windows)]
+        let program = Program::Just(String::from("explorer"));
+
+        let schemes = [
+            "ipfs", "ipns", "magnet", "mailto", "gemini", "gopher", "https", "http", "news", "file",
+            "git", "ssh", "ftp",
+        ];
+
+        Self {
+            program,
+            schemes: schemes.into_iter().map(String::from).collect(),
+            programs: Default::default(),
         }
     }
 }
@@ -344,6 +418,8 @@ pub enum HintInternalAction {
     Select,
     /// Move the vi mode cursor to the beginning of the hint.
     MoveViModeCursor,
+    /// Open the URL or path with the configured opener.
+    Open,
 }
 
 /// Actions for hint bindings.
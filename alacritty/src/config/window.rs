@@ -1,4 +1,5 @@
 use std::fmt::{self, Formatter};
+use std::time::Duration;
 
 use log::{error, warn};
 use serde::de::{self, MapAccess, Visitor};
@@ -12,6 +13,7 @@ use alacritty_config_derive::{ConfigDeserialize, SerdeReplace};
 
 use crate::config::ui_config::{Delta, Percentage};
 use crate::config::LOG_TARGET_CONFIG;
+use crate::display::color::Rgb;
 
 /// Default Alacritty name, used for window title and class.
 pub const DEFAULT_NAME: &str = "Alacritty";
@@ -37,6 +39,9 @@ pub struct WindowConfig {
     /// Use dynamic title.
     pub dynamic_title: bool,
 
+    /// Filtering of title changes requested by applications.
+    pub title_updates: TitleUpdates,
+
     /// Information to identify a particular window.
     #[config(flatten)]
     pub identity: Identity,
@@ -53,37 +58,58 @@ pub struct WindowConfig {
     /// Resize increments.
     pub resize_increments: bool,
 
+    /// Show the grid dimensions while resizing.
+    pub resize_overlay: bool,
+
     /// Pixel padding.
     padding: Delta<u16>,
 
+    /// Border drawn inside the window.
+    pub border: WindowBorder,
+
+    /// Composition of the UI elements drawn on top of the terminal.
+    pub overlays: Overlays,
+
     /// Initial dimensions.
     dimensions: Dimensions,
 
+    /// Grid kept visible by adjusting the font size whenever the window is resized.
+    fit_grid: Option<Dimensions>,
+
     /// System decorations theme variant.
     decorations_theme_variant: Option<Theme>,
 
     /// Window level.
     pub level: WindowLevel,
+
+    /// Confirmation required before closing the window.
+    pub confirm_close: ConfirmClose,
 }
 
 impl Default for WindowConfig {
     fn default() -> Self {
         Self {
             dynamic_title: true,
+            title_updates: Default::default(),
             blur: Default::default(),
             embed: Default::default(),
             padding: Default::default(),
+            border: Default::default(),
+            overlays: Default::default(),
             opacity: Default::default(),
             position: Default::default(),
             identity: Default::default(),
             dimensions: Default::default(),
+            fit_grid: Default::default(),
             decorations: Default::default(),
             startup_mode: Default::default(),
             dynamic_padding: Default::default(),
             resize_increments: Default::default(),
+            resize_overlay: Default::default(),
             decorations_theme_variant: Default::default(),
             option_as_alt: Default::default(),
             level: Default::default(),
+            confirm_close: Default::default(),
         }
     }
 }
@@ -121,10 +147,18 @@ impl WindowConfig {
         }
     }
 
+    /// Grid dimensions the font size is adjusted to.
+    #[inline]
+    pub fn fit_grid(&self) -> Option<Dimensions> {
+        self.fit_grid.filter(|dimensions| dimensions.columns != 0 && dimensions.lines != 0)
+    }
+
     #[inline]
     pub fn padding(&self, scale_factor: f32) -> (f32, f32) {
-        let padding_x = (f32::from(self.padding.x) * scale_factor).floor();
-        let padding_y = (f32::from(self.padding.y) * scale_factor).floor();
+        // Keep the terminal content clear of the border.
+        let border = f32::from(self.border.width);
+        let padding_x = ((f32::from(self.padding.x) + border) * scale_factor).floor();
+        let padding_y = ((f32::from(self.padding.y) + border) * scale_factor).floor();
         (padding_x, padding_y)
     }
 
@@ -202,6 +236,91 @@ pub struct Dimensions {
     pub lines: usize,
 }
 
+/// Filtering of title changes requested by applications.
+#[derive(ConfigDeserialize, Default, Debug, Clone, PartialEq, Eq)]
+pub struct TitleUpdates {
+    /// Minimum time between title changes in milliseconds.
+    min_interval: u16,
+
+    /// Titles containing any of these strings are ignored.
+    ignore: Vec<String>,
+}
+
+impl TitleUpdates {
+    #[inline]
+    pub fn min_interval(&self) -> Duration {
+        Duration::from_millis(u64::from(self.min_interval))
+    }
+
+    /// Check if a title change should be ignored.
+    pub fn is_ignored(&self, title: &str) -> bool {
+        self.ignore.iter().any(|ignored| title.contains(ignored.as_str()))
+    }
+}
+
+/// Configuration of the UI layers drawn on top of the terminal.
+#[derive(ConfigDeserialize, Default, Debug, Copy, Clone, PartialEq)]
+pub struct Overlays {
+    pub decorations: OverlayLayer,
+    pub cursor: OverlayLayer,
+    pub mode_indicator: OverlayLayer,
+    pub border: OverlayLayer,
+    pub visual_bell: OverlayLayer,
+    pub ime: OverlayLayer,
+    pub message_bar: OverlayLayer,
+}
+
+/// Configuration of a single UI layer.
+#[derive(ConfigDeserialize, Default, Debug, Copy, Clone, PartialEq)]
+pub struct OverlayLayer {
+    /// Drawing order relative to the other layers.
+    pub z_index: Option<i8>,
+
+    /// Opacity applied to the layer.
+    pub opacity: Percentage,
+
+    /// Restrict the layer to the terminal grid, excluding the padding.
+    pub clip: bool,
+}
+
+/// Border drawn along the window edges.
+#[derive(ConfigDeserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct WindowBorder {
+    /// Border width in pixels.
+    pub width: u16,
+
+    /// Border color while the window is focused.
+    pub focused: Rgb,
+
+    /// Border color while the window is not focused.
+    pub unfocused: Rgb,
+
+    /// Border color while the window is requesting attention.
+    pub urgent: Rgb,
+}
+
+impl Default for WindowBorder {
+    fn default() -> Self {
+        Self {
+            width: 0,
+            focused: Rgb::new(0x7a, 0xa6, 0xda),
+            unfocused: Rgb::new(0x42, 0x42, 0x42),
+            urgent: Rgb::new(0xd5, 0x4e, 0x53),
+        }
+    }
+}
+
+impl WindowBorder {
+    /// Border color for the window state.
+    pub fn color(&self, focused: bool, urgent: bool) -> Rgb {
+        match (focused, urgent) {
+            (true, _) => self.focused,
+            (false, true) => self.urgent,
+            (false, false) => self.unfocused,
+        }
+    }
+}
+
 /// Window class hint.
 #[derive(SerdeReplace, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct Class {
@@ -318,6 +437,20 @@ pub enum WindowLevel {
     AlwaysOnTop,
 }
 
+/// Confirmation required before closing the window.
+#[derive(ConfigDeserialize, Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConfirmClose {
+    /// Close the window immediately.
+    #[default]
+    Never,
+
+    /// Always confirm closing the window.
+    Always,
+
+    /// Confirm closing the window while a process is running in the foreground.
+    IfProcessRunning,
+}
+
 impl From<WindowLevel> for WinitWindowLevel {
     fn from(level: WindowLevel) -> Self {
         match level {
@@ -22,6 +22,8 @@ use windows_sys::Win32::System::Threading::{CREATE_NEW_PROCESS_GROUP, CREATE_NO_
 
 #[cfg(target_os = "macos")]
 use crate::macos;
+#[cfg(not(any(target_os = "macos", windows)))]
+use crate::renderer;
 
 /// Start a new process in the background.
 #[cfg(windows)]
@@ -30,12 +32,19 @@ where
     I: IntoIterator<Item = S> + Copy,
     S: AsRef<OsStr>,
 {
+    let mut command = Command::new(program);
+    command.args(args);
+    spawn_daemon_command(command)
+}
+
+/// Start a prepared command in the background.
+#[cfg(windows)]
+pub fn spawn_daemon_command(mut command: Command) -> io::Result<()> {
     // Setting all the I/O handles to null and setting the
     // CREATE_NEW_PROCESS_GROUP and CREATE_NO_WINDOW has the effect
     // that console applications will run without opening a new
     // console window.
-    Command::new(program)
-        .args(args)
+    command
         .stdin(Stdio::null())
         .stdout(Stdio::null())
         .stderr(Stdio::null())
@@ -57,7 +66,20 @@ where
     S: AsRef<OsStr>,
 {
     let mut command = Command::new(program);
-    command.args(args).stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+    command.args(args);
+    spawn_daemon_command(command, master_fd, shell_pid)
+}
+
+/// Start a prepared command in the background.
+#[cfg(not(windows))]
+pub fn spawn_daemon_command(
+    mut command: Command,
+    master_fd: RawFd,
+    shell_pid: u32,
+) -> io::Result<()> {
+    command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+    #[cfg(not(target_os = "macos"))]
+    renderer::platform::restore_gpu_env_command(&mut command);
     if let Ok(cwd) = foreground_process_path(master_fd, shell_pid) {
         command.current_dir(cwd);
     }
@@ -15,7 +15,7 @@ use crate::config::color::Colors;
 /// Factor for automatic computation of dim colors.
 pub const DIM_FACTOR: f32 = 0.66;
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub struct List([Rgb; COUNT]);
 
 impl From<&'_ Colors> for List {
@@ -0,0 +1,124 @@
+use std::time::{Duration, Instant};
+
+use alacritty_terminal::term::color::COUNT;
+
+use crate::display::color::List;
+
+/// Animated transition between two color palettes.
+pub struct ColorTransition {
+    /// Palette at the start of the transition.
+    from: List,
+
+    /// Palette at the end of the transition.
+    to: List,
+
+    /// Total duration of the transition.
+    duration: Duration,
+
+    /// The time the transition started, if it is still in progress.
+    started_at: Option<Instant>,
+}
+
+impl ColorTransition {
+    pub fn new(colors: List) -> Self {
+        Self { from: colors, to: colors, duration: Duration::ZERO, started_at: None }
+    }
+
+    /// Transition to a new palette, starting from the currently visible colors.
+    pub fn start(&mut self, to: List, duration: Duration) {
+        if to == self.to {
+            return;
+        }
+
+        let now = Instant::now();
+        self.from = self.colors_at_instant(now);
+        self.to = to;
+        self.duration = duration;
+        self.started_at = Some(now);
+    }
+
+    /// Get the currently visible palette.
+    pub fn colors(&self) -> List {
+        self.colors_at_instant(Instant::now())
+    }
+
+    /// Check whether or not the transition has finished.
+    pub fn completed(&mut self) -> bool {
+        let finished = self.started_at.map_or(true, |started_at| {
+            Instant::now().saturating_duration_since(started_at) >= self.duration
+        });
+        if finished {
+            self.started_at = None;
+        }
+
+        finished
+    }
+
+    /// Get the visible palette at a particular instant.
+    fn colors_at_instant(&self, instant: Instant) -> List {
+        let elapsed = match self.started_at {
+            Some(started_at) => instant.saturating_duration_since(started_at),
+            None => return self.to,
+        };
+
+        if elapsed >= self.duration {
+            return self.to;
+        }
+
+        let progress = elapsed.as_secs_f32() / self.duration.as_secs_f32();
+        let mut colors = self.to;
+        for i in (0..COUNT).filter(|&i| self.from[i] != self.to[i]) {
+            colors[i] = self.from[i] * (1. - progress) + self.to[i] * progress;
+        }
+        colors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alacritty_terminal::vte::ansi::NamedColor;
+
+    use crate::config::color::Colors;
+    use crate::display::color::Rgb;
+
+    #[test]
+    fn interpolate_palette() {
+        let mut from = List::from(&Colors::default());
+        from[NamedColor::Background] = Rgb::new(0, 0, 0);
+        let mut to = from;
+        to[NamedColor::Background] = Rgb::new(200, 100, 50);
+
+        let mut transition = ColorTransition::new(from);
+        assert!(transition.completed());
+
+        let duration = Duration::from_secs(10);
+        transition.start(to, duration);
+        let started_at = transition.started_at.unwrap();
+
+        assert!(transition.colors_at_instant(started_at) == from);
+        let halfway = transition.colors_at_instant(started_at + duration / 2);
+        assert_eq!(halfway[NamedColor::Background], Rgb::new(100, 50, 25));
+        assert_eq!(halfway[NamedColor::Foreground], to[NamedColor::Foreground]);
+        assert!(transition.colors_at_instant(started_at + duration) == to);
+        assert!(!transition.completed());
+
+        // Restarting with the same palette keeps the transition going.
+        transition.start(to, duration);
+        assert_eq!(transition.started_at, Some(started_at));
+    }
+
+    #[test]
+    fn instant_without_duration() {
+        let from = List::from(&Colors::default());
+        let mut to = from;
+        to[NamedColor::Foreground] = Rgb::new(1, 2, 3);
+
+        let mut transition = ColorTransition::new(from);
+        transition.start(to, Duration::ZERO);
+
+        assert!(transition.colors() == to);
+        assert!(transition.completed());
+    }
+}
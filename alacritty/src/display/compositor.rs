@@ -0,0 +1,134 @@
+//! Composition of the UI layers drawn on top of the terminal grid.
+
+use alacritty_terminal::grid::Dimensions;
+
+use crate::config::window::{OverlayLayer, Overlays};
+use crate::display::SizeInfo;
+use crate::renderer::rects::RenderRect;
+
+/// UI layer drawn on top of the terminal grid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Layer {
+    /// Underlines, strikeouts and other cell decorations.
+    Decorations,
+    /// Terminal, vi mode and search bar cursors.
+    Cursor,
+    /// Border indicating input modes.
+    ModeIndicator,
+    /// Window border.
+    Border,
+    /// Visual bell flash.
+    VisualBell,
+    /// Preedit text of the input method.
+    Ime,
+    /// Background of the message bar.
+    MessageBar,
+}
+
+impl Layer {
+    /// Drawing order of this layer, defaulting to the declaration order.
+    fn z_index(self, overlays: &Overlays) -> i16 {
+        self.config(overlays).z_index.map_or(self as i16, i16::from)
+    }
+
+    /// Configuration of this layer.
+    fn config(self, overlays: &Overlays) -> &OverlayLayer {
+        match self {
+            Self::Decorations => &overlays.decorations,
+            Self::Cursor => &overlays.cursor,
+            Self::ModeIndicator => &overlays.mode_indicator,
+            Self::Border => &overlays.border,
+            Self::VisualBell => &overlays.visual_bell,
+            Self::Ime => &overlays.ime,
+            Self::MessageBar => &overlays.message_bar,
+        }
+    }
+}
+
+/// Rectangles of all UI layers in a frame.
+#[derive(Default, Debug)]
+pub struct Compositor {
+    rects: Vec<(Layer, RenderRect)>,
+}
+
+impl Compositor {
+    /// Add a rectangle to a layer.
+    #[inline]
+    pub fn push(&mut self, layer: Layer, rect: RenderRect) {
+        self.rects.push((layer, rect));
+    }
+
+    /// Add multiple rectangles to a layer.
+    #[inline]
+    pub fn extend<I: IntoIterator<Item = RenderRect>>(&mut self, layer: Layer, rects: I) {
+        self.rects.extend(rects.into_iter().map(|rect| (layer, rect)));
+    }
+
+    /// Get the rectangles in drawing order.
+    ///
+    /// Layers are ordered by their z-index, while rectangles within the same layer keep the
+    /// order they were added in.
+    pub fn compose(mut self, overlays: &Overlays, size_info: &SizeInfo) -> Vec<RenderRect> {
+        self.rects.sort_by_key(|(layer, _)| layer.z_index(overlays));
+
+        self.rects
+            .into_iter()
+            .filter_map(|(layer, mut rect)| {
+                let config = layer.config(overlays);
+                rect.alpha *= config.opacity.as_f32();
+                if config.clip {
+                    clip(&mut rect, size_info)?;
+                }
+                Some(rect)
+            })
+            .collect()
+    }
+}
+
+/// Restrict a rectangle to the terminal grid, excluding the padding.
+///
+/// Returns `None` if the rectangle is entirely outside of the grid.
+fn clip(rect: &mut RenderRect, size_info: &SizeInfo) -> Option<()> {
+    let left = size_info.padding_x();
+    let top = size_info.padding_y();
+    let right = left + size_info.columns() as f32 * size_info.cell_width();
+    let bottom = top + size_info.screen_lines() as f32 * size_info.cell_height();
+
+    let x = rect.x.max(left);
+    let y = rect.y.max(top);
+    let width = (rect.x + rect.width).min(right) - x;
+    let height = (rect.y + rect.height).min(bottom) - y;
+    if width <= 0. || height <= 0. {
+        return None;
+    }
+
+    *rect = RenderRect { x, y, width, height, ..*rect };
+
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::display::color::Rgb;
+
+    #[test]
+    fn compose_by_z_index() {
+        let size_info = SizeInfo::new(100., 100., 10., 10., 5., 5., false);
+        let color = Rgb::new(0, 0, 0);
+
+        let mut overlays = Overlays::default();
+        overlays.visual_bell.z_index = Some(-1);
+        overlays.visual_bell.clip = true;
+
+        let mut compositor = Compositor::default();
+        compositor.push(Layer::MessageBar, RenderRect::new(0., 90., 100., 10., color, 1.));
+        compositor.push(Layer::VisualBell, RenderRect::new(0., 0., 100., 100., color, 1.));
+
+        let rects = compositor.compose(&overlays, &size_info);
+        assert_eq!(rects.len(), 2);
+        assert_eq!((rects[0].x, rects[0].y, rects[0].width, rects[0].height), (5., 5., 90., 90.));
+        assert_eq!(rects[1].y, 90.);
+    }
+}
@@ -4,7 +4,7 @@ use std::ops::Deref;
 use std::{cmp, mem};
 
 use alacritty_terminal::event::EventListener;
-use alacritty_terminal::grid::{Dimensions, Indexed};
+use alacritty_terminal::grid::{Dimensions, Grid, GridCell, Indexed};
 use alacritty_terminal::index::{Column, Line, Point};
 use alacritty_terminal::selection::SelectionRange;
 use alacritty_terminal::term::cell::{Cell, Flags, Hyperlink};
@@ -26,6 +26,7 @@ pub const MIN_CURSOR_CONTRAST: f64 = 1.5;
 /// This provides the terminal cursor and an iterator over all non-empty cells.
 pub struct RenderableContent<'a> {
     terminal_content: TerminalContent<'a>,
+    grid: &'a Grid<Cell>,
     cursor: RenderableCursor,
     cursor_shape: CursorShape,
     cursor_point: Point<usize>,
@@ -35,6 +36,7 @@ pub struct RenderableContent<'a> {
     colors: &'a List,
     focused_match: Option<&'a Match>,
     size: &'a SizeInfo,
+    reveal_hidden: bool,
 }
 
 impl<'a> RenderableContent<'a> {
@@ -76,7 +78,9 @@ impl<'a> RenderableContent<'a> {
         Self {
             colors: &display.colors,
             size: &display.size_info,
+            reveal_hidden: display.reveal_hidden.is_some(),
             cursor: RenderableCursor::new_hidden(),
+            grid: term.grid(),
             terminal_content,
             focused_match,
             cursor_shape,
@@ -109,6 +113,25 @@ impl<'a> RenderableContent<'a> {
         self.terminal_content.selection
     }
 
+    /// Check if a cell's glyph should overflow into the following empty cell.
+    ///
+    /// This only affects rendering, the terminal grid still uses a single cell for the glyph.
+    fn overflows(&self, cell: &RenderableCell) -> bool {
+        if cell.flags.contains(Flags::WIDE_CHAR)
+            || !self.config.font.is_double_width(cell.character)
+        {
+            return false;
+        }
+
+        let next = Point::new(cell.point.line, cell.point.column + 1);
+        if next.column >= self.grid.columns() || next == self.cursor_point {
+            return false;
+        }
+
+        let next = term::viewport_to_point(self.display_offset(), next);
+        self.grid[next].is_empty()
+    }
+
     /// Assemble the information required to render the terminal cursor.
     fn renderable_cursor(&mut self, cell: &RenderableCell) -> RenderableCursor {
         // Cursor colors.
@@ -130,9 +153,15 @@ impl<'a> RenderableContent<'a> {
         let mut cursor_color = cursor_color.color(cell.fg, cell.bg);
 
         // Invert cursor color with insufficient contrast to prevent invisible cursors.
+        let primary = &self.config.colors.primary;
         if insufficient_contrast {
-            cursor_color = self.config.colors.primary.foreground;
-            text_color = self.config.colors.primary.background;
+            cursor_color = primary.foreground;
+            text_color = primary.background;
+        } else if (self.config.cursor.ensure_contrast || self.config.general.increase_contrast())
+            && cursor_color.contrast(*cell.bg) < MIN_CURSOR_CONTRAST
+        {
+            (cursor_color, text_color) =
+                contrasting_cursor_colors(cell.bg, primary.foreground, primary.background);
         }
 
         let width = if cell.flags.contains(Flags::WIDE_CHAR) {
@@ -163,6 +192,10 @@ impl Iterator for RenderableContent<'_> {
             let cell = self.terminal_content.display_iter.next()?;
             let mut cell = RenderableCell::new(self, cell);
 
+            if self.overflows(&cell) {
+                cell.flags.insert(Flags::WIDE_CHAR);
+            }
+
             if self.cursor_point == cell.point {
                 // Store the cursor which should be rendered.
                 self.cursor = self.renderable_cursor(&cell);
@@ -218,7 +251,7 @@ impl RenderableCell {
             Self::compute_bg_alpha(content.config, cell.bg)
         };
 
-        let is_selected = content.terminal_content.selection.map_or(false, |selection| {
+        let is_selected = content.terminal_content.selection.is_some_and(|selection| {
             selection.contains_cell(
                 &cell,
                 content.terminal_content.cursor.point,
@@ -232,6 +265,10 @@ impl RenderableCell {
         let mut character = cell.c;
         let mut flags = cell.flags;
 
+        if content.reveal_hidden {
+            flags.remove(Flags::HIDDEN);
+        }
+
         let num_cols = content.size.columns();
         if let Some((c, is_first)) = content
             .hint
@@ -256,14 +293,14 @@ impl RenderableCell {
             let config_bg = colors.selection.background;
             Self::compute_cell_rgb(&mut fg, &mut bg, &mut bg_alpha, config_fg, config_bg);
 
-            if fg == bg && !cell.flags.contains(Flags::HIDDEN) {
+            if fg == bg && !flags.contains(Flags::HIDDEN) {
                 // Reveal inversed text when fg/bg is the same.
                 fg = content.color(NamedColor::Background as usize);
                 bg = content.color(NamedColor::Foreground as usize);
                 bg_alpha = 1.0;
             }
-        } else if content.search.as_mut().map_or(false, |search| search.advance(cell.point)) {
-            let focused = content.focused_match.map_or(false, |fm| fm.contains(&cell.point));
+        } else if content.search.as_mut().is_some_and(|search| search.advance(cell.point)) {
+            let focused = content.focused_match.is_some_and(|fm| fm.contains(&cell.point));
             let (config_fg, config_bg) = if focused {
                 (colors.search.focused_match.foreground, colors.search.focused_match.background)
             } else {
@@ -550,3 +587,31 @@ impl Deref for HintMatches<'_> {
         self.matches.deref()
     }
 }
+
+/// Pick the color standing out the most against a cell's background for the cursor.
+///
+/// Returns the cursor color and the color for the text below it.
+fn contrasting_cursor_colors(cell_bg: Rgb, foreground: Rgb, background: Rgb) -> (Rgb, Rgb) {
+    if foreground.contrast(*cell_bg) >= background.contrast(*cell_bg) {
+        (foreground, background)
+    } else {
+        (background, foreground)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_contrast() {
+        let white = Rgb::new(0xff, 0xff, 0xff);
+        let black = Rgb::new(0, 0, 0);
+
+        assert_eq!(contrasting_cursor_colors(white, white, black), (black, white));
+        assert_eq!(contrasting_cursor_colors(black, white, black), (white, black));
+
+        let gray = Rgb::new(0x40, 0x40, 0x40);
+        assert_eq!(contrasting_cursor_colors(gray, black, white), (white, black));
+    }
+}
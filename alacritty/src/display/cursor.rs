@@ -7,6 +7,12 @@ use crate::display::content::RenderableCursor;
 use crate::display::SizeInfo;
 use crate::renderer::rects::RenderRect;
 
+/// Size of the cursor ring relative to the cell height.
+const RING_SIZE: f32 = 2.5;
+
+/// Width of the cursor ring's outline in pixels.
+const RING_THICKNESS: f32 = 3.;
+
 /// Trait for conversion into the iterator.
 pub trait IntoRects {
     /// Consume the cursor for an iterator of rects.
@@ -35,6 +41,23 @@ impl IntoRects for RenderableCursor {
     }
 }
 
+/// Create an iterator yielding the rects of a ring centered on the cursor.
+///
+/// The ring makes the cursor easier to follow, like when sharing the screen.
+pub fn ring(cursor: &RenderableCursor, size_info: &SizeInfo, scale_factor: f32) -> CursorRects {
+    let point = cursor.point();
+    let width = size_info.cell_width() * cursor.width().get() as f32;
+    let center_x = point.column.0 as f32 * size_info.cell_width() + width / 2.;
+    let center_y = (point.line as f32 + 0.5) * size_info.cell_height();
+
+    let size = (RING_SIZE * size_info.cell_height()).round();
+    let x = center_x + size_info.padding_x() - size / 2.;
+    let y = center_y + size_info.padding_y() - size / 2.;
+    let thickness = (RING_THICKNESS * scale_factor).round();
+
+    hollow(x, y, size, size, thickness, cursor.color())
+}
+
 /// Cursor rect iterator.
 #[derive(Default)]
 pub struct CursorRects {
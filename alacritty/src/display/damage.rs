@@ -1,4 +1,5 @@
 use std::iter::Peekable;
+use std::ops::Range;
 use std::{cmp, mem};
 
 use glutin::surface::Rect;
@@ -20,6 +21,8 @@ pub struct DamageTracker {
     pub old_selection: Option<SelectionRange>,
     /// Highlight damage submitted for the compositor.
     pub debug: bool,
+    /// Damage accumulated over all frames for debugging.
+    pub heatmap: Option<DamageHeatmap>,
 
     /// The damage for the frames.
     frames: [FrameDamage; 2],
@@ -33,6 +36,7 @@ impl DamageTracker {
             columns,
             screen_lines,
             debug: false,
+            heatmap: None,
             old_vi_cursor: None,
             old_selection: None,
             frames: Default::default(),
@@ -56,6 +60,10 @@ impl DamageTracker {
     /// Advance to the next frame resetting the state for the active frame.
     #[inline]
     pub fn swap_damage(&mut self) {
+        if let Some(heatmap) = &mut self.heatmap {
+            heatmap.accumulate(&self.frames[0]);
+        }
+
         let screen_lines = self.screen_lines;
         let columns = self.columns;
         self.frame().reset(screen_lines, columns);
@@ -70,6 +78,17 @@ impl DamageTracker {
             frame.reset(screen_lines, columns);
         }
         self.frame().full = true;
+
+        if let Some(heatmap) = &mut self.heatmap {
+            *heatmap = DamageHeatmap::new(screen_lines, columns);
+        }
+    }
+
+    /// Enable or disable accumulation of the damage heatmap.
+    pub fn set_heatmap_enabled(&mut self, enabled: bool) {
+        if enabled != self.heatmap.is_some() {
+            self.heatmap = enabled.then(|| DamageHeatmap::new(self.screen_lines, self.columns));
+        }
     }
 
     /// Damage vi cursor inside the viewport.
@@ -164,6 +183,18 @@ impl FrameDamage {
         self.full = true;
     }
 
+    /// Check if the entire frame needs to be redrawn.
+    #[inline]
+    pub fn is_fully_damaged(&self) -> bool {
+        self.full
+    }
+
+    /// Damage of all terminal lines in the frame.
+    #[inline]
+    pub fn lines(&self) -> &[LineDamageBounds] {
+        &self.lines
+    }
+
     /// Add viewport rectangle to damage.
     ///
     /// This allows covering elements outside of the terminal viewport, like message bar.
@@ -202,6 +233,58 @@ impl FrameDamage {
     }
 }
 
+/// Number of times each cell in the viewport was damaged.
+#[derive(Debug)]
+pub struct DamageHeatmap {
+    counts: Vec<u32>,
+    columns: usize,
+    max: u32,
+}
+
+impl DamageHeatmap {
+    fn new(screen_lines: usize, columns: usize) -> Self {
+        Self { counts: vec![0; screen_lines * columns], columns, max: 0 }
+    }
+
+    /// Add the damage of a frame.
+    fn accumulate(&mut self, frame: &FrameDamage) {
+        for (line, counts) in self.counts.chunks_exact_mut(self.columns).enumerate() {
+            let columns = match frame.lines.get(line) {
+                _ if frame.full => 0..counts.len(),
+                Some(damage) if damage.is_damaged() => {
+                    damage.left..cmp::min(damage.right + 1, counts.len())
+                },
+                _ => continue,
+            };
+
+            for count in &mut counts[columns] {
+                *count = count.saturating_add(1);
+                self.max = cmp::max(self.max, *count);
+            }
+        }
+    }
+
+    /// Damaged cell ranges with their damage count relative to the most damaged cell.
+    pub fn regions(&self) -> impl Iterator<Item = (usize, Range<usize>, f32)> + '_ {
+        let max = self.max as f32;
+        self.counts.chunks_exact(self.columns).enumerate().flat_map(move |(line, counts)| {
+            let mut start = 0;
+            let mut regions = Vec::new();
+            for column in 1..=counts.len() {
+                if column < counts.len() && counts[column] == counts[start] {
+                    continue;
+                }
+
+                if counts[start] != 0 {
+                    regions.push((line, start..column, counts[start] as f32 / max));
+                }
+                start = column;
+            }
+            regions
+        })
+    }
+}
+
 /// Convert viewport `y` coordinate to [`Rect`] damage coordinate.
 pub fn viewport_y_to_damage_y(size_info: &SizeInfo, y: i32, height: i32) -> i32 {
     size_info.height() as i32 - y - height
@@ -302,6 +385,8 @@ fn merge_rects(lhs: Rect, rhs: Rect) -> Rect {
 mod tests {
     use super::*;
 
+    use alacritty_terminal::index::Column;
+
     #[test]
     fn damage_rect_math() {
         let rect_side = 10;
@@ -346,6 +431,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn heatmap_accumulation() {
+        let mut heatmap = DamageHeatmap::new(2, 4);
+        let mut frame = FrameDamage::default();
+        frame.reset(2, 4);
+
+        frame.damage_line(LineDamageBounds::new(0, 1, 2));
+        heatmap.accumulate(&frame);
+        frame.reset(2, 4);
+        frame.damage_point(Point::new(0, Column(2)));
+        heatmap.accumulate(&frame);
+
+        let regions: Vec<_> = heatmap.regions().collect();
+        assert_eq!(regions, vec![(0, 1..2, 0.5), (0, 2..3, 1.)]);
+
+        frame.mark_fully_damaged();
+        heatmap.accumulate(&frame);
+
+        let regions: Vec<_> = heatmap.regions().collect();
+        assert_eq!(regions, vec![
+            (0, 0..1, 1. / 3.),
+            (0, 1..2, 2. / 3.),
+            (0, 2..3, 1.),
+            (0, 3..4, 1. / 3.),
+            (1, 0..4, 1. / 3.)
+        ]);
+    }
+
     #[test]
     fn add_viewport_damage() {
         let mut frame_damage = FrameDamage::default();
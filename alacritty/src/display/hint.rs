@@ -1,10 +1,8 @@
 use std::borrow::Cow;
 use std::cmp::Reverse;
-use std::collections::HashSet;
 use std::iter;
 use std::rc::Rc;
 
-use ahash::RandomState;
 use winit::keyboard::ModifiersState;
 
 use alacritty_terminal::grid::{BidirectionalIterator, Dimensions};
@@ -332,40 +330,9 @@ pub fn visible_regex_match_iter<'a, T>(
 }
 
 /// Iterate over all visible hyperlinks, yanking only unique ones.
-pub fn visible_unique_hyperlinks_iter<T>(term: &Term<T>) -> impl Iterator<Item = Match> + '_ {
-    let mut display_iter = term.grid().display_iter().peekable();
-
-    // Avoid creating hints for the same hyperlinks, but from a different places.
-    let mut unique_hyperlinks = HashSet::<Hyperlink, RandomState>::default();
-
-    iter::from_fn(move || {
-        // Find the start of the next unique hyperlink.
-        let (cell, hyperlink) = display_iter.find_map(|cell| {
-            let hyperlink = cell.hyperlink()?;
-            (!unique_hyperlinks.contains(&hyperlink)).then(|| {
-                unique_hyperlinks.insert(hyperlink.clone());
-                (cell, hyperlink)
-            })
-        })?;
-
-        let start = cell.point;
-        let mut end = start;
-
-        // Find the end bound of just found unique hyperlink.
-        while let Some(next_cell) = display_iter.peek() {
-            // Cell at display iter doesn't match, yield the hyperlink and start over with
-            // `find_map`.
-            if next_cell.hyperlink().as_ref() != Some(&hyperlink) {
-                break;
-            }
-
-            // Advance to the next cell.
-            end = next_cell.point;
-            let _ = display_iter.next();
-        }
-
-        Some(start..=end)
-    })
+pub fn visible_unique_hyperlinks_iter<T>(term: &Term<T>) -> impl Iterator<Item = Match> {
+    let hyperlinks = term.hyperlinks_in_viewport().into_iter();
+    hyperlinks.filter_map(|hyperlink| hyperlink.ranges.into_iter().next())
 }
 
 /// Retrieve the match, if the specified point is inside the content matching the regex.
@@ -396,7 +363,7 @@ pub fn highlighted_at<T>(
 
     config.hints.enabled.iter().find_map(|hint| {
         // Check if all required modifiers are pressed.
-        let highlight = hint.mouse.map_or(false, |mouse| {
+        let highlight = hint.mouse.is_some_and(|mouse| {
             mouse.enabled
                 && mouse_mods.contains(mouse.mods.0)
                 && (!mouse_mode || mouse_mods.contains(ModifiersState::SHIFT))
@@ -422,6 +389,77 @@ pub fn highlighted_at<T>(
     })
 }
 
+/// Find the hint closest to the specified point within its line.
+///
+/// Unlike [`highlighted_at`], this ignores the mouse configuration of all hints. The hint matching
+/// the point is preferred, otherwise the hint nearest to it will be returned.
+pub fn closest_on_line<T>(term: &Term<T>, config: &UiConfig, point: Point) -> Option<HintMatch> {
+    let start = term.line_search_left(point);
+    let end = term.line_search_right(point);
+
+    let mut closest: Option<(usize, HintMatch)> = None;
+    let mut update_closest = |bounds: Match, hyperlink: Option<Hyperlink>, hint: &Rc<Hint>| {
+        let distance = distance(term, point, &bounds);
+        if closest.as_ref().map_or(true, |(closest, _)| distance < *closest) {
+            closest = Some((distance, HintMatch { bounds, hyperlink, hint: hint.clone() }));
+        }
+    };
+
+    for hint in &config.hints.enabled {
+        if hint.content.hyperlinks {
+            let cells = term.grid().iter_from(start).map(|cell| cell.point);
+            let mut hyperlink_end = None;
+            for cell_point in iter::once(start).chain(cells).take_while(|cell| *cell <= end) {
+                // Skip the remaining cells of the last hyperlink.
+                if hyperlink_end.is_some_and(|hyperlink_end| cell_point <= hyperlink_end) {
+                    continue;
+                }
+
+                if let Some((hyperlink, bounds)) = hyperlink_at(term, cell_point) {
+                    hyperlink_end = Some(*bounds.end());
+                    update_closest(bounds, Some(hyperlink), hint);
+                }
+            }
+        }
+
+        let matches = hint.content.regex.as_ref().and_then(|regex| {
+            regex.with_compiled(|regex| {
+                let matches: Vec<_> = RegexIter::new(start, end, Direction::Right, term, regex)
+                    .collect();
+
+                if !hint.post_processing {
+                    return matches;
+                }
+
+                matches
+                    .into_iter()
+                    .flat_map(|rm| HintPostProcessor::new(term, regex, rm).collect::<Vec<_>>())
+                    .collect()
+            })
+        });
+
+        for bounds in matches.into_iter().flatten() {
+            update_closest(bounds, None, hint);
+        }
+    }
+
+    closest.map(|(_, hint)| hint)
+}
+
+/// Number of cells between a point and a match.
+fn distance<T>(term: &Term<T>, point: Point, bounds: &Match) -> usize {
+    let columns = term.columns() as i64;
+    let index = |point: Point| i64::from(point.line.0) * columns + point.column.0 as i64;
+
+    if point < *bounds.start() {
+        (index(*bounds.start()) - index(point)) as usize
+    } else if point > *bounds.end() {
+        (index(point) - index(*bounds.end())) as usize
+    } else {
+        0
+    }
+}
+
 /// Retrieve the hyperlink with its range, if there is one at the specified point.
 ///
 /// This will only return contiguous cells, even if another hyperlink with the same ID exists.
@@ -432,7 +470,7 @@ fn hyperlink_at<T>(term: &Term<T>, point: Point) -> Option<(Hyperlink, Match)> {
 
     let mut match_end = point;
     for cell in grid.iter_from(point) {
-        if cell.hyperlink().map_or(false, |link| link == hyperlink) {
+        if cell.hyperlink().is_some_and(|link| link == hyperlink) {
             match_end = cell.point;
         } else {
             break;
@@ -442,7 +480,7 @@ fn hyperlink_at<T>(term: &Term<T>, point: Point) -> Option<(Hyperlink, Match)> {
     let mut match_start = point;
     let mut iter = grid.iter_from(point);
     while let Some(cell) = iter.prev() {
-        if cell.hyperlink().map_or(false, |link| link == hyperlink) {
+        if cell.hyperlink().is_some_and(|link| link == hyperlink) {
             match_start = cell.point;
         } else {
             break;
@@ -693,6 +731,57 @@ mod tests {
         assert_eq!(None, unique_hyperlinks.next());
     }
 
+    #[test]
+    fn hyperlink_fragments_are_grouped() {
+        let mut term = mock_term("000 000 000");
+        term.goto(0, 0);
+
+        let hyperlink = Hyperlink::new(Some("1"), String::from("file:///foo"));
+        let other_id = Hyperlink::new(Some("2"), String::from("file:///foo"));
+
+        // Create two fragments of the same hyperlink and one with a different ID.
+        term.set_hyperlink(Some(hyperlink.clone().into()));
+        term.input('a');
+        term.set_hyperlink(None);
+        term.goto(0, 4);
+        term.set_hyperlink(Some(hyperlink.clone().into()));
+        term.input('b');
+        term.set_hyperlink(Some(other_id.clone().into()));
+        term.goto(0, 8);
+        term.input('c');
+        term.set_hyperlink(None);
+
+        let config = UiConfig::default();
+        let point = Point::new(Line(0), Column(0));
+        let hint = highlighted_at(&term, &config, point, ModifiersState::empty()).unwrap();
+
+        assert_eq!(hint.bounds(), &(point..=point));
+        assert!(hint.should_highlight(Point::new(Line(0), Column(4)), Some(&hyperlink)));
+        assert!(!hint.should_highlight(Point::new(Line(0), Column(8)), Some(&other_id)));
+        assert!(!hint.should_highlight(Point::new(Line(0), Column(2)), None));
+    }
+
+    #[test]
+    fn closest_hint_on_line() {
+        let term = mock_term("https://a.org x https://b.org\r\nno hints");
+        let config = UiConfig::default();
+
+        let closest = |column| {
+            let point = Point::new(Line(0), Column(column));
+            closest_on_line(&term, &config, point).map(|hint| hint.bounds().clone())
+        };
+        let first = Point::new(Line(0), Column(0))..=Point::new(Line(0), Column(12));
+        let second = Point::new(Line(0), Column(16))..=Point::new(Line(0), Column(28));
+
+        assert_eq!(closest(5), Some(first.clone()));
+        assert_eq!(closest(14), Some(first));
+        assert_eq!(closest(15), Some(second.clone()));
+        assert_eq!(closest(20), Some(second));
+
+        let point = Point::new(Line(1), Column(0));
+        assert_eq!(closest_on_line(&term, &config, point), None);
+    }
+
     #[test]
     fn visible_regex_match_covers_entire_viewport() {
         let content = "I'm a match!\r\n".repeat(4096);
@@ -3,9 +3,12 @@
 
 use std::cmp;
 use std::fmt::{self, Formatter};
+use std::fs;
+use std::iter;
 use std::mem::{self, ManuallyDrop};
 use std::num::NonZeroU32;
 use std::ops::Deref;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
 use glutin::config::GetGlConfig;
@@ -15,11 +18,12 @@ use glutin::error::ErrorKind;
 use glutin::prelude::*;
 use glutin::surface::{Surface, SwapInterval, WindowSurface};
 
-use log::{debug, info};
+use log::{debug, error, info, warn};
 use parking_lot::MutexGuard;
 use serde::{Deserialize, Serialize};
 use winit::dpi::PhysicalSize;
-use winit::keyboard::ModifiersState;
+use winit::event::MouseButton;
+use winit::keyboard::{ModifiersState, PhysicalKey};
 use winit::raw_window_handle::RawWindowHandle;
 use winit::window::CursorIcon;
 
@@ -30,12 +34,14 @@ use alacritty_terminal::event::{EventListener, OnResize, WindowSize};
 use alacritty_terminal::grid::Dimensions as TermDimensions;
 use alacritty_terminal::index::{Column, Direction, Line, Point};
 use alacritty_terminal::selection::Selection;
-use alacritty_terminal::term::cell::Flags;
+use alacritty_terminal::term::cell::{Flags, Hyperlink};
 use alacritty_terminal::term::{
     self, LineDamageBounds, Term, TermDamage, TermMode, MIN_COLUMNS, MIN_SCREEN_LINES,
 };
 use alacritty_terminal::vte::ansi::{CursorShape, NamedColor};
 
+#[cfg(feature = "audio")]
+use crate::audio::AudibleBell;
 use crate::config::debug::RendererPreference;
 use crate::config::font::Font;
 use crate::config::window::Dimensions;
@@ -44,14 +50,23 @@ use crate::config::window::StartupMode;
 use crate::config::UiConfig;
 use crate::display::bell::VisualBell;
 use crate::display::color::{List, Rgb};
+use crate::display::color_transition::ColorTransition;
+use crate::display::compositor::{Compositor, Layer};
 use crate::display::content::{RenderableContent, RenderableCursor};
 use crate::display::cursor::IntoRects;
 use crate::display::damage::{damage_y_to_viewport_y, DamageTracker};
 use crate::display::hint::{HintMatch, HintState};
 use crate::display::meter::Meter;
+use crate::display::presentation::Presentation;
+use crate::display::resize_overlay::ResizeOverlay;
 use crate::display::window::Window;
 use crate::event::{Event, EventType, Mouse, SearchState};
+use crate::lock_keys::LockKeys;
+#[cfg(unix)]
+use crate::ipc::{self, IpcFrame};
+use crate::logging::LOG_TARGET_CONFIG;
 use crate::message_bar::{MessageBuffer, MessageType};
+use crate::metrics;
 use crate::renderer::rects::{RenderLine, RenderLines, RenderRect};
 use crate::renderer::{self, platform, GlyphCache, Renderer};
 use crate::scheduler::{Scheduler, TimerId, Topic};
@@ -64,8 +79,12 @@ pub mod hint;
 pub mod window;
 
 mod bell;
+mod color_transition;
+mod compositor;
 mod damage;
 mod meter;
+mod presentation;
+mod resize_overlay;
 
 /// Label for the forward terminal search bar.
 const FORWARD_SEARCH_LABEL: &str = "Search: ";
@@ -79,6 +98,18 @@ const SHORTENER: char = '…';
 /// Color which is used to highlight damaged rects when debugging.
 const DAMAGE_RECT_COLOR: Rgb = Rgb::new(255, 0, 255);
 
+/// Color which is used to show the accumulated damage heatmap when debugging.
+const DAMAGE_HEATMAP_COLOR: Rgb = Rgb::new(255, 64, 0);
+
+/// Font size increment in pixels used to fit the grid of `window.fit_grid` into the window.
+const FIT_GRID_STEP: f32 = 0.5;
+
+/// Maximum number of font size changes to fit the grid of `window.fit_grid` into the window.
+const MAX_FIT_GRID_ATTEMPTS: usize = 8;
+
+/// Interval for retrying to recreate a lost OpenGL context.
+const CONTEXT_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Debug)]
 pub enum Error {
     /// Error with window management.
@@ -304,6 +335,9 @@ impl TermDimensions for SizeInfo {
 pub struct DisplayUpdate {
     pub dirty: bool,
 
+    /// Whether the new dimensions are the result of the user resizing the window.
+    pub interactive_resize: bool,
+
     dimensions: Option<PhysicalSize<u32>>,
     cursor_dirty: bool,
     font: Option<Font>,
@@ -338,6 +372,13 @@ impl DisplayUpdate {
     }
 }
 
+/// Input which triggered the reveal binding for concealed text.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RevealTrigger {
+    Key(PhysicalKey),
+    Mouse(MouseButton),
+}
+
 /// The display wraps a window, font rasterizer, and GPU renderer.
 pub struct Display {
     pub window: Window,
@@ -354,16 +395,55 @@ pub struct Display {
     /// Frames since hint highlight was created.
     vi_highlighted_hint_age: usize,
 
+    /// Hyperlink which will be opened when it is triggered again.
+    pub unconfirmed_hyperlink: Option<Hyperlink>,
+
+    /// Large selection which will be copied when it is copied again.
+    pub unconfirmed_copy: Option<Selection>,
+
     pub raw_window_handle: RawWindowHandle,
 
     /// UI cursor visibility for blinking.
     pub cursor_hidden: bool,
 
+    /// Input holding the reveal binding, while concealed text is rendered.
+    pub reveal_hidden: Option<RevealTrigger>,
+
+    /// Active keyboard locks.
+    pub lock_keys: LockKeys,
+
+    /// A job of the shell is stopped.
+    pub stopped_job: bool,
+
     pub visual_bell: VisualBell,
 
+    #[cfg(feature = "audio")]
+    pub audible_bell: AudibleBell,
+
+    /// Input mode indicator was drawn in the last frame.
+    mode_indicator_visible: bool,
+
+    /// Window border color of the last frame.
+    border_color: Option<Rgb>,
+
+    /// Cached glyphs reported to the metrics.
+    reported_glyphs: usize,
+
+    /// Grid dimensions shown during interactive resize.
+    pub resize_overlay: ResizeOverlay,
+
+    /// Presentation mode state, including the recently pressed key chords.
+    pub presentation: Presentation,
+
+    /// Clipboard history entries shown for selection, most recent first.
+    pub clipboard_history: Option<Vec<String>>,
+
     /// Mapped RGB values for each terminal color.
     pub colors: List,
 
+    /// Animation between the previous and current color config.
+    pub color_transition: ColorTransition,
+
     /// State of the keyboard hints.
     pub hint_state: HintState,
 
@@ -391,10 +471,16 @@ pub struct Display {
     renderer: ManuallyDrop<Renderer>,
     renderer_preference: Option<RendererPreference>,
 
+    /// Path of the post-processing shader.
+    shader: Option<PathBuf>,
+
     surface: ManuallyDrop<Surface<WindowSurface>>,
 
     context: ManuallyDrop<PossiblyCurrentContext>,
 
+    /// Context was lost, but could not be recreated yet.
+    context_lost: bool,
+
     glyph_cache: GlyphCache,
     meter: Meter,
 }
@@ -420,7 +506,7 @@ impl Display {
         let (cell_width, cell_height) = compute_cell_size(config, &metrics);
 
         // Resize the window to account for the user configured size.
-        if let Some(dimensions) = config.window.dimensions() {
+        if let Some(dimensions) = config.window.dimensions().or(config.window.fit_grid()) {
             let size = window_size(config, dimensions, cell_width, cell_height, scale_factor);
             window.request_inner_size(size);
         }
@@ -444,18 +530,21 @@ impl Display {
             glyph_cache.reset_glyph_cache(&mut api);
         });
 
-        let padding = config.window.padding(window.scale_factor as f32);
         let viewport_size = window.inner_size();
+        let (width, height) = (viewport_size.width as f32, viewport_size.height as f32);
+        let padding = grid_padding(config, width, height, cell_width, cell_height, scale_factor);
 
         // Create new size with at least one column and row.
         let size_info = SizeInfo::new(
-            viewport_size.width as f32,
-            viewport_size.height as f32,
+            width,
+            height,
             cell_width,
             cell_height,
             padding.0,
             padding.1,
-            config.window.dynamic_padding && config.window.dimensions().is_none(),
+            config.window.dynamic_padding
+                && config.window.dimensions().is_none()
+                && config.window.fit_grid().is_none(),
         );
 
         info!("Cell size: {} x {}", cell_width, cell_height);
@@ -465,6 +554,9 @@ impl Display {
         // Update OpenGL projection.
         renderer.resize(&size_info);
 
+        let shader = config.renderer.shader.clone();
+        load_shader(&mut renderer, shader.as_deref(), &size_info);
+
         // Clear screen.
         let background_color = config.colors.primary.background;
         renderer.clear(background_color, config.window_opacity());
@@ -478,7 +570,7 @@ impl Display {
         // On Wayland we can safely ignore this call, since the window isn't visible until you
         // actually draw something into it and commit those changes.
         if !is_wayland {
-            surface.swap_buffers(&context).expect("failed to swap buffers.");
+            surface.swap_buffers(&context)?;
             renderer.finish();
         }
 
@@ -508,6 +600,7 @@ impl Display {
 
         let mut damage_tracker = DamageTracker::new(size_info.screen_lines(), size_info.columns());
         damage_tracker.debug = config.debug.highlight_damage;
+        damage_tracker.set_heatmap_enabled(config.debug.damage_heatmap);
 
         // Disable vsync.
         if let Err(err) = surface.set_swap_interval(&context, SwapInterval::DontWait) {
@@ -516,11 +609,19 @@ impl Display {
 
         Ok(Self {
             context: ManuallyDrop::new(context),
+            context_lost: false,
             visual_bell: VisualBell::from(&config.bell),
+            #[cfg(feature = "audio")]
+            audible_bell: Default::default(),
+            resize_overlay: Default::default(),
+            presentation: Default::default(),
+            clipboard_history: Default::default(),
             renderer: ManuallyDrop::new(renderer),
             renderer_preference: config.debug.renderer,
+            shader,
             surface: ManuallyDrop::new(surface),
             colors: List::from(&config.colors),
+            color_transition: ColorTransition::new(List::from(&config.colors)),
             frame_timer: FrameTimer::new(),
             raw_window_handle,
             damage_tracker,
@@ -531,17 +632,31 @@ impl Display {
             window,
             pending_renderer_update: Default::default(),
             vi_highlighted_hint_age: Default::default(),
+            unconfirmed_hyperlink: Default::default(),
+            unconfirmed_copy: Default::default(),
             highlighted_hint_age: Default::default(),
             vi_highlighted_hint: Default::default(),
             highlighted_hint: Default::default(),
             hint_mouse_point: Default::default(),
             pending_update: Default::default(),
             cursor_hidden: Default::default(),
+            reveal_hidden: Default::default(),
+            lock_keys: Default::default(),
+            stopped_job: Default::default(),
+            mode_indicator_visible: Default::default(),
+            border_color: Default::default(),
+            reported_glyphs: Default::default(),
             meter: Default::default(),
             ime: Default::default(),
         })
     }
 
+    /// Check if the OpenGL context is lost and could not be recreated yet.
+    #[inline]
+    pub fn is_context_lost(&self) -> bool {
+        self.context_lost
+    }
+
     #[inline]
     pub fn gl_context(&self) -> &PossiblyCurrentContext {
         &self.context
@@ -549,15 +664,22 @@ impl Display {
 
     pub fn make_not_current(&mut self) {
         if self.context.is_current() {
-            self.context.make_not_current_in_place().expect("failed to disable context");
+            if let Err(err) = self.context.make_not_current_in_place() {
+                warn!("Failed to disable context: {err}");
+            }
         }
     }
 
-    pub fn make_current(&mut self) {
+    /// Make the window's OpenGL context current.
+    ///
+    /// Returns `false` while the context is lost and could not be recreated yet.
+    pub fn make_current(&mut self) -> bool {
         let is_current = self.context.is_current();
 
         // Attempt to make the context current if it's not.
-        let context_loss = if is_current {
+        let context_loss = if self.context_lost {
+            true
+        } else if is_current {
             self.renderer.was_context_reset()
         } else {
             match self.context.make_current(&self.surface) {
@@ -570,38 +692,52 @@ impl Display {
         };
 
         if !context_loss {
-            return;
+            return true;
         }
 
-        let gl_display = self.context.display();
-        let gl_config = self.context.config();
-        let raw_window_handle = Some(self.window.raw_window_handle());
-        let context = platform::create_gl_context(&gl_display, &gl_config, raw_window_handle)
-            .expect("failed to recreate context.");
-
-        // Drop the old context and renderer.
-        unsafe {
-            ManuallyDrop::drop(&mut self.renderer);
-            ManuallyDrop::drop(&mut self.context);
-        }
+        // Keep the old context around and retry on the next frame if recreation failed.
+        let (context, renderer) = match self.recreate_context() {
+            Ok(recreated) => recreated,
+            Err(err) => {
+                if !self.context_lost {
+                    error!("Failed to recreate lost GPU context: {err}");
+                }
+                self.context_lost = true;
+                return false;
+            },
+        };
+        self.context_lost = false;
 
-        // Activate new context.
-        let context = context.treat_as_possibly_current();
+        // Replace the old context and renderer.
+        //
+        // The old renderer is not dropped, since its OpenGL objects were lost with its context
+        // and deleting them now would delete objects of the new context instead.
+        unsafe { ManuallyDrop::drop(&mut self.context) };
         self.context = ManuallyDrop::new(context);
-        self.context.make_current(&self.surface).expect("failed to reativate context after reset.");
-
-        // Recreate renderer.
-        let renderer = Renderer::new(&self.context, self.renderer_preference)
-            .expect("failed to recreate renderer after reset");
         self.renderer = ManuallyDrop::new(renderer);
 
         // Resize the renderer.
         self.renderer.resize(&self.size_info);
+        load_shader(&mut self.renderer, self.shader.as_deref(), &self.size_info);
 
         self.reset_glyph_cache();
         self.damage_tracker.frame().mark_fully_damaged();
 
         debug!("Recovered window {:?} from gpu reset", self.window.id());
+        warn!("GPU context was lost and has been recreated");
+
+        true
+    }
+
+    /// Create and activate a new OpenGL context with its renderer.
+    fn recreate_context(&self) -> Result<(PossiblyCurrentContext, Renderer), Error> {
+        let gl_display = self.context.display();
+        let gl_config = self.context.config();
+        let raw_window_handle = Some(self.window.raw_window_handle());
+        let context = platform::create_gl_context(&gl_display, &gl_config, raw_window_handle)?;
+        let context = context.make_current(&self.surface)?;
+        let renderer = Renderer::new(&context, self.renderer_preference)?;
+        Ok((context, renderer))
     }
 
     fn swap_buffers(&self) {
@@ -610,7 +746,9 @@ impl Display {
             #[cfg(not(any(target_os = "macos", windows)))]
             (Surface::Egl(surface), PossiblyCurrentContext::Egl(context))
                 if matches!(self.raw_window_handle, RawWindowHandle::Wayland(_))
-                    && !self.damage_tracker.debug =>
+                    && !self.renderer.has_post_process()
+                    && !self.damage_tracker.debug
+                    && self.damage_tracker.heatmap.is_none() =>
             {
                 let damage = self.damage_tracker.shape_frame_damage(self.size_info.into());
                 surface.swap_buffers_with_damage(context, &damage)
@@ -636,6 +774,60 @@ impl Display {
         compute_cell_size(config, &glyph_cache.font_metrics())
     }
 
+    /// Change the font size to the largest size showing the entire grid within the window.
+    ///
+    /// This will return the new cell width and height if the font size was changed.
+    fn fit_font_size(
+        &mut self,
+        config: &UiConfig,
+        grid: Dimensions,
+        width: f32,
+        height: f32,
+        (mut cell_width, mut cell_height): (f32, f32),
+    ) -> Option<(f32, f32)> {
+        let padding = config.window.padding(self.window.scale_factor as f32);
+        let max_cell_width = (width - 2. * padding.0) / grid.columns as f32;
+        let max_cell_height = (height - 2. * padding.1) / grid.lines as f32;
+        if max_cell_width < 1. || max_cell_height < 1. {
+            return None;
+        }
+
+        let mut font_size = self.font_size.as_px();
+        let mut changed = false;
+        for attempt in 0..MAX_FIT_GRID_ATTEMPTS {
+            // Estimate the font size, since cells grow roughly linear with it.
+            let scale = (max_cell_width / cell_width).min(max_cell_height / cell_height);
+            let mut new_size = (font_size * scale / FIT_GRID_STEP).floor() * FIT_GRID_STEP;
+
+            // Shrink the font until the grid fits, since cell dimensions are rounded.
+            if attempt > 0 {
+                if scale >= 1. {
+                    break;
+                }
+                new_size = new_size.min(font_size - FIT_GRID_STEP);
+            }
+
+            new_size = new_size.max(FIT_GRID_STEP);
+            if new_size == font_size {
+                break;
+            }
+
+            font_size = new_size;
+            let font = config.font.clone().with_size(FontSize::from_px(font_size));
+            let cell_dimensions = Self::update_font_size(&mut self.glyph_cache, config, &font);
+            (cell_width, cell_height) = cell_dimensions;
+            changed = true;
+        }
+
+        if !changed {
+            return None;
+        }
+
+        self.font_size = FontSize::from_px(font_size);
+
+        Some((cell_width, cell_height))
+    }
+
     /// Reset glyph cache.
     fn reset_glyph_cache(&mut self) {
         let cache = &mut self.glyph_cache;
@@ -687,7 +879,26 @@ impl Display {
             height = dimensions.height as f32;
         }
 
-        let padding = config.window.padding(self.window.scale_factor as f32);
+        // Adjust the font size to keep the configured grid visible.
+        let fit_grid = config.window.fit_grid();
+        if let Some(grid) = fit_grid.filter(|_| pending_update.dimensions().is_some()) {
+            let cell_dimensions = (cell_width, cell_height);
+            if let Some(cell_dimensions) =
+                self.fit_font_size(config, grid, width, height, cell_dimensions)
+            {
+                (cell_width, cell_height) = cell_dimensions;
+
+                info!("Cell size: {} x {}", cell_width, cell_height);
+
+                let renderer_update =
+                    self.pending_renderer_update.get_or_insert(Default::default());
+                renderer_update.clear_font_cache = true;
+                self.damage_tracker.frame().mark_fully_damaged();
+            }
+        }
+
+        let scale_factor = self.window.scale_factor as f32;
+        let padding = grid_padding(config, width, height, cell_width, cell_height, scale_factor);
 
         let mut new_size = SizeInfo::new(
             width,
@@ -696,7 +907,7 @@ impl Display {
             cell_height,
             padding.0,
             padding.1,
-            config.window.dynamic_padding,
+            config.window.dynamic_padding && fit_grid.is_none(),
         );
 
         // Update number of column/lines in the viewport.
@@ -722,6 +933,14 @@ impl Display {
 
             // Resize damage tracking.
             self.damage_tracker.resize(new_size.screen_lines(), new_size.columns());
+
+            // Show the new dimensions while the user is resizing the window.
+            if config.window.resize_overlay
+                && pending_update.interactive_resize
+                && !config.general.reduce_motion()
+            {
+                self.resize_overlay.show();
+            }
         }
 
         // Check if dimensions have changed.
@@ -780,6 +999,23 @@ impl Display {
         config: &UiConfig,
         search_state: &mut SearchState,
     ) {
+        // Skip drawing until the lost OpenGL context could be recreated.
+        if !self.make_current() {
+            let window_id = self.window.id();
+            let timer_id = TimerId::new(Topic::Frame, window_id);
+            let event = Event::new(EventType::Frame, window_id);
+            self.window.has_frame = false;
+            scheduler.schedule(event, CONTEXT_RETRY_INTERVAL, false, timer_id);
+            return;
+        }
+
+        // Advance the palette transition, which changes every cell.
+        let colors = self.color_transition.colors();
+        if colors != self.colors {
+            self.colors = colors;
+            self.damage_tracker.frame().mark_fully_damaged();
+        }
+
         // Collect renderable content before the terminal is dropped.
         let mut content = RenderableContent::new(config, self, &terminal, search_state);
         let mut grid_cells = Vec::new();
@@ -798,6 +1034,16 @@ impl Display {
         let size_info = self.size_info;
 
         let vi_mode = terminal.mode().contains(TermMode::VI);
+        let mode_indicator = &config.terminal.mode_indicator;
+        let mode_indicator_visible = (mode_indicator.bracketed_paste
+            && terminal.mode().contains(TermMode::BRACKETED_PASTE))
+            || (mode_indicator.mouse && terminal.mode().intersects(TermMode::MOUSE_MODE))
+            || (mode_indicator.caps_lock && self.lock_keys.contains(LockKeys::CAPS_LOCK))
+            || (mode_indicator.stopped_job && self.stopped_job);
+        let border = &config.window.border;
+        let border_color = (border.width != 0)
+            .then(|| border.color(terminal.is_focused, self.window.is_urgent()));
+
         let vi_cursor_point = if vi_mode { Some(terminal.vi_mode_cursor.point) } else { None };
 
         // Add damage from the terminal.
@@ -819,22 +1065,56 @@ impl Display {
 
         // Add damage from alacritty's UI elements overlapping terminal.
 
+        let cursor_ring_visible = self.presentation.active()
+            && config.presentation.cursor_ring
+            && cursor.shape() != CursorShape::Hidden;
         let requires_full_damage = self.visual_bell.intensity() != 0.
             || self.hint_state.active()
-            || search_state.regex().is_some();
+            || search_state.regex().is_some()
+            || cursor_ring_visible;
         if requires_full_damage {
             self.damage_tracker.frame().mark_fully_damaged();
             self.damage_tracker.next_frame().mark_fully_damaged();
         }
 
+        // Redraw the window border when the mode indicator is toggled.
+        if mode_indicator_visible != self.mode_indicator_visible {
+            self.mode_indicator_visible = mode_indicator_visible;
+            self.damage_tracker.frame().mark_fully_damaged();
+        }
+
+        // Redraw the window border when its color changes.
+        if border_color != self.border_color {
+            self.border_color = border_color;
+            self.damage_tracker.frame().mark_fully_damaged();
+        }
+
         let vi_cursor_viewport_point =
             vi_cursor_point.and_then(|cursor| term::point_to_viewport(display_offset, cursor));
         self.damage_tracker.damage_vi_cursor(vi_cursor_viewport_point);
         self.damage_tracker.damage_selection(selection_range, display_offset);
 
-        // Make sure this window's OpenGL context is active.
-        self.make_current();
+        let frame_damage = self.damage_tracker.frame();
+        let damaged_lines = if frame_damage.is_fully_damaged() {
+            size_info.screen_lines()
+        } else {
+            frame_damage.lines().iter().filter(|line| line.is_damaged()).count()
+        };
+        metrics::frame_drawn(damaged_lines);
+
+        // Stream the updated cells to IPC subscribers.
+        #[cfg(unix)]
+        if ipc::has_frame_subscribers() {
+            let window_id = self.window.id();
+            let frame_damage = self.damage_tracker.frame();
+            let full = frame_damage.is_fully_damaged() || ipc::take_dropped_frame(window_id);
+            let damage = (!full).then(|| frame_damage.lines().to_vec());
+            let cells = grid_cells.clone();
+            let frame = IpcFrame::new(damage, &size_info, background_color, cursor, cells);
+            ipc::send_frame(window_id, frame);
+        }
 
+        self.renderer.begin_frame();
         self.renderer.clear(background_color, config.window_opacity());
         let mut lines = RenderLines::new();
 
@@ -862,7 +1142,7 @@ impl Display {
                     let hyperlink = cell.extra.as_ref().and_then(|extra| extra.hyperlink.as_ref());
 
                     let should_highlight = |hint: &Option<HintMatch>| {
-                        hint.as_ref().map_or(false, |hint| hint.should_highlight(point, hyperlink))
+                        hint.as_ref().is_some_and(|hint| hint.should_highlight(point, hyperlink))
                     };
                     if should_highlight(highlighted_hint) || should_highlight(vi_highlighted_hint) {
                         damage_tracker.frame().damage_point(cell.point);
@@ -878,7 +1158,10 @@ impl Display {
             self.renderer.draw_cells(&size_info, glyph_cache, cells);
         }
 
-        let mut rects = lines.rects(&metrics, &size_info);
+        metrics::update_cached_glyphs(&mut self.reported_glyphs, self.glyph_cache.glyph_count());
+
+        let mut compositor = Compositor::default();
+        compositor.extend(Layer::Decorations, lines.rects(&metrics, &size_info));
 
         if let Some(vi_cursor_point) = vi_cursor_point {
             // Indicate vi mode by showing the cursor's position in the top right corner.
@@ -892,8 +1175,47 @@ impl Display {
             self.draw_line_indicator(config, total_lines, None, display_offset);
         };
 
+        self.draw_resize_overlay(config, background_color);
+
+        self.draw_key_chords(config, background_color);
+        self.draw_clipboard_history(config);
+
         // Draw cursor.
-        rects.extend(cursor.rects(&size_info, config.cursor.thickness()));
+        compositor.extend(Layer::Cursor, cursor.rects(&size_info, config.cursor.thickness()));
+
+        // Highlight the cursor position in presentation mode.
+        if cursor_ring_visible {
+            let scale_factor = self.window.scale_factor as f32;
+            compositor.extend(Layer::Cursor, cursor::ring(&cursor, &size_info, scale_factor));
+        }
+
+        let border_thickness = match border_color {
+            Some(_) => (f32::from(border.width) * self.window.scale_factor as f32).floor(),
+            None => 0.,
+        };
+
+        // Indicate modes changing input behavior with a border on the left window edge.
+        if mode_indicator_visible {
+            // Stay inside the window border, to keep the indicator visible.
+            let width = mode_indicator.thickness(self.window.scale_factor as f32);
+            let height = size_info.height() - 2. * border_thickness;
+            let (x, y) = (border_thickness, border_thickness);
+            let rect = RenderRect::new(x, y, width, height, mode_indicator.color, 1.);
+            compositor.push(Layer::ModeIndicator, rect);
+        }
+
+        // Draw the window border inside the padding.
+        if let Some(color) = border_color {
+            let width = size_info.width();
+            let height = size_info.height();
+            let thickness = border_thickness;
+            compositor.extend(Layer::Border, [
+                RenderRect::new(0., 0., width, thickness, color, 1.),
+                RenderRect::new(0., height - thickness, width, thickness, color, 1.),
+                RenderRect::new(0., 0., thickness, height, color, 1.),
+                RenderRect::new(width - thickness, 0., thickness, height, color, 1.),
+            ]);
+        }
 
         // Push visual bell after url/underline/strikeout rects.
         let visual_bell_intensity = self.visual_bell.intensity();
@@ -906,7 +1228,7 @@ impl Display {
                 config.bell.color,
                 visual_bell_intensity as f32,
             );
-            rects.push(visual_bell_rect);
+            compositor.push(Layer::VisualBell, visual_bell_rect);
         }
 
         // Handle IME positioning and search bar rendering.
@@ -933,7 +1255,8 @@ impl Display {
                     let cursor_width = NonZeroU32::new(1).unwrap();
                     let cursor =
                         RenderableCursor::new(Point::new(line, column), shape, fg, cursor_width);
-                    rects.extend(cursor.rects(&size_info, config.cursor.thickness()));
+                    let rects = cursor.rects(&size_info, config.cursor.thickness());
+                    compositor.extend(Layer::Cursor, rects);
                 }
 
                 Some(Point::new(line, column))
@@ -957,7 +1280,7 @@ impl Display {
                     (foreground_color, background_color)
                 };
 
-                self.draw_ime_preview(point, fg, bg, &mut rects, config);
+                self.draw_ime_preview(point, fg, bg, &mut compositor, config);
             }
         }
 
@@ -980,13 +1303,13 @@ impl Display {
             let message_bar_rect =
                 RenderRect::new(x as f32, y, width as f32, height as f32, bg, 1.);
 
-            // Push message_bar in the end, so it'll be above all other content.
-            rects.push(message_bar_rect);
+            compositor.push(Layer::MessageBar, message_bar_rect);
 
             // Always damage message bar, since it could have messages of the same size in it.
             self.damage_tracker.frame().add_viewport_rect(&size_info, x, y as i32, width, height);
 
             // Draw rectangles.
+            let rects = compositor.compose(&config.window.overlays, &size_info);
             self.renderer.draw_rects(&size_info, &metrics, rects);
 
             // Relay messages to the user.
@@ -1005,6 +1328,7 @@ impl Display {
             }
         } else {
             // Draw rectangles.
+            let rects = compositor.compose(&config.window.overlays, &size_info);
             self.renderer.draw_rects(&size_info, &metrics, rects);
         }
 
@@ -1020,13 +1344,18 @@ impl Display {
         self.window.pre_present_notify();
 
         // Highlight damage for debugging.
-        if self.damage_tracker.debug {
-            let damage = self.damage_tracker.shape_frame_damage(self.size_info.into());
-            let mut rects = Vec::with_capacity(damage.len());
-            self.highlight_damage(&mut rects);
+        if self.damage_tracker.debug || self.damage_tracker.heatmap.is_some() {
+            let mut rects = Vec::new();
+            self.highlight_damage_heatmap(&mut rects);
+            if self.damage_tracker.debug {
+                self.highlight_damage(&mut rects);
+            }
             self.renderer.draw_rects(&self.size_info, &metrics, rects);
         }
 
+        // Apply the post-processing shader, keeping its time still when animations are disabled.
+        self.renderer.end_frame(&self.size_info, !config.general.reduce_motion());
+
         // Clearing debug highlights from the previous frame requires full redraw.
         self.swap_buffers();
 
@@ -1046,11 +1375,34 @@ impl Display {
         self.damage_tracker.swap_damage();
     }
 
+    /// Check if the post-processing shader requires continuous redraws.
+    pub fn has_animated_shader(&self) -> bool {
+        self.renderer.is_animated()
+    }
+
     /// Update to a new configuration.
     pub fn update_config(&mut self, config: &UiConfig) {
+        if config.renderer.shader != self.shader {
+            self.shader = config.renderer.shader.clone();
+
+            // A lost context loads the new shader once it is recreated.
+            if self.make_current() {
+                load_shader(&mut self.renderer, self.shader.as_deref(), &self.size_info);
+            }
+            self.damage_tracker.frame().mark_fully_damaged();
+        }
+
         self.damage_tracker.debug = config.debug.highlight_damage;
+        self.damage_tracker.set_heatmap_enabled(config.debug.damage_heatmap);
         self.visual_bell.update_config(&config.bell);
-        self.colors = List::from(&config.colors);
+
+        // Transition to the new palette, which is applied once the next frame is drawn.
+        let duration = if config.general.reduce_motion() {
+            Duration::ZERO
+        } else {
+            config.colors.transition_duration()
+        };
+        self.color_transition.start(List::from(&config.colors), duration);
     }
 
     /// Update the mouse/vi mode cursor hint highlighting.
@@ -1097,7 +1449,7 @@ impl Display {
         if highlighted_hint.is_some() {
             // If mouse changed the line, we should update the hyperlink preview, since the
             // highlighted hint could be disrupted by the old preview.
-            dirty = self.hint_mouse_point.map_or(false, |p| p.line != point.line);
+            dirty = self.hint_mouse_point.is_some_and(|p| p.line != point.line);
             self.hint_mouse_point = Some(point);
             self.window.set_mouse_cursor(CursorIcon::Pointer);
         } else if self.highlighted_hint.is_some() {
@@ -1128,7 +1480,7 @@ impl Display {
         point: Point<usize>,
         fg: Rgb,
         bg: Rgb,
-        rects: &mut Vec<RenderRect>,
+        compositor: &mut Compositor,
         config: &UiConfig,
     ) {
         let preedit = match self.ime.preedit() {
@@ -1185,7 +1537,7 @@ impl Display {
 
         // Add underline for preedit text.
         let underline = RenderLine { start, end, color: fg };
-        rects.extend(underline.rects(Flags::UNDERLINE, &metrics, &self.size_info));
+        compositor.extend(Layer::Ime, underline.rects(Flags::UNDERLINE, &metrics, &self.size_info));
 
         let ime_popup_point = match preedit.cursor_end_offset {
             Some(cursor_end_offset) => {
@@ -1203,7 +1555,8 @@ impl Display {
                 );
                 let cursor_point = Point::new(point.line, cursor_column);
                 let cursor = RenderableCursor::new(cursor_point, shape, fg, width);
-                rects.extend(cursor.rects(&self.size_info, config.cursor.thickness()));
+                let rects = cursor.rects(&self.size_info, config.cursor.thickness());
+                compositor.extend(Layer::Ime, rects);
                 cursor_point
             },
             _ => end,
@@ -1343,6 +1696,114 @@ impl Display {
         self.renderer.draw_string(point, fg, bg, timing.chars(), &self.size_info, glyph_cache);
     }
 
+    /// Draw the grid dimensions centered in the window, while it is being resized.
+    #[inline(never)]
+    fn draw_resize_overlay(&mut self, config: &UiConfig, background_color: Rgb) {
+        let opacity = self.resize_overlay.opacity();
+        if opacity == 0. {
+            return;
+        }
+
+        let columns = self.size_info.columns();
+        let text = format!(" {}x{} ", columns, self.size_info.screen_lines());
+        let column = Column(columns.saturating_sub(text.len()) / 2);
+        let point = Point::new(self.size_info.screen_lines() / 2, column);
+
+        // Damage the overlay for current and next frame.
+        let right = cmp::min(point.column.0 + text.len(), columns) - 1;
+        let damage = LineDamageBounds::new(point.line, point.column.0, right);
+        self.damage_tracker.frame().damage_line(damage);
+        self.damage_tracker.next_frame().damage_line(damage);
+
+        // Fade out by blending into the terminal's background.
+        let colors = &config.colors;
+        let fade = |color: Rgb| color * opacity + background_color * (1. - opacity);
+        let fg = fade(colors.line_indicator.foreground.unwrap_or(colors.primary.background));
+        let bg = fade(colors.line_indicator.background.unwrap_or(colors.primary.foreground));
+
+        let glyph_cache = &mut self.glyph_cache;
+        self.renderer.draw_string(point, fg, bg, text.chars(), &self.size_info, glyph_cache);
+    }
+
+    /// Draw the recently pressed key chords in the bottom right corner, in presentation mode.
+    #[inline(never)]
+    fn draw_key_chords(&mut self, config: &UiConfig, background_color: Rgb) {
+        let mut opacity = self.presentation.opacity();
+        if opacity == 0. || !config.presentation.show_keys {
+            return;
+        }
+
+        // Hide the key chords without fading out when animations are disabled.
+        if config.general.reduce_motion() {
+            opacity = 1.;
+        }
+
+        // Truncate the oldest key chords when they don't fit.
+        let columns = self.size_info.columns();
+        let chords = format!(" {} ", self.presentation.key_chords());
+        let skipped = chords.chars().count().saturating_sub(columns);
+        let text: String = chords.chars().skip(skipped).collect();
+
+        let column = Column(columns - text.chars().count());
+        let point = Point::new(self.size_info.screen_lines().saturating_sub(1), column);
+
+        // Damage the key chords for current and next frame.
+        let damage = LineDamageBounds::new(point.line, point.column.0, columns - 1);
+        self.damage_tracker.frame().damage_line(damage);
+        self.damage_tracker.next_frame().damage_line(damage);
+
+        // Fade out by blending into the terminal's background.
+        let colors = &config.colors;
+        let fade = |color: Rgb| color * opacity + background_color * (1. - opacity);
+        let fg = fade(colors.line_indicator.foreground.unwrap_or(colors.primary.background));
+        let bg = fade(colors.line_indicator.background.unwrap_or(colors.primary.foreground));
+
+        let glyph_cache = &mut self.glyph_cache;
+        self.renderer.draw_string(point, fg, bg, text.chars(), &self.size_info, glyph_cache);
+    }
+
+    /// Draw the clipboard history entries at the top of the window.
+    #[inline(never)]
+    fn draw_clipboard_history(&mut self, config: &UiConfig) {
+        let entries = match &self.clipboard_history {
+            Some(entries) => entries,
+            None => return,
+        };
+
+        let title = if entries.is_empty() {
+            String::from("Clipboard history is empty")
+        } else {
+            String::from("Press a number to paste, any other key to cancel")
+        };
+        let entries = entries.iter().enumerate().map(|(index, entry)| {
+            // Show the entry on a single line.
+            let entry: String =
+                entry.chars().map(|c| if c.is_control() { ' ' } else { c }).collect();
+            format!("{index}: {entry}")
+        });
+
+        let colors = &config.colors;
+        let fg = colors.line_indicator.foreground.unwrap_or(colors.primary.background);
+        let bg = colors.line_indicator.background.unwrap_or(colors.primary.foreground);
+
+        let columns = self.size_info.columns();
+        let lines = iter::once(title).chain(entries).take(self.size_info.screen_lines());
+        for (line, text) in lines.enumerate() {
+            // Cover the entire line, to separate the entries from the terminal content.
+            let text = format!(" {text}");
+            let text = text.chars().chain(iter::repeat(' ')).take(columns);
+
+            // Damage the entry for current and next frame.
+            let damage = LineDamageBounds::new(line, 0, columns - 1);
+            self.damage_tracker.frame().damage_line(damage);
+            self.damage_tracker.next_frame().damage_line(damage);
+
+            let point = Point::new(line, Column(0));
+            let glyph_cache = &mut self.glyph_cache;
+            self.renderer.draw_string(point, fg, bg, text, &self.size_info, glyph_cache);
+        }
+    }
+
     /// Draw an indicator for the position of a line in history.
     #[inline(never)]
     fn draw_line_indicator(
@@ -1388,6 +1849,28 @@ impl Display {
         }
     }
 
+    /// Highlight cells by how often they were damaged.
+    ///
+    /// This function is for debug purposes only.
+    fn highlight_damage_heatmap(&self, render_rects: &mut Vec<RenderRect>) {
+        let heatmap = match &self.damage_tracker.heatmap {
+            Some(heatmap) => heatmap,
+            None => return,
+        };
+
+        let size_info = &self.size_info;
+        for (line, columns, heat) in heatmap.regions() {
+            let x = size_info.padding_x() + columns.start as f32 * size_info.cell_width();
+            let y = size_info.padding_y() + line as f32 * size_info.cell_height();
+            let width = columns.len() as f32 * size_info.cell_width();
+            let height = size_info.cell_height();
+            let alpha = 0.1 + 0.5 * heat;
+            let render_rect = RenderRect::new(x, y, width, height, DAMAGE_HEATMAP_COLOR, alpha);
+
+            render_rects.push(render_rect);
+        }
+    }
+
     /// Check whether a hint highlight needs to be cleared.
     fn validate_hint_highlights(&mut self, display_offset: usize) {
         let frame = self.damage_tracker.frame();
@@ -1457,6 +1940,8 @@ impl Display {
 
 impl Drop for Display {
     fn drop(&mut self) {
+        metrics::update_cached_glyphs(&mut self.reported_glyphs, 0);
+
         // Switch OpenGL context before dropping, otherwise objects (like programs) from other
         // contexts might be deleted when dropping renderer.
         self.make_current();
@@ -1611,6 +2096,28 @@ fn compute_cell_size(config: &UiConfig, metrics: &crossfont::Metrics) -> (f32, f
     )
 }
 
+/// Calculate the padding around the terminal grid.
+///
+/// The grid of `window.fit_grid` is centered within the window.
+fn grid_padding(
+    config: &UiConfig,
+    width: f32,
+    height: f32,
+    cell_width: f32,
+    cell_height: f32,
+    scale_factor: f32,
+) -> (f32, f32) {
+    let padding = config.window.padding(scale_factor);
+    let grid = match config.window.fit_grid() {
+        Some(grid) => grid,
+        None => return padding,
+    };
+
+    let padding_x = ((width - grid.columns as f32 * cell_width) / 2.).floor();
+    let padding_y = ((height - grid.lines as f32 * cell_height) / 2.).floor();
+    (padding.0.max(padding_x), padding.1.max(padding_y))
+}
+
 /// Calculate the size of the window given padding, terminal dimensions and cell size.
 fn window_size(
     config: &UiConfig,
@@ -1629,3 +2136,22 @@ fn window_size(
 
     PhysicalSize::new(width as u32, height as u32)
 }
+
+/// Load the post-processing shader, rendering without it if it can't be compiled.
+fn load_shader(renderer: &mut Renderer, path: Option<&Path>, size_info: &SizeInfo) {
+    // Remove the previous shader, so frames are drawn unprocessed on error.
+    let _ = renderer.set_post_process_shader(None, size_info);
+
+    let path = match path {
+        Some(path) => path,
+        None => return,
+    };
+
+    let result = fs::read_to_string(path)
+        .map_err(|err| renderer::Error::Other(err.to_string()))
+        .and_then(|shader| renderer.set_post_process_shader(Some(&shader), size_info));
+
+    if let Err(err) = result {
+        error!(target: LOG_TARGET_CONFIG, "Unable to load shader {path:?}: {err}");
+    }
+}
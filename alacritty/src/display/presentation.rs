@@ -0,0 +1,231 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crossfont::Size as FontSize;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+
+/// Time the key chords are fully visible after the last key press.
+const VISIBLE_DURATION: Duration = Duration::from_millis(1500);
+
+/// Time it takes the key chords to fade out.
+const FADE_DURATION: Duration = Duration::from_millis(500);
+
+/// Maximum number of key chords shown at once.
+const MAX_CHORDS: usize = 5;
+
+/// Maximum number of characters shown for consecutive text input.
+const MAX_TEXT_LEN: usize = 16;
+
+/// State of the presentation mode.
+#[derive(Default)]
+pub struct Presentation {
+    /// Font size before the presentation mode was started, while it is active.
+    font_size: Option<FontSize>,
+
+    /// Recently pressed key chords, oldest first.
+    chords: VecDeque<Chord>,
+
+    /// The last time a key was pressed, if the key chords are still visible.
+    pressed_at: Option<Instant>,
+}
+
+impl Presentation {
+    /// Check whether or not the presentation mode is active.
+    pub fn active(&self) -> bool {
+        self.font_size.is_some()
+    }
+
+    /// Start the presentation mode, storing the font size which should be restored afterwards.
+    pub fn start(&mut self, font_size: FontSize) {
+        self.font_size = Some(font_size);
+    }
+
+    /// Stop the presentation mode, returning the font size from before it was started.
+    pub fn stop(&mut self) -> Option<FontSize> {
+        self.chords.clear();
+        self.pressed_at = None;
+        self.font_size.take()
+    }
+
+    /// Add a key press to the key chords.
+    ///
+    /// The `key` should not have any modifiers applied, while `text` is the text it produces
+    /// with just the shift modifier.
+    pub fn record_key(&mut self, key: &Key, text: &str, mods: ModifiersState) {
+        let chord = match Chord::new(key, text, mods) {
+            Some(chord) => chord,
+            None => return,
+        };
+
+        // Start over once the previous key chords have disappeared.
+        if self.opacity() == 0. {
+            self.chords.clear();
+        }
+        self.pressed_at = Some(Instant::now());
+
+        // Merge consecutive text input into a single chord.
+        if let (Some(Chord::Text(last)), Chord::Text(text)) = (self.chords.back_mut(), &chord) {
+            last.push_str(text);
+            let overflow = last.chars().count().saturating_sub(MAX_TEXT_LEN);
+            if let Some((index, _)) = last.char_indices().nth(overflow) {
+                last.drain(..index);
+            }
+            return;
+        }
+
+        if self.chords.len() == MAX_CHORDS {
+            self.chords.pop_front();
+        }
+        self.chords.push_back(chord);
+    }
+
+    /// Text of all visible key chords.
+    pub fn key_chords(&self) -> String {
+        let chords: Vec<_> = self.chords.iter().map(Chord::as_str).collect();
+        chords.join("  ")
+    }
+
+    /// Get the current opacity of the key chords.
+    pub fn opacity(&self) -> f32 {
+        self.opacity_at_instant(Instant::now())
+    }
+
+    /// Check whether or not the key chords have disappeared completely.
+    pub fn completed(&mut self) -> bool {
+        if self.opacity() == 0. {
+            self.pressed_at = None;
+        }
+
+        self.pressed_at.is_none()
+    }
+
+    /// Get the opacity of the key chords at a particular instant.
+    fn opacity_at_instant(&self, instant: Instant) -> f32 {
+        let elapsed = match self.pressed_at {
+            Some(pressed_at) => instant.saturating_duration_since(pressed_at),
+            None => return 0.,
+        };
+
+        let fade_elapsed = match elapsed.checked_sub(VISIBLE_DURATION) {
+            Some(fade_elapsed) => fade_elapsed,
+            None => return 1.,
+        };
+
+        1. - (fade_elapsed.as_secs_f32() / FADE_DURATION.as_secs_f32()).min(1.)
+    }
+}
+
+/// Key press shown in the key chord display.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Chord {
+    /// Text typed without modifiers, other than shift.
+    Text(String),
+
+    /// Key with its modifiers, like `Ctrl+Shift+C`.
+    Keys(String),
+}
+
+impl Chord {
+    /// Create a chord for a key press.
+    ///
+    /// Returns `None` for keys which should not be shown, like modifiers on their own.
+    fn new(key: &Key, text: &str, mods: ModifiersState) -> Option<Self> {
+        let name = match key {
+            Key::Character(_) if (mods - ModifiersState::SHIFT).is_empty() => {
+                return (!text.is_empty()).then(|| Self::Text(text.into()));
+            },
+            Key::Character(c) => c.to_uppercase(),
+            Key::Named(
+                NamedKey::Shift
+                | NamedKey::Control
+                | NamedKey::Alt
+                | NamedKey::Super
+                | NamedKey::Meta
+                | NamedKey::Hyper
+                | NamedKey::AltGraph,
+            ) => return None,
+            Key::Named(named) => format!("{named:?}"),
+            _ => return None,
+        };
+
+        let mut chord = String::new();
+        for (modifier, label) in [
+            (ModifiersState::CONTROL, "Ctrl+"),
+            (ModifiersState::ALT, "Alt+"),
+            (ModifiersState::SHIFT, "Shift+"),
+            (ModifiersState::SUPER, "Super+"),
+        ] {
+            if mods.contains(modifier) {
+                chord.push_str(label);
+            }
+        }
+        chord.push_str(&name);
+
+        Some(Self::Keys(chord))
+    }
+
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Text(text) | Self::Keys(text) => text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn character(c: &str) -> Key {
+        Key::Character(c.into())
+    }
+
+    #[test]
+    fn chord_names() {
+        let ctrl_shift = ModifiersState::CONTROL | ModifiersState::SHIFT;
+        let chord = Chord::new(&character("c"), "C", ctrl_shift);
+        assert_eq!(chord, Some(Chord::Keys("Ctrl+Shift+C".into())));
+
+        let chord = Chord::new(&character("1"), "!", ModifiersState::SHIFT);
+        assert_eq!(chord, Some(Chord::Text("!".into())));
+
+        let chord = Chord::new(&Key::Named(NamedKey::ArrowUp), "", ModifiersState::empty());
+        assert_eq!(chord, Some(Chord::Keys("ArrowUp".into())));
+
+        let chord = Chord::new(&Key::Named(NamedKey::Control), "", ModifiersState::CONTROL);
+        assert_eq!(chord, None);
+    }
+
+    #[test]
+    fn merge_text() {
+        let mut presentation = Presentation::default();
+        presentation.record_key(&character("l"), "l", ModifiersState::empty());
+        presentation.record_key(&character("s"), "s", ModifiersState::empty());
+        presentation.record_key(&Key::Named(NamedKey::Enter), "\r", ModifiersState::empty());
+        presentation.record_key(&character("d"), "d", ModifiersState::CONTROL);
+        assert_eq!(presentation.key_chords(), "ls  Enter  Ctrl+D");
+
+        for _ in 0..MAX_CHORDS {
+            presentation.record_key(&character("x"), "x", ModifiersState::ALT);
+        }
+        assert_eq!(presentation.key_chords(), ["Alt+X"; MAX_CHORDS].join("  "));
+
+        for _ in 0..MAX_TEXT_LEN + 1 {
+            presentation.record_key(&character("a"), "a", ModifiersState::empty());
+        }
+        assert_eq!(presentation.chords.back().unwrap().as_str().len(), MAX_TEXT_LEN);
+    }
+
+    #[test]
+    fn fade_out() {
+        let mut presentation = Presentation::default();
+        assert_eq!(presentation.opacity(), 0.);
+        assert!(presentation.completed());
+
+        presentation.record_key(&character("a"), "a", ModifiersState::empty());
+        let pressed_at = presentation.pressed_at.unwrap();
+        assert_eq!(presentation.opacity_at_instant(pressed_at + VISIBLE_DURATION), 1.);
+
+        let faded = pressed_at + VISIBLE_DURATION + FADE_DURATION;
+        assert_eq!(presentation.opacity_at_instant(faded), 0.);
+    }
+}
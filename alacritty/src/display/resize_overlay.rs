@@ -0,0 +1,74 @@
+use std::time::{Duration, Instant};
+
+/// Time the overlay is fully visible after the last resize.
+const VISIBLE_DURATION: Duration = Duration::from_millis(750);
+
+/// Time it takes the overlay to fade out.
+const FADE_DURATION: Duration = Duration::from_millis(250);
+
+/// Overlay with the grid dimensions, shown while the window is being resized.
+#[derive(Default)]
+pub struct ResizeOverlay {
+    /// The last time the window was resized, if the overlay is still visible.
+    resized_at: Option<Instant>,
+}
+
+impl ResizeOverlay {
+    /// Show the overlay, restarting its timeout.
+    pub fn show(&mut self) {
+        self.resized_at = Some(Instant::now());
+    }
+
+    /// Get the current opacity of the overlay.
+    pub fn opacity(&self) -> f32 {
+        self.opacity_at_instant(Instant::now())
+    }
+
+    /// Check whether or not the overlay has disappeared completely.
+    pub fn completed(&mut self) -> bool {
+        if self.opacity() == 0. {
+            self.resized_at = None;
+        }
+
+        self.resized_at.is_none()
+    }
+
+    /// Get the opacity of the overlay at a particular instant.
+    fn opacity_at_instant(&self, instant: Instant) -> f32 {
+        let elapsed = match self.resized_at {
+            Some(resized_at) => instant.saturating_duration_since(resized_at),
+            None => return 0.,
+        };
+
+        let fade_elapsed = match elapsed.checked_sub(VISIBLE_DURATION) {
+            Some(fade_elapsed) => fade_elapsed,
+            None => return 1.,
+        };
+
+        1. - (fade_elapsed.as_secs_f32() / FADE_DURATION.as_secs_f32()).min(1.)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fade_out() {
+        let mut overlay = ResizeOverlay::default();
+        assert_eq!(overlay.opacity(), 0.);
+        assert!(overlay.completed());
+
+        overlay.show();
+        let resized_at = overlay.resized_at.unwrap();
+        assert_eq!(overlay.opacity_at_instant(resized_at), 1.);
+        assert_eq!(overlay.opacity_at_instant(resized_at + VISIBLE_DURATION), 1.);
+
+        let half_faded = resized_at + VISIBLE_DURATION + FADE_DURATION / 2;
+        assert!((overlay.opacity_at_instant(half_faded) - 0.5).abs() < 0.01);
+
+        let faded = resized_at + VISIBLE_DURATION + FADE_DURATION;
+        assert_eq!(overlay.opacity_at_instant(faded), 0.);
+        assert!(!overlay.completed());
+    }
+}
@@ -112,12 +112,20 @@ pub struct Window {
     /// Hold the window when terminal exits.
     pub hold: bool,
 
+    /// Window is requesting the user's attention.
+    is_urgent: bool,
+
     window: WinitWindow,
 
     /// Current window title.
     title: String,
 
     is_x11: bool,
+    ime_allowed: bool,
+
+    /// Whether the window was maximized or fullscreen during the last resize.
+    resized_tiled: Option<bool>,
+
     current_mouse_cursor: CursorIcon,
     mouse_visible: bool,
 }
@@ -207,10 +215,13 @@ impl Window {
             title: identity.title,
             current_mouse_cursor,
             mouse_visible: true,
+            is_urgent: false,
             has_frame: true,
             scale_factor,
             window,
             is_x11,
+            ime_allowed: true,
+            resized_tiled: None,
         })
     }
 
@@ -350,10 +361,16 @@ impl Window {
         }
     }
 
-    pub fn set_urgent(&self, is_urgent: bool) {
+    pub fn set_urgent(&mut self, is_urgent: bool) {
         let attention = if is_urgent { Some(UserAttentionType::Critical) } else { None };
 
         self.window.request_user_attention(attention);
+        self.is_urgent = is_urgent;
+    }
+
+    #[inline]
+    pub fn is_urgent(&self) -> bool {
+        self.is_urgent
     }
 
     pub fn id(&self) -> WindowId {
@@ -390,6 +407,16 @@ impl Window {
         self.set_maximized(!self.window.is_maximized());
     }
 
+    /// Check whether the latest resize was caused by the user changing the window's size.
+    ///
+    /// This ignores the initial resize, and resizes caused by maximizing the window or switching
+    /// to fullscreen, or leaving either of those states.
+    pub fn is_interactive_resize(&mut self) -> bool {
+        let tiled = self.window.is_maximized() || self.window.fullscreen().is_some();
+        let was_tiled = self.resized_tiled.replace(tiled);
+        was_tiled == Some(false) && !tiled
+    }
+
     /// Inform windowing system about presenting to the window.
     ///
     /// Should be called right before presenting to the window with e.g. `eglSwapBuffers`.
@@ -428,13 +455,27 @@ impl Window {
         self.window.set_simple_fullscreen(simple_fullscreen);
     }
 
-    pub fn set_ime_allowed(&self, allowed: bool) {
+    pub fn set_ime_allowed(&mut self, allowed: bool) {
+        self.ime_allowed = allowed;
+
         // Skip runtime IME manipulation on X11 since it breaks some IMEs.
         if !self.is_x11 {
             self.window.set_ime_allowed(allowed);
         }
     }
 
+    /// Show the virtual keyboard.
+    ///
+    /// Wayland compositors show virtual keyboards when text input is activated, so this restarts
+    /// the text input of the window. Windows with IME disabled are left untouched.
+    pub fn show_virtual_keyboard(&self) {
+        #[cfg(not(any(target_os = "macos", windows)))]
+        if !self.is_x11 && self.ime_allowed {
+            self.window.set_ime_allowed(false);
+            self.window.set_ime_allowed(true);
+        }
+    }
+
     /// Adjust the IME editor position according to the new location of the cursor.
     pub fn update_ime_position(&self, point: Point<usize>, size: &SizeInfo) {
         // NOTE: X11 doesn't support cursor area, so we need to offset manually to not obscure
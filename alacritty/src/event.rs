@@ -3,7 +3,7 @@
 use crate::ConfigMonitor;
 use glutin::config::GetGlConfig;
 use std::borrow::Cow;
-use std::cmp::min;
+use std::cmp::{max, min};
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::error::Error;
@@ -11,8 +11,14 @@ use std::ffi::OsStr;
 use std::fmt::Debug;
 #[cfg(not(windows))]
 use std::os::unix::io::RawFd;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
 use std::path::PathBuf;
+#[cfg(unix)]
+use std::process::Command;
 use std::rc::Rc;
+#[cfg(unix)]
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use std::{env, f32, mem};
 
@@ -35,26 +41,41 @@ use alacritty_terminal::event_loop::Notifier;
 use alacritty_terminal::grid::{BidirectionalIterator, Dimensions, Scroll};
 use alacritty_terminal::index::{Boundary, Column, Direction, Line, Point, Side};
 use alacritty_terminal::selection::{Selection, SelectionType};
+use alacritty_terminal::term::cell::{Hyperlink, LineLength};
 use alacritty_terminal::term::search::{Match, RegexSearch};
 use alacritty_terminal::term::{self, ClipboardType, Term, TermMode};
+#[cfg(unix)]
+use alacritty_terminal::thread;
+use alacritty_terminal::tty;
 use alacritty_terminal::vte::ansi::NamedColor;
 
 #[cfg(unix)]
-use crate::cli::{IpcConfig, ParsedOptions};
+use crate::cli::{self, IpcConfig, IpcDetach, IpcGet, IpcPreviewTheme, ParsedOptions};
 use crate::cli::{Options as CliOptions, WindowOptions};
-use crate::clipboard::Clipboard;
+use crate::clipboard::{Clipboard, ClipboardContent};
 use crate::config::ui_config::{HintAction, HintInternalAction};
+use crate::config::window::ConfirmClose;
 use crate::config::{self, UiConfig};
 #[cfg(not(windows))]
 use crate::daemon::foreground_process_path;
 use crate::daemon::spawn_daemon;
+#[cfg(unix)]
+use crate::daemon::spawn_daemon_command;
 use crate::display::color::Rgb;
 use crate::display::hint::HintMatch;
 use crate::display::window::Window;
 use crate::display::{Display, Preedit, SizeInfo};
+use crate::event_trace::{EventTrace, TraceEvent};
+use crate::hooks::Hook;
+use crate::lock_keys::LockKeys;
 use crate::input::{self, ActionContext as _, FONT_SIZE_STEP};
+use crate::launcher;
+#[cfg(unix)]
+use crate::ipc::{self, DetachedTerminal, IpcEvent};
 use crate::logging::{LOG_TARGET_CONFIG, LOG_TARGET_WINIT};
-use crate::message_bar::{Message, MessageBuffer};
+use crate::message_bar::{Message, MessageBuffer, MessageType};
+use crate::metrics;
+use crate::notification::Notifications;
 use crate::scheduler::{Scheduler, TimerId, Topic};
 use crate::window_context::WindowContext;
 
@@ -67,9 +88,21 @@ const MAX_SEARCH_WHILE_TYPING: Option<usize> = Some(1000);
 /// Maximum number of search terms stored in the history.
 const MAX_SEARCH_HISTORY_SIZE: usize = 255;
 
+/// Maximum number of clipboard history entries shown for selection, one for each digit.
+const MAX_CLIPBOARD_HISTORY_ENTRIES: usize = 10;
+
 /// Touch zoom speed.
 const TOUCH_ZOOM_FACTOR: f32 = 0.01;
 
+/// Message bar target for hyperlink confirmation prompts.
+const HYPERLINK_CONFIRMATION_TARGET: &str = "hyperlink_confirmation";
+
+/// Message bar target for large copy confirmation prompts.
+const COPY_CONFIRMATION_TARGET: &str = "copy_confirmation";
+
+/// Message bar target for window close confirmation prompts.
+const CLOSE_CONFIRMATION_TARGET: &str = "close_confirmation";
+
 /// The event processor.
 ///
 /// Stores some state from received events and dispatches actions when they are
@@ -86,8 +119,11 @@ pub struct Processor {
     gl_config: Option<GlutinConfig>,
     #[cfg(unix)]
     global_ipc_options: ParsedOptions,
+    #[cfg(unix)]
+    quit_after_detach: bool,
     cli_options: CliOptions,
     config: Rc<UiConfig>,
+    event_trace: Option<EventTrace>,
 }
 
 impl Processor {
@@ -106,7 +142,8 @@ impl Processor {
 
         // SAFETY: Since this takes a pointer to the winit event loop, it MUST be dropped first,
         // which is done in `loop_exiting`.
-        let clipboard = unsafe { Clipboard::new(event_loop.display_handle().unwrap().as_raw()) };
+        let display = event_loop.display_handle().unwrap().as_raw();
+        let clipboard = unsafe { Clipboard::new(display, &config.clipboard) };
 
         // Create a config monitor.
         //
@@ -118,6 +155,19 @@ impl Processor {
                 ConfigMonitor::new(config.config_paths.clone(), event_loop.create_proxy());
         }
 
+        if let Some(path) = &cli_options.replay_events {
+            #[cfg(feature = "event-replay")]
+            crate::event_trace::spawn_replay(path.clone(), event_loop.create_proxy());
+            #[cfg(not(feature = "event-replay"))]
+            error!("Unable to replay {path:?}: Alacritty was built without event replay");
+        }
+
+        let event_trace = cli_options.event_trace.as_ref().and_then(|path| {
+            EventTrace::new(path)
+                .map_err(|err| error!("Unable to create event trace {path:?}: {err}"))
+                .ok()
+        });
+
         Processor {
             initial_window_options,
             initial_window_error: None,
@@ -130,7 +180,10 @@ impl Processor {
             windows: Default::default(),
             #[cfg(unix)]
             global_ipc_options: Default::default(),
+            #[cfg(unix)]
+            quit_after_detach: false,
             config_monitor,
+            event_trace,
         }
     }
 
@@ -142,15 +195,20 @@ impl Processor {
         &mut self,
         event_loop: &ActiveEventLoop,
         window_options: WindowOptions,
+        #[cfg(unix)] detached: Option<&DetachedTerminal>,
     ) -> Result<(), Box<dyn Error>> {
         let window_context = WindowContext::initial(
             event_loop,
             self.proxy.clone(),
             self.config.clone(),
             window_options,
+            #[cfg(unix)]
+            detached,
         )?;
 
         self.gl_config = Some(window_context.display.gl_context().config());
+        #[cfg(unix)]
+        ipc::send_event(window_context.id(), IpcEvent::WindowCreated);
         self.windows.insert(window_context.id(), window_context);
 
         Ok(())
@@ -161,7 +219,16 @@ impl Processor {
         &mut self,
         event_loop: &ActiveEventLoop,
         options: WindowOptions,
+        #[cfg(unix)] detached: Option<&DetachedTerminal>,
     ) -> Result<(), Box<dyn Error>> {
+        // XXX Ensure that no context is current when creating a new window,
+        // otherwise it may lock the backing buffer of the
+        // surface of current context when asking
+        // e.g. EGL on Wayland to create a new context.
+        for window_context in self.windows.values_mut() {
+            window_context.display.make_not_current();
+        }
+
         let gl_config = self.gl_config.as_ref().unwrap();
 
         // Override config with CLI/IPC options.
@@ -178,12 +245,88 @@ impl Processor {
             config,
             options,
             config_overrides,
+            #[cfg(unix)]
+            detached,
         )?;
 
+        #[cfg(unix)]
+        ipc::send_event(window_context.id(), IpcEvent::WindowCreated);
         self.windows.insert(window_context.id(), window_context);
         Ok(())
     }
 
+    /// Move windows to another Alacritty instance.
+    ///
+    /// The windows are kept until the target instance has taken over their terminal, see
+    /// [`Self::finish_detach`].
+    #[cfg(unix)]
+    fn detach_windows(&mut self, detach: &IpcDetach, window_id: Option<WindowId>) {
+        self.quit_after_detach |= detach.quit;
+
+        let window_ids: Vec<WindowId> = self
+            .windows
+            .keys()
+            .filter(|id| window_id.is_none() || window_id == Some(**id))
+            .copied()
+            .collect();
+
+        for window_id in window_ids {
+            // Connect before detaching, to keep the window if the target is unavailable.
+            let stream = match UnixStream::connect(&detach.target) {
+                Ok(stream) => stream,
+                Err(err) => {
+                    error!("Unable to connect to {:?}: {err}", detach.target);
+                    return;
+                },
+            };
+
+            let terminal = match self.windows.get_mut(&window_id).map(|w| w.start_detach()) {
+                Some(Ok(terminal)) => terminal,
+                Some(Err(err)) => {
+                    error!("Unable to detach window: {err}");
+                    continue;
+                },
+                None => continue,
+            };
+
+            // Transfer the terminal in the background, since it has to serialize the scrollback.
+            let proxy = self.proxy.clone();
+            thread::spawn_named("detach terminal", move || {
+                let result = ipc::send_terminal(stream, terminal);
+                if let Err(err) = &result {
+                    error!("Unable to detach window: {err}");
+                }
+
+                let event = EventType::IpcDetachFinished(result.is_ok());
+                let _ = proxy.send_event(Event::new(event, window_id));
+            });
+        }
+    }
+
+    /// Close a window after its terminal was moved to another Alacritty instance.
+    ///
+    /// If the transfer failed, the window is kept and its terminal resumed instead.
+    #[cfg(unix)]
+    fn finish_detach(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId, success: bool) {
+        if !success {
+            if let Some(window_context) = self.windows.get_mut(&window_id) {
+                window_context.cancel_detach();
+            }
+            return;
+        }
+
+        if let Some(mut window_context) = self.windows.remove(&window_id) {
+            window_context.finish_detach();
+            self.scheduler.unschedule_window(window_id);
+            ipc::send_event(window_id, IpcEvent::WindowClosed);
+        }
+
+        // Shutdown once all terminals have been moved.
+        if self.windows.is_empty() && (!self.cli_options.daemon || self.quit_after_detach) {
+            event_loop.exit();
+        }
+    }
+
     /// Run the event loop.
     ///
     /// The result is exit code generate from the loop.
@@ -226,8 +369,19 @@ impl ApplicationHandler<Event> for Processor {
             return;
         }
 
+        // Windows of a restarted instance are attached once they arrive.
+        #[cfg(unix)]
+        if self.cli_options.restart_from.is_some() {
+            return;
+        }
+
         if let Some(window_options) = self.initial_window_options.take() {
-            if let Err(err) = self.create_initial_window(event_loop, window_options) {
+            if let Err(err) = self.create_initial_window(
+                event_loop,
+                window_options,
+                #[cfg(unix)]
+                None,
+            ) {
                 self.initial_window_error = Some(err);
                 event_loop.exit();
                 return;
@@ -247,6 +401,10 @@ impl ApplicationHandler<Event> for Processor {
             info!(target: LOG_TARGET_WINIT, "{event:?}");
         }
 
+        if let Some(event_trace) = &mut self.event_trace {
+            event_trace.record(Some(window_id), TraceEvent::from(&event));
+        }
+
         // Ignore all events we do not care about.
         if Self::skip_window_event(&event) {
             return;
@@ -278,8 +436,19 @@ impl ApplicationHandler<Event> for Processor {
             info!(target: LOG_TARGET_WINIT, "{event:?}");
         }
 
+        if let Some(event_trace) = &mut self.event_trace {
+            event_trace.record(event.window_id, TraceEvent::user(&event.payload));
+        }
+
         // Handle events which don't mandate the WindowId.
         match (event.payload, event.window_id.as_ref()) {
+            // Replay recorded events in the first window.
+            #[cfg(feature = "event-replay")]
+            (EventType::Replay(event), _) => {
+                if let Some(window_id) = self.windows.keys().next().copied() {
+                    self.window_event(event_loop, window_id, event);
+                }
+            },
             // Process IPC config update.
             #[cfg(unix)]
             (EventType::IpcConfig(ipc_config), window_id) => {
@@ -308,6 +477,63 @@ impl ApplicationHandler<Event> for Processor {
                     }
                 }
             },
+            // Process IPC theme preview.
+            #[cfg(unix)]
+            (EventType::IpcPreviewTheme(preview), window_id) => {
+                let colors: Vec<String> = preview.colors.into_iter().collect();
+                let options = ParsedOptions::from_options(&colors);
+
+                for (id, window_context) in self
+                    .windows
+                    .iter_mut()
+                    .filter(|(id, _)| window_id.is_none() || window_id == Some(*id))
+                {
+                    let timer_id = TimerId::new(Topic::ThemePreview, *id);
+                    self.scheduler.unschedule(timer_id);
+
+                    if preview.confirm {
+                        window_context.confirm_theme_preview();
+                    } else if preview.revert {
+                        window_context.revert_theme_preview(self.config.clone());
+                    } else {
+                        window_context.preview_theme(self.config.clone(), options.clone());
+
+                        // Revert the theme unless it is confirmed in time.
+                        let event = Event::new(EventType::ThemePreviewTimeout, *id);
+                        self.scheduler.schedule(event, preview.duration, false, timer_id);
+                    }
+                }
+            },
+            // Reply to IPC state queries.
+            #[cfg(unix)]
+            (EventType::IpcGet(stream, get), window_id) => {
+                for window_context in self
+                    .windows
+                    .iter()
+                    .filter(|(id, _)| window_id.is_none() || window_id == Some(*id))
+                    .map(|(_, window_context)| window_context)
+                {
+                    ipc::send_reply(&stream, &window_context.ipc_state(&get));
+                }
+            },
+            // Display synthetic output sent over IPC.
+            #[cfg(unix)]
+            (EventType::IpcInject(text), window_id) => {
+                for window_context in self
+                    .windows
+                    .iter()
+                    .filter(|(id, _)| window_id.is_none() || window_id == Some(*id))
+                    .map(|(_, window_context)| window_context)
+                {
+                    window_context.inject(text.clone());
+                }
+            },
+            #[cfg(unix)]
+            (EventType::ThemePreviewTimeout, Some(window_id)) => {
+                if let Some(window_context) = self.windows.get_mut(window_id) {
+                    window_context.revert_theme_preview(self.config.clone());
+                }
+            },
             (EventType::ConfigReload(path), _) => {
                 // Clear config logs from message bar for all terminals.
                 for window_context in self.windows.values_mut() {
@@ -332,6 +558,8 @@ impl ApplicationHandler<Event> for Processor {
                         };
                     }
 
+                    self.clipboard.set_history_size(self.config.clipboard.history);
+
                     for window_context in self.windows.values_mut() {
                         window_context.update_config(self.config.clone());
                     }
@@ -339,24 +567,49 @@ impl ApplicationHandler<Event> for Processor {
             },
             // Create a new terminal window.
             (EventType::CreateWindow(options), _) => {
-                // XXX Ensure that no context is current when creating a new window,
-                // otherwise it may lock the backing buffer of the
-                // surface of current context when asking
-                // e.g. EGL on Wayland to create a new context.
-                for window_context in self.windows.values_mut() {
-                    window_context.display.make_not_current();
-                }
-
                 if self.gl_config.is_none() {
                     // Handle initial window creation in daemon mode.
-                    if let Err(err) = self.create_initial_window(event_loop, options) {
+                    if let Err(err) = self.create_initial_window(
+                        event_loop,
+                        options,
+                        #[cfg(unix)]
+                        None,
+                    ) {
                         self.initial_window_error = Some(err);
                         event_loop.exit();
                     }
-                } else if let Err(err) = self.create_window(event_loop, options) {
+                } else if let Err(err) = self.create_window(
+                    event_loop,
+                    options,
+                    #[cfg(unix)]
+                    None,
+                ) {
                     error!("Could not open window: {:?}", err);
                 }
             },
+            // Take over a window detached from another Alacritty instance.
+            #[cfg(unix)]
+            (EventType::IpcAttach(terminal), _) => {
+                let result = if self.gl_config.is_none() {
+                    self.create_initial_window(event_loop, Default::default(), Some(&terminal))
+                } else {
+                    self.create_window(event_loop, Default::default(), Some(&terminal))
+                };
+
+                match result {
+                    Ok(()) => terminal.acknowledge(),
+                    Err(err) => error!("Could not attach window: {:?}", err),
+                }
+            },
+            // Move windows to another Alacritty instance.
+            #[cfg(unix)]
+            (EventType::IpcDetach(detach), window_id) => {
+                self.detach_windows(&detach, window_id.copied());
+            },
+            #[cfg(unix)]
+            (EventType::IpcDetachFinished(success), Some(window_id)) => {
+                self.finish_detach(event_loop, *window_id, success);
+            },
             // Process events affecting all windows.
             (payload, None) => {
                 let event = WinitEvent::UserEvent(Event::new(payload, None));
@@ -394,6 +647,9 @@ impl ApplicationHandler<Event> for Processor {
                 // Unschedule pending events.
                 self.scheduler.unschedule_window(window_context.id());
 
+                #[cfg(unix)]
+                ipc::send_event(window_context.id(), IpcEvent::WindowClosed);
+
                 // Shutdown if no more terminals are open.
                 if self.windows.is_empty() && !self.cli_options.daemon {
                     // Write ref tests of last window to disk.
@@ -433,6 +689,12 @@ impl ApplicationHandler<Event> for Processor {
             info!(target: LOG_TARGET_WINIT, "About to wait");
         }
 
+        // Write the trace once per event loop iteration, instead of for every event.
+        if let Some(event_trace) = &mut self.event_trace {
+            event_trace.record(None, TraceEvent::AboutToWait);
+            event_trace.flush();
+        }
+
         // Dispatch event to all windows.
         for window_context in self.windows.values_mut() {
             window_context.handle_event(
@@ -477,7 +739,7 @@ impl ApplicationHandler<Event> for Processor {
 
         // SAFETY: The clipboard must be dropped before the event loop, so use the nop clipboard
         // as a safe placeholder.
-        mem::swap(&mut self.clipboard, &mut Clipboard::new_nop());
+        self.clipboard = Clipboard::new_nop();
     }
 }
 
@@ -513,10 +775,31 @@ pub enum EventType {
     CreateWindow(WindowOptions),
     #[cfg(unix)]
     IpcConfig(IpcConfig),
+    #[cfg(unix)]
+    IpcPreviewTheme(IpcPreviewTheme),
+    #[cfg(unix)]
+    IpcGet(Arc<UnixStream>, IpcGet),
+    #[cfg(unix)]
+    IpcInject(String),
+    #[cfg(unix)]
+    IpcDetach(IpcDetach),
+    #[cfg(unix)]
+    IpcAttach(Arc<DetachedTerminal>),
+    #[cfg(unix)]
+    IpcDetachFinished(bool),
+    #[cfg(unix)]
+    ThemePreviewTimeout,
     BlinkCursor,
     BlinkCursorTimeout,
+    MouseIdle,
+    TouchLongPress(u64),
     SearchNext,
+    TitleUpdate,
+    #[cfg(unix)]
+    StoppedJob,
     Frame,
+    #[cfg(feature = "event-replay")]
+    Replay(WindowEvent),
 }
 
 impl From<TerminalEvent> for EventType {
@@ -600,6 +883,32 @@ impl Default for SearchState {
     }
 }
 
+/// Vi copy mode state.
+#[derive(Default)]
+pub struct CopyModeState {
+    /// Whether reading the terminal output is paused.
+    pub frozen: bool,
+
+    /// Whether the register name is waiting for input.
+    pub register_pending: bool,
+
+    /// Register used by the next copy or paste.
+    pub register: Option<char>,
+}
+
+impl CopyModeState {
+    /// Name the register using the text of the first key after register selection.
+    ///
+    /// Keys without text, like modifiers, are ignored while control characters cancel the
+    /// selection.
+    pub fn register_input(&mut self, text: &str) {
+        if let Some(register) = text.chars().next() {
+            self.register_pending = false;
+            self.register = (!register.is_control()).then_some(register);
+        }
+    }
+}
+
 /// Vi inline search state.
 pub struct InlineSearchState {
     /// Whether inline search is currently waiting for search character input.
@@ -638,18 +947,22 @@ pub struct ActionContext<'a, N, T> {
     pub scheduler: &'a mut Scheduler,
     pub search_state: &'a mut SearchState,
     pub inline_search_state: &'a mut InlineSearchState,
+    pub copy_mode: &'a mut CopyModeState,
     pub dirty: &'a mut bool,
     pub occluded: &'a mut bool,
     pub preserve_title: bool,
+    pub pending_title: &'a mut Option<String>,
+    pub notifications: &'a mut Notifications,
     #[cfg(not(windows))]
     pub master_fd: RawFd,
-    #[cfg(not(windows))]
     pub shell_pid: u32,
 }
 
 impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionContext<'a, N, T> {
     #[inline]
     fn write_to_pty<B: Into<Cow<'static, [u8]>>>(&self, val: B) {
+        let val = val.into();
+        metrics::pty_written(val.len());
         self.notifier.notify(val);
     }
 
@@ -680,7 +993,7 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
         let vi_mode = self.terminal.mode().contains(TermMode::VI);
 
         // Update selection.
-        if vi_mode && self.terminal.selection.as_ref().map_or(false, |s| !s.is_empty()) {
+        if vi_mode && self.terminal.selection.as_ref().is_some_and(|s| !s.is_empty()) {
             self.update_selection(self.terminal.vi_mode_cursor.point, Side::Right);
         } else if self.mouse.left_button_state == ElementState::Pressed
             || self.mouse.right_button_state == ElementState::Pressed
@@ -702,15 +1015,87 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
 
     // Copy text selection.
     fn copy_selection(&mut self, ty: ClipboardType) {
-        let text = match self.terminal.selection_to_string().filter(|s| !s.is_empty()) {
+        // Pending confirmations stay valid while their prompt is visible, even once the selection
+        // was cleared, like after copying in vi mode.
+        let unconfirmed = self.display.unconfirmed_copy.take();
+        let unconfirmed =
+            unconfirmed.filter(|_| self.message_buffer.contains_target(COPY_CONFIRMATION_TARGET));
+
+        let selection = match (&self.terminal.selection, &unconfirmed) {
+            (Some(selection), _) if !selection.is_empty() => selection.clone(),
+            (None, Some(unconfirmed)) => unconfirmed.clone(),
+            _ => {
+                self.display.unconfirmed_copy = unconfirmed;
+                return;
+            },
+        };
+
+        // Avoid locking up by serializing huge selections without confirmation.
+        let threshold_mib = self.config.selection.copy_confirmation_threshold as usize;
+        let threshold = threshold_mib.saturating_mul(1024 * 1024);
+        let confirmed = unconfirmed.as_ref() == Some(&selection);
+        if !confirmed && threshold > 0 {
+            let size = selection_size(self.terminal, &selection);
+            if size > threshold {
+                let size = size.div_ceil(1024 * 1024);
+                let text = format!("Copy the selection of about {size} MiB again to confirm");
+                let mut message = Message::new(text, MessageType::Warning);
+                message.set_target(COPY_CONFIRMATION_TARGET.into());
+
+                self.message_buffer.remove_target(COPY_CONFIRMATION_TARGET);
+                self.message_buffer.push(message);
+                self.display.pending_update.dirty = true;
+
+                self.display.unconfirmed_copy = Some(selection);
+                return;
+            }
+        }
+
+        if self.message_buffer.contains_target(COPY_CONFIRMATION_TARGET) {
+            self.message_buffer.remove_target(COPY_CONFIRMATION_TARGET);
+            self.display.pending_update.dirty = true;
+        }
+
+        let selection = self.terminal.selection.replace(selection);
+        let text = self.terminal.selection_to_string();
+        self.terminal.selection = selection;
+
+        let text = match text.filter(|s| !s.is_empty()) {
             Some(text) => text,
             None => return,
         };
 
+        // Store the text in the selected register instead of the clipboard.
+        if let Some(register) = self.copy_mode.register.take() {
+            self.clipboard.store_register(register, text);
+            return;
+        }
+
+        // Offer selections of existing files to file managers.
+        let content = ClipboardContent::with_file_detection(text);
+
         if ty == ClipboardType::Selection && self.config.selection.save_to_clipboard {
-            self.clipboard.store(ClipboardType::Clipboard, text.clone());
+            self.clipboard.store_content(ClipboardType::Clipboard, content.clone());
         }
-        self.clipboard.store(ty, text);
+        self.clipboard.store_content(ty, content);
+    }
+
+    // Copy the focused line with surrounding context.
+    fn copy_lines(&mut self, context: usize) {
+        let point = match self.search_state.focused_match() {
+            Some(focused_match) if self.search_active() => *focused_match.start(),
+            _ if self.terminal.mode().contains(TermMode::VI) => self.terminal.vi_mode_cursor.point,
+            _ => self.terminal.grid().cursor.point,
+        };
+
+        let context = min(context, self.terminal.total_lines()) as i32;
+        let start_line = max(point.line - context, self.terminal.topmost_line());
+        let end_line = min(point.line + context, self.terminal.bottommost_line());
+        let start = Point::new(start_line, Column(0));
+        let end = Point::new(end_line, self.terminal.last_column());
+
+        let text = self.terminal.bounds_to_string(start, end);
+        self.clipboard.store(ClipboardType::Clipboard, text);
     }
 
     fn selection_is_empty(&self) -> bool {
@@ -721,7 +1106,7 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
         // Clear the selection on the terminal.
         let selection = self.terminal.selection.take();
         // Mark the terminal as dirty when selection wasn't empty.
-        *self.dirty |= selection.map_or(false, |s| !s.is_empty());
+        *self.dirty |= selection.is_some_and(|s| !s.is_empty());
     }
 
     fn update_selection(&mut self, mut point: Point, side: Side) {
@@ -794,6 +1179,11 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
         self.modifiers
     }
 
+    #[inline]
+    fn lock_keys(&mut self) -> &mut LockKeys {
+        &mut self.display.lock_keys
+    }
+
     #[inline]
     fn window(&mut self) -> &mut Window {
         &mut self.display.window
@@ -841,6 +1231,80 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
         self.spawn_daemon(&alacritty, &args);
     }
 
+    #[cfg(unix)]
+    fn restart_alacritty(&mut self) {
+        let socket_path = match ipc::socket_path() {
+            Some(socket_path) => socket_path,
+            None => {
+                error!("Unable to restart Alacritty without IPC socket");
+                return;
+            },
+        };
+
+        let alacritty = match alacritty_executable() {
+            Ok(alacritty) => alacritty,
+            Err(err) => {
+                error!("Unable to find Alacritty executable: {err}");
+                return;
+            },
+        };
+
+        let args = cli::restart_args(env::args_os().skip(1), socket_path);
+        let mut command = Command::new(&alacritty);
+        command.args(&args);
+
+        match spawn_daemon_command(command, self.master_fd, self.shell_pid) {
+            Ok(_) => debug!("Restarting {alacritty:?} with args {args:?}"),
+            Err(err) => warn!("Unable to restart {alacritty:?}: {err}"),
+        }
+    }
+
+    #[cfg(windows)]
+    fn restart_alacritty(&mut self) {
+        warn!("Restarting Alacritty is not supported on Windows");
+    }
+
+    #[cfg(unix)]
+    fn resume_job(&mut self) {
+        let process_group = match tty::stopped_job(self.shell_pid) {
+            Some(process_group) => process_group,
+            None => return,
+        };
+
+        if let Err(err) = tty::resume_job(self.master_fd, process_group) {
+            warn!("Unable to resume stopped job: {err}");
+            return;
+        }
+
+        self.display.stopped_job = false;
+        *self.dirty = true;
+    }
+
+    fn confirm_close(&mut self) -> bool {
+        // Closing the window while its prompt is visible confirms it.
+        let confirmed = self.message_buffer.contains_target(CLOSE_CONFIRMATION_TARGET);
+        self.message_buffer.remove_target(CLOSE_CONFIRMATION_TARGET);
+        self.display.pending_update.dirty = true;
+
+        if confirmed {
+            return true;
+        }
+
+        let text = match self.config.window.confirm_close {
+            ConfirmClose::Always => "Close the window again to confirm",
+            ConfirmClose::IfProcessRunning if self.foreground_process_running() => {
+                "A process is still running, close the window again to terminate it"
+            },
+            _ => return true,
+        };
+
+        let mut message = Message::new(text.into(), MessageType::Warning);
+        message.set_target(CLOSE_CONFIRMATION_TARGET.into());
+        self.message_buffer.push(message);
+
+        false
+    }
+
     #[cfg(not(windows))]
     fn create_new_window(&mut self, #[cfg(target_os = "macos")] tabbing_id: Option<String>) {
         let mut options = WindowOptions::default();
@@ -890,11 +1354,31 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
     fn reset_font_size(&mut self) {
         let scale_factor = self.display.window.scale_factor as f32;
         self.display.font_size = self.config.font.size().scale(scale_factor);
+
+        // Keep the font enlarged while presenting.
+        if self.display.presentation.active() {
+            let font_scale = self.config.presentation.font_scale();
+            self.display.font_size = self.display.font_size.scale(font_scale);
+        }
+
         self.display
             .pending_update
             .set_font(self.config.font.clone().with_size(self.display.font_size));
     }
 
+    fn toggle_presentation_mode(&mut self) {
+        self.display.font_size = match self.display.presentation.stop() {
+            Some(font_size) => font_size,
+            None => {
+                self.display.presentation.start(self.display.font_size);
+                self.display.font_size.scale(self.config.presentation.font_scale())
+            },
+        };
+
+        let font = self.config.font.clone().with_size(self.display.font_size);
+        self.display.pending_update.set_font(font);
+    }
+
     #[inline]
     fn pop_message(&mut self) {
         if !self.message_buffer.is_empty() {
@@ -1142,6 +1626,24 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
         *self.dirty = true;
     }
 
+    fn show_clipboard_history(&mut self) {
+        let entries = self.clipboard.history().take(MAX_CLIPBOARD_HISTORY_ENTRIES);
+        self.display.clipboard_history = Some(entries.map(str::to_owned).collect());
+        *self.dirty = true;
+    }
+
+    /// Paste the clipboard history entry matching the pressed digit.
+    ///
+    /// Any other key, including keys without text, closes the clipboard history without pasting.
+    fn clipboard_history_input(&mut self, character: Option<char>) {
+        let entries = self.display.clipboard_history.take().unwrap_or_default();
+        let index = character.and_then(|c| c.to_digit(10)).map(|index| index as usize);
+        if let Some(entry) = index.and_then(|index| entries.get(index)) {
+            self.paste(entry, true);
+        }
+        *self.dirty = true;
+    }
+
     /// Trigger a hint action.
     fn trigger_hint(&mut self, hint: &HintMatch) {
         if self.mouse.block_hint_launcher {
@@ -1154,12 +1656,26 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
             None => return,
         };
 
+        // Require confirmation before opening hyperlinks with potentially dangerous targets.
+        let launches = matches!(
+            hint.action(),
+            HintAction::Command(_) | HintAction::Action(HintInternalAction::Open)
+        );
+        if let Some(hyperlink) = hint.hyperlink().filter(|_| launches) {
+            if !self.confirm_hyperlink(hyperlink) {
+                return;
+            }
+        }
+
         match &hint.action() {
             // Launch an external program.
-            HintAction::Command(command) => {
-                let mut args = command.args().to_vec();
-                args.push(text.into());
-                self.spawn_daemon(command.program(), &args);
+            HintAction::Command(command) => match launcher::validate_argument(&text) {
+                Ok(()) => {
+                    let mut args = command.args().to_vec();
+                    args.push(text.into());
+                    self.spawn_daemon(&launcher::program_path(command), &args);
+                },
+                Err(err) => warn!("Refusing to pass {text:?} to {:?}: {err}", command.program()),
             },
             // Copy the text to the clipboard.
             HintAction::Action(HintInternalAction::Copy) => {
@@ -1183,6 +1699,23 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
                 self.terminal.vi_goto_point(*hint_bounds.start());
                 self.mark_dirty();
             },
+            // Open the text with the program configured for its scheme.
+            HintAction::Action(HintInternalAction::Open) => {
+                match launcher::program_for(&self.config.hints.opener, &text) {
+                    Ok(program) => {
+                        #[cfg(windows)]
+                        let text = launcher::wsl_target(
+                            text.into(),
+                            self.config.terminal.wsl.distribution.as_deref(),
+                        );
+
+                        let mut args = program.args().to_vec();
+                        args.push(text.into());
+                        self.spawn_daemon(&launcher::program_path(program), &args);
+                    },
+                    Err(err) => warn!("Refusing to open {text:?}: {err}"),
+                }
+            },
         }
     }
 
@@ -1283,6 +1816,9 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
             if self.search_state.dfas.take().is_some() {
                 self.display.damage_tracker.frame().mark_fully_damaged();
             }
+
+            // Leaving vi mode also leaves copy mode.
+            *self.copy_mode = CopyModeState::default();
         } else {
             self.clear_selection();
         }
@@ -1299,6 +1835,25 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
         *self.dirty = true;
     }
 
+    /// Toggle vi mode with frozen terminal output.
+    fn toggle_copy_mode(&mut self) {
+        if self.copy_mode.frozen {
+            self.toggle_vi_mode();
+            return;
+        }
+
+        if !self.terminal.mode().contains(TermMode::VI) {
+            self.toggle_vi_mode();
+        }
+
+        self.copy_mode.frozen = true;
+    }
+
+    /// Get vi copy mode state.
+    fn copy_mode_state(&mut self) -> &mut CopyModeState {
+        self.copy_mode
+    }
+
     /// Get vi inline search state.
     fn inline_search_state(&mut self) -> &mut InlineSearchState {
         self.inline_search_state
@@ -1363,6 +1918,42 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
 }
 
 impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
+    /// Check if a process other than the shell is running in the foreground.
+    #[cfg(not(windows))]
+    fn foreground_process_running(&self) -> bool {
+        tty::foreground_process_running(self.master_fd, self.shell_pid)
+    }
+
+    /// Check if a process other than the shell is running in the foreground.
+    #[cfg(windows)]
+    fn foreground_process_running(&self) -> bool {
+        tty::foreground_process_running(self.shell_pid)
+    }
+
+    /// Check if a hyperlink was confirmed by triggering it twice.
+    ///
+    /// Returns `true` if the hyperlink can be opened without further confirmation.
+    fn confirm_hyperlink(&mut self, hyperlink: &Hyperlink) -> bool {
+        let confirmed = self.display.unconfirmed_hyperlink.take();
+        self.message_buffer.remove_target(HYPERLINK_CONFIRMATION_TARGET);
+        self.display.pending_update.dirty = true;
+
+        if !launcher::needs_confirmation(hyperlink.uri())
+            || confirmed.as_ref() == Some(hyperlink)
+        {
+            return true;
+        }
+
+        let text = format!("Trigger the hyperlink again to open {:?}", hyperlink.uri());
+        let mut message = Message::new(text, MessageType::Warning);
+        message.set_target(HYPERLINK_CONFIRMATION_TARGET.into());
+        self.message_buffer.push(message);
+
+        self.display.unconfirmed_hyperlink = Some(hyperlink.clone());
+
+        false
+    }
+
     fn update_search(&mut self) {
         let regex = match self.search_state.regex() {
             Some(regex) => regex,
@@ -1480,6 +2071,18 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         self.search_state.focused_match = None;
     }
 
+    /// Run the command configured for a terminal event.
+    fn run_hook(&self, hook: Hook) {
+        hook.run(
+            &self.config.hooks,
+            self.display.window.id(),
+            #[cfg(not(windows))]
+            self.master_fd,
+            #[cfg(not(windows))]
+            self.shell_pid,
+        );
+    }
+
     /// Update the cursor blinking state.
     fn update_cursor_blinking(&mut self) {
         // Get config cursor style.
@@ -1493,7 +2096,8 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         let terminal_blinking = self.terminal.cursor_style().blinking;
         let mut blinking = cursor_style.blinking_override().unwrap_or(terminal_blinking);
         blinking &= (vi_mode || self.terminal().mode().contains(TermMode::SHOW_CURSOR))
-            && self.display().ime.preedit().is_none();
+            && self.display().ime.preedit().is_none()
+            && !self.config.general.reduce_motion();
 
         // Update cursor blinking state.
         let window_id = self.display.window.id();
@@ -1520,6 +2124,18 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         self.scheduler.schedule(event, blinking_interval, true, timer_id);
     }
 
+    /// Restart the timer hiding the mouse pointer after inactivity.
+    fn schedule_mouse_idle(&mut self) {
+        let window_id = self.display.window.id();
+        let timer_id = TimerId::new(Topic::MouseIdle, window_id);
+        self.scheduler.unschedule(timer_id);
+
+        if let Some(timeout) = self.config.mouse.hide_when_idle() {
+            let event = Event::new(EventType::MouseIdle, window_id);
+            self.scheduler.schedule(event, timeout, false, timer_id);
+        }
+    }
+
     fn schedule_blinking_timeout(&mut self) {
         let blinking_timeout = self.config.cursor.blink_timeout();
         if blinking_timeout == Duration::ZERO {
@@ -1569,9 +2185,43 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
     }
 }
 
+/// Estimate the size of a selection's text in bytes, without converting it to a string.
+fn selection_size<T>(terminal: &Term<T>, selection: &Selection) -> usize {
+    let range = match selection.to_range(terminal) {
+        Some(range) => range,
+        None => return 0,
+    };
+
+    let columns = if range.is_block {
+        range.end.column.0 - range.start.column.0 + 1
+    } else {
+        terminal.columns()
+    };
+
+    // Count occupied cells, plus one byte for each line's newline.
+    (range.start.line.0..=range.end.line.0)
+        .map(|line| min(terminal.grid()[Line(line)].line_length().0, columns) + 1)
+        .sum()
+}
+
+/// Path of the running Alacritty executable, even after it was replaced by an upgrade.
+#[cfg(unix)]
+fn alacritty_executable() -> std::io::Result<PathBuf> {
+    let executable = env::current_exe()?;
+
+    // Linux marks the executable as deleted once it was replaced.
+    #[cfg(target_os = "linux")]
+    if let Some(path) = executable.to_str().and_then(|path| path.strip_suffix(" (deleted)")) {
+        return Ok(PathBuf::from(path));
+    }
+
+    Ok(executable)
+}
+
 /// Identified purpose of the touch input.
-#[derive(Debug)]
+#[derive(Default, Debug)]
 pub enum TouchPurpose {
+    #[default]
     None,
     Select(TouchEvent),
     Scroll(TouchEvent),
@@ -1580,12 +2230,6 @@ pub enum TouchPurpose {
     Invalid(HashSet<u64, RandomState>),
 }
 
-impl Default for TouchPurpose {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 /// Touch zooming state.
 #[derive(Debug)]
 pub struct TouchZoom {
@@ -1729,6 +2373,16 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
                     self.ctx.display.cursor_hidden = false;
                     *self.ctx.dirty = true;
                 },
+                EventType::MouseIdle => self.ctx.window().set_mouse_visible(false),
+                #[cfg(unix)]
+                EventType::StoppedJob => {
+                    let stopped_job = tty::stopped_job(self.ctx.shell_pid).is_some();
+                    if stopped_job != self.ctx.display.stopped_job {
+                        self.ctx.display.stopped_job = stopped_job;
+                        *self.ctx.dirty = true;
+                    }
+                },
+                EventType::TouchLongPress(id) => self.on_touch_long_press(id),
                 // Add message only if it's not already queued.
                 EventType::Message(message) if !self.ctx.message_buffer.is_queued(&message) => {
                     self.ctx.message_buffer.push(message);
@@ -1736,30 +2390,49 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
                 },
                 EventType::Terminal(event) => match event {
                     TerminalEvent::Title(title) => {
-                        if !self.ctx.preserve_title && self.ctx.config.window.dynamic_title {
-                            self.ctx.window().set_title(title);
+                        let window_config = &self.ctx.config.window;
+                        if !self.ctx.preserve_title
+                            && window_config.dynamic_title
+                            && !window_config.title_updates.is_ignored(&title)
+                        {
+                            *self.ctx.pending_title = Some(title);
                         }
                     },
                     TerminalEvent::ResetTitle => {
                         let window_config = &self.ctx.config.window;
                         if !self.ctx.preserve_title && window_config.dynamic_title {
-                            self.ctx.display.window.set_title(window_config.identity.title.clone());
+                            *self.ctx.pending_title = Some(window_config.identity.title.clone());
                         }
                     },
                     TerminalEvent::Bell => {
+                        #[cfg(unix)]
+                        ipc::send_event(self.ctx.display.window.id(), IpcEvent::Bell);
+
                         // Set window urgency hint when window is not focused.
                         let focused = self.ctx.terminal.is_focused;
                         if !focused && self.ctx.terminal.mode().contains(TermMode::URGENCY_HINTS) {
                             self.ctx.window().set_urgent(true);
+
+                            // Update the window border to the urgent color.
+                            if self.ctx.config.window.border.width != 0 {
+                                *self.ctx.dirty = true;
+                            }
                         }
 
-                        // Ring visual bell.
-                        self.ctx.display.visual_bell.ring();
+                        // Ring visual bell, unless animations are disabled.
+                        if !self.ctx.config.general.reduce_motion() {
+                            self.ctx.display.visual_bell.ring();
+                        }
+
+                        // Play bell sound.
+                        #[cfg(feature = "audio")]
+                        self.ctx.display.audible_bell.ring(&self.ctx.config.bell, focused);
 
                         // Execute bell command.
                         if let Some(bell_command) = &self.ctx.config.bell.command {
                             self.ctx.spawn_daemon(bell_command.program(), bell_command.args());
                         }
+                        self.ctx.run_hook(Hook::Bell);
                     },
                     TerminalEvent::ClipboardStore(clipboard_type, content) => {
                         if self.ctx.terminal.is_focused {
@@ -1788,21 +2461,51 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
                     TerminalEvent::PtyWrite(text) => self.ctx.write_to_pty(text.into_bytes()),
                     TerminalEvent::MouseCursorDirty => self.reset_mouse_cursor(),
                     TerminalEvent::CursorBlinkingChange => self.ctx.update_cursor_blinking(),
-                    TerminalEvent::Exit | TerminalEvent::ChildExit(_) | TerminalEvent::Wakeup => (),
+                    TerminalEvent::Notification(title, body) => {
+                        if !self.ctx.notifications.accept() {
+                            debug!("Dropped desktop notification: {title:?}, {body:?}");
+                            return;
+                        }
+
+                        if self.ctx.config.terminal.notifications {
+                            let window_title = self.ctx.display.window.title();
+                            let title = title.as_deref().unwrap_or(window_title);
+                            self.ctx.notifications.notify(title, &body);
+                        }
+
+                        self.ctx.run_hook(Hook::Notification(title, body));
+                    },
+                    TerminalEvent::ChildExit(code) => self.ctx.run_hook(Hook::ChildExit(code)),
+                    TerminalEvent::Exit
+                    | TerminalEvent::Wakeup
+                    | TerminalEvent::LineCommit(_)
+                    | TerminalEvent::CommandFinished => (),
                 },
                 #[cfg(unix)]
-                EventType::IpcConfig(_) => (),
+                EventType::IpcConfig(_)
+                | EventType::IpcPreviewTheme(_)
+                | EventType::IpcGet(..)
+                | EventType::IpcInject(_)
+                | EventType::IpcDetach(_)
+                | EventType::IpcAttach(_)
+                | EventType::IpcDetachFinished(_)
+                | EventType::ThemePreviewTimeout => (),
                 EventType::Message(_)
                 | EventType::ConfigReload(_)
                 | EventType::CreateWindow(_)
+                | EventType::TitleUpdate
                 | EventType::Frame => (),
+                #[cfg(feature = "event-replay")]
+                EventType::Replay(_) => (),
             },
             WinitEvent::WindowEvent { event, .. } => {
                 match event {
                     WindowEvent::CloseRequested => {
-                        // User asked to close the window, so no need to hold it.
-                        self.ctx.window().hold = false;
-                        self.ctx.terminal.exit();
+                        if self.ctx.confirm_close() {
+                            // User asked to close the window, so no need to hold it.
+                            self.ctx.window().hold = false;
+                            self.ctx.terminal.exit();
+                        }
                     },
                     WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
                         let old_scale_factor =
@@ -1825,7 +2528,10 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
                             return;
                         }
 
-                        self.ctx.display.pending_update.set_dimensions(size);
+                        let interactive_resize = self.ctx.display.window.is_interactive_resize();
+                        let pending_update = &mut self.ctx.display.pending_update;
+                        pending_update.interactive_resize |= interactive_resize;
+                        pending_update.set_dimensions(size);
                     },
                     WindowEvent::KeyboardInput { event, is_synthetic: false, .. } => {
                         self.key_input(event);
@@ -1833,22 +2539,27 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
                     WindowEvent::ModifiersChanged(modifiers) => self.modifiers_input(modifiers),
                     WindowEvent::MouseInput { state, button, .. } => {
                         self.ctx.window().set_mouse_visible(true);
+                        self.ctx.schedule_mouse_idle();
                         self.mouse_input(state, button);
                     },
                     WindowEvent::CursorMoved { position, .. } => {
                         self.ctx.window().set_mouse_visible(true);
+                        self.ctx.schedule_mouse_idle();
                         self.mouse_moved(position);
                     },
                     WindowEvent::MouseWheel { delta, phase, .. } => {
                         self.ctx.window().set_mouse_visible(true);
+                        self.ctx.schedule_mouse_idle();
                         self.mouse_wheel_input(delta, phase);
                     },
                     WindowEvent::Touch(touch) => self.touch(touch),
                     WindowEvent::Focused(is_focused) => {
                         self.ctx.terminal.is_focused = is_focused;
 
-                        // When the unfocused hollow is used we must redraw on focus change.
-                        if self.ctx.config.cursor.unfocused_hollow {
+                        // When the unfocused hollow or a border is used we must redraw on focus
+                        // change.
+                        let border = self.ctx.config.window.border.width != 0;
+                        if self.ctx.config.cursor.unfocused_hollow || border {
                             *self.ctx.dirty = true;
                         }
 
@@ -1917,7 +2628,7 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
                     | WindowEvent::Moved(_) => (),
                 }
             },
-            WinitEvent::Suspended { .. }
+            WinitEvent::Suspended
             | WinitEvent::NewEvents { .. }
             | WinitEvent::DeviceEvent { .. }
             | WinitEvent::LoopExiting
@@ -1947,6 +2658,21 @@ impl EventProxy {
 
 impl EventListener for EventProxy {
     fn send_event(&self, event: TerminalEvent) {
-        let _ = self.proxy.send_event(Event::new(event.into(), self.window_id));
+        match event {
+            #[cfg(unix)]
+            TerminalEvent::LineCommit(line) => ipc::send_output(self.window_id, &line),
+            #[cfg(unix)]
+            TerminalEvent::CommandFinished => {
+                ipc::send_event(self.window_id, IpcEvent::CommandFinished)
+            },
+            event => {
+                let _ = self.proxy.send_event(Event::new(event.into(), self.window_id));
+            },
+        }
+    }
+
+    #[cfg(unix)]
+    fn wants_committed_lines(&self) -> bool {
+        ipc::has_output_subscribers()
     }
 }
@@ -0,0 +1,310 @@
+//! Structured trace of the events handled by the event loop.
+
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::Instant;
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+use winit::event::{ElementState, Ime, MouseButton, MouseScrollDelta, TouchPhase, WindowEvent};
+use winit::keyboard::{Key, KeyLocation, ModifiersState, PhysicalKey};
+use winit::window::WindowId;
+
+#[cfg(feature = "event-replay")]
+pub use replay::spawn_replay;
+
+/// Writer for event traces in the JSON lines format.
+pub struct EventTrace {
+    writer: BufWriter<File>,
+    start: Instant,
+}
+
+impl EventTrace {
+    pub fn new(path: &Path) -> io::Result<Self> {
+        let writer = BufWriter::new(File::create(path)?);
+        Ok(Self { writer, start: Instant::now() })
+    }
+
+    /// Record an event.
+    pub fn record(&mut self, window_id: Option<WindowId>, event: TraceEvent) {
+        let entry = TraceEntry {
+            time: self.start.elapsed().as_secs_f64(),
+            window_id: window_id.map(u64::from),
+            event,
+        };
+
+        let result = serde_json::to_writer(&mut self.writer, &entry)
+            .map_err(io::Error::from)
+            .and_then(|_| self.writer.write_all(b"\n"));
+        if let Err(err) = result {
+            warn!("Unable to write event trace: {err}");
+        }
+    }
+
+    /// Write all buffered events to the trace file.
+    pub fn flush(&mut self) {
+        if let Err(err) = self.writer.flush() {
+            warn!("Unable to write event trace: {err}");
+        }
+    }
+}
+
+/// Line of an event trace.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TraceEntry {
+    /// Seconds since the start of the trace.
+    pub time: f64,
+
+    /// Window which received the event.
+    pub window_id: Option<u64>,
+
+    pub event: TraceEvent,
+}
+
+/// Event recorded in an event trace.
+///
+/// Only events relevant for reproducing input handling record their payload.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum TraceEvent {
+    Resized {
+        width: u32,
+        height: u32,
+    },
+    ScaleFactorChanged {
+        scale_factor: f64,
+    },
+    Focused {
+        focused: bool,
+    },
+    KeyboardInput {
+        logical_key: Key,
+        physical_key: PhysicalKey,
+        text: Option<String>,
+        location: KeyLocation,
+        state: ElementState,
+        repeat: bool,
+    },
+    ModifiersChanged {
+        state: ModifiersState,
+    },
+    Ime {
+        ime: Ime,
+    },
+    CursorMoved {
+        x: f64,
+        y: f64,
+    },
+    CursorEntered,
+    CursorLeft,
+    MouseWheel {
+        delta: MouseScrollDelta,
+        phase: TouchPhase,
+    },
+    MouseInput {
+        state: ElementState,
+        button: MouseButton,
+    },
+    Touch {
+        id: u64,
+        phase: TouchPhase,
+        x: f64,
+        y: f64,
+    },
+    /// Alacritty's own events.
+    User {
+        name: String,
+    },
+    AboutToWait,
+    /// Window events without a recorded payload.
+    Other {
+        name: String,
+    },
+}
+
+impl TraceEvent {
+    /// Record an Alacritty event by name.
+    pub fn user(event: &impl Debug) -> Self {
+        Self::User { name: variant_name(event) }
+    }
+}
+
+impl From<&WindowEvent> for TraceEvent {
+    fn from(event: &WindowEvent) -> Self {
+        match event {
+            WindowEvent::Resized(size) => Self::Resized { width: size.width, height: size.height },
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                Self::ScaleFactorChanged { scale_factor: *scale_factor }
+            },
+            WindowEvent::Focused(focused) => Self::Focused { focused: *focused },
+            WindowEvent::KeyboardInput { event, .. } => Self::KeyboardInput {
+                logical_key: event.logical_key.clone(),
+                physical_key: event.physical_key,
+                text: event.text.as_ref().map(|text| text.to_string()),
+                location: event.location,
+                state: event.state,
+                repeat: event.repeat,
+            },
+            WindowEvent::ModifiersChanged(modifiers) => {
+                Self::ModifiersChanged { state: modifiers.state() }
+            },
+            WindowEvent::Ime(ime) => Self::Ime { ime: ime.clone() },
+            WindowEvent::CursorMoved { position, .. } => {
+                Self::CursorMoved { x: position.x, y: position.y }
+            },
+            WindowEvent::CursorEntered { .. } => Self::CursorEntered,
+            WindowEvent::CursorLeft { .. } => Self::CursorLeft,
+            WindowEvent::MouseWheel { delta, phase, .. } => {
+                Self::MouseWheel { delta: *delta, phase: *phase }
+            },
+            WindowEvent::MouseInput { state, button, .. } => {
+                Self::MouseInput { state: *state, button: *button }
+            },
+            WindowEvent::Touch(touch) => Self::Touch {
+                id: touch.id,
+                phase: touch.phase,
+                x: touch.location.x,
+                y: touch.location.y,
+            },
+            event => Self::Other { name: variant_name(event) },
+        }
+    }
+}
+
+/// Name of an enum variant, based on its debug representation.
+fn variant_name(event: &impl Debug) -> String {
+    let debug = format!("{event:?}");
+    debug.split(|c: char| !c.is_alphanumeric()).next().unwrap_or_default().into()
+}
+
+#[cfg(feature = "event-replay")]
+mod replay {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{Duration, Instant};
+
+    use log::{error, warn};
+    use winit::dpi::{PhysicalPosition, PhysicalSize};
+    use winit::event::{DeviceId, ElementState, Ime, WindowEvent};
+    use winit::event_loop::EventLoopProxy;
+
+    use alacritty_terminal::thread;
+
+    use crate::event::{Event, EventType};
+    use crate::event_trace::{TraceEntry, TraceEvent};
+
+    /// Send the window events of a trace to the event loop, with their original timing.
+    pub fn spawn_replay(path: PathBuf, proxy: EventLoopProxy<Event>) {
+        let start = Instant::now();
+        thread::spawn_named("event replay", move || {
+            let trace = match fs::read_to_string(&path) {
+                Ok(trace) => trace,
+                Err(err) => {
+                    error!("Unable to read event trace {path:?}: {err}");
+                    return;
+                },
+            };
+
+            for line in trace.lines() {
+                let entry: TraceEntry = match serde_json::from_str(line) {
+                    Ok(entry) => entry,
+                    Err(err) => {
+                        warn!("Skipping invalid event trace entry: {err}");
+                        continue;
+                    },
+                };
+
+                let event = match window_event(entry.event) {
+                    Some(event) => event,
+                    None => continue,
+                };
+
+                let time = start + Duration::from_secs_f64(entry.time.max(0.));
+                std::thread::sleep(time.saturating_duration_since(Instant::now()));
+
+                let event = Event::new(EventType::Replay(event), None);
+                if proxy.send_event(event).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    /// Convert a recorded event back into a window event.
+    fn window_event(event: TraceEvent) -> Option<WindowEvent> {
+        let device_id = DeviceId::dummy();
+
+        let event = match event {
+            TraceEvent::Resized { width, height } => {
+                WindowEvent::Resized(PhysicalSize::new(width, height))
+            },
+            TraceEvent::Focused { focused } => WindowEvent::Focused(focused),
+            // Winit doesn't allow creating key events, so their text is committed instead.
+            TraceEvent::KeyboardInput { text: Some(text), state: ElementState::Pressed, .. } => {
+                WindowEvent::Ime(Ime::Commit(text))
+            },
+            TraceEvent::ModifiersChanged { state } => WindowEvent::ModifiersChanged(state.into()),
+            TraceEvent::Ime { ime } => WindowEvent::Ime(ime),
+            TraceEvent::CursorMoved { x, y } => {
+                WindowEvent::CursorMoved { device_id, position: PhysicalPosition::new(x, y) }
+            },
+            TraceEvent::CursorEntered => WindowEvent::CursorEntered { device_id },
+            TraceEvent::CursorLeft => WindowEvent::CursorLeft { device_id },
+            TraceEvent::MouseWheel { delta, phase } => {
+                WindowEvent::MouseWheel { device_id, delta, phase }
+            },
+            TraceEvent::MouseInput { state, button } => {
+                WindowEvent::MouseInput { device_id, state, button }
+            },
+            _ => return None,
+        };
+
+        Some(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use winit::keyboard::{KeyCode, NamedKey};
+
+    #[test]
+    fn stable_format() {
+        let event = WindowEvent::Focused(true);
+        let entry = TraceEntry { time: 1.5, window_id: Some(3), event: (&event).into() };
+
+        let json = serde_json::to_string(&entry).unwrap();
+        assert_eq!(json, r#"{"time":1.5,"window_id":3,"event":{"type":"Focused","focused":true}}"#);
+        assert_eq!(serde_json::from_str::<TraceEntry>(&json).unwrap(), entry);
+    }
+
+    #[test]
+    fn keyboard_input() {
+        let event = TraceEvent::KeyboardInput {
+            logical_key: Key::Named(NamedKey::Enter),
+            physical_key: PhysicalKey::Code(KeyCode::Enter),
+            text: Some(String::from("\r")),
+            location: KeyLocation::Standard,
+            state: ElementState::Pressed,
+            repeat: false,
+        };
+
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(serde_json::from_str::<TraceEvent>(&json).unwrap(), event);
+    }
+
+    #[test]
+    fn user_event_name() {
+        #[derive(Debug)]
+        enum Event {
+            Wakeup(#[allow(dead_code)] u8),
+        }
+
+        assert_eq!(TraceEvent::user(&Event::Wakeup(1)), TraceEvent::User {
+            name: String::from("Wakeup")
+        });
+    }
+}
@@ -0,0 +1,148 @@
+//! Commands run on terminal events.
+
+#[cfg(not(windows))]
+use std::os::unix::io::RawFd;
+use std::process::Command;
+
+use log::{debug, warn};
+use winit::window::WindowId;
+
+use crate::config::hooks::Hooks;
+use crate::config::ui_config::Program;
+use crate::daemon::spawn_daemon_command;
+
+/// Terminal events which run a user command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Hook {
+    Bell,
+    Title(String),
+    ChildExit(i32),
+    Notification(Option<String>, String),
+}
+
+impl Hook {
+    /// Run the command configured for the hook.
+    pub fn run(
+        &self,
+        hooks: &Hooks,
+        window_id: WindowId,
+        #[cfg(not(windows))] master_fd: RawFd,
+        #[cfg(not(windows))] shell_pid: u32,
+    ) {
+        let program = match self.program(hooks) {
+            Some(program) => program,
+            None => return,
+        };
+
+        let command = self.command(program, window_id);
+
+        #[cfg(not(windows))]
+        let result = spawn_daemon_command(command, master_fd, shell_pid);
+        #[cfg(windows)]
+        let result = spawn_daemon_command(command);
+
+        match result {
+            Ok(_) => debug!("Launched {} hook {:?}", self.name(), program.program()),
+            Err(err) => {
+                warn!("Unable to launch {} hook {:?}: {err}", self.name(), program.program())
+            },
+        }
+    }
+
+    /// Name of the hook in the configuration file.
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Bell => "bell",
+            Self::Title(_) => "title",
+            Self::ChildExit(_) => "child_exit",
+            Self::Notification(..) => "notification",
+        }
+    }
+
+    /// Command configured for the hook.
+    fn program<'a>(&self, hooks: &'a Hooks) -> Option<&'a Program> {
+        match self {
+            Self::Bell => hooks.bell.as_ref(),
+            Self::Title(_) => hooks.title.as_ref(),
+            Self::ChildExit(_) => hooks.child_exit.as_ref(),
+            Self::Notification(..) => hooks.notification.as_ref(),
+        }
+    }
+
+    /// Command for the hook's program.
+    ///
+    /// Details about the event are passed to the command through environment variables.
+    fn command(&self, program: &Program, window_id: WindowId) -> Command {
+        let mut command = Command::new(program.program());
+        command.args(program.args());
+        command.env("ALACRITTY_HOOK", self.name());
+        command.env("ALACRITTY_WINDOW_ID", u64::from(window_id).to_string());
+        match self {
+            Self::Title(title) => command.env("ALACRITTY_TITLE", title),
+            Self::ChildExit(code) => command.env("ALACRITTY_EXIT_CODE", code.to_string()),
+            Self::Notification(title, body) => {
+                if let Some(title) = title {
+                    command.env("ALACRITTY_NOTIFICATION_TITLE", title);
+                }
+                command.env("ALACRITTY_NOTIFICATION_BODY", body)
+            },
+            Self::Bell => &mut command,
+        };
+        command
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ffi::OsStr;
+
+    /// Environment variables set for a hook's command.
+    fn env(hook: Hook) -> Vec<(String, String)> {
+        let program = Program::WithArgs { program: "notify".into(), args: vec!["-u".into()] };
+        let command = hook.command(&program, WindowId::from(3));
+        assert_eq!(command.get_program(), "notify");
+        assert_eq!(command.get_args().collect::<Vec<_>>(), [OsStr::new("-u")]);
+
+        command
+            .get_envs()
+            .map(|(key, value)| {
+                let value = value.unwrap_or_default().to_string_lossy().into_owned();
+                (key.to_string_lossy().into_owned(), value)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn program() {
+        let hooks = Hooks { title: Some(Program::Just("title".into())), ..Hooks::default() };
+        assert_eq!(Hook::Title("x".into()).program(&hooks), hooks.title.as_ref());
+        assert_eq!(Hook::Bell.program(&hooks), None);
+    }
+
+    #[test]
+    fn title_env() {
+        let mut env = env(Hook::Title("vim".into()));
+        env.sort();
+        assert_eq!(env, [
+            ("ALACRITTY_HOOK".into(), "title".into()),
+            ("ALACRITTY_TITLE".into(), "vim".into()),
+            ("ALACRITTY_WINDOW_ID".into(), "3".into()),
+        ]);
+    }
+
+    #[test]
+    fn notification_env() {
+        let env = env(Hook::Notification(None, "Done".into()));
+        assert!(env.contains(&("ALACRITTY_HOOK".into(), "notification".into())));
+        assert!(env.contains(&("ALACRITTY_NOTIFICATION_BODY".into(), "Done".into())));
+        assert!(!env.iter().any(|(key, _)| key == "ALACRITTY_NOTIFICATION_TITLE"));
+    }
+
+    #[test]
+    fn child_exit_env() {
+        let env = env(Hook::ChildExit(2));
+        assert!(env.contains(&("ALACRITTY_EXIT_CODE".into(), "2".into())));
+    }
+}
@@ -12,6 +12,7 @@ use alacritty_terminal::term::TermMode;
 use winit::platform::modifier_supplement::KeyEventExtModifierSupplement;
 
 use crate::config::{Action, BindingKey, BindingMode};
+use crate::display::RevealTrigger;
 use crate::event::TYPING_SEARCH_DELAY;
 use crate::input::{ActionContext, Execute, Processor};
 use crate::scheduler::{TimerId, Topic};
@@ -28,6 +29,8 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
         let mods = self.ctx.modifiers().state();
 
         if key.state == ElementState::Released {
+            self.stop_revealing_hidden(RevealTrigger::Key(key.physical_key));
+
             if self.ctx.inline_search_state().char_pending {
                 self.ctx.window().set_ime_allowed(true);
             }
@@ -35,6 +38,24 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
             return;
         }
 
+        // Redraw when a lock key indicator might need to be toggled.
+        let lock_keys = *self.ctx.lock_keys();
+        self.ctx.lock_keys().update(&key, mods);
+        if *self.ctx.lock_keys() != lock_keys {
+            self.ctx.mark_dirty();
+        }
+
+        // Show the key chord in presentation mode.
+        if self.ctx.display().presentation.active()
+            && self.ctx.config().presentation.show_keys
+            && !key.repeat
+        {
+            let key_text = key.text.as_deref().unwrap_or_default();
+            let presentation = &mut self.ctx.display().presentation;
+            presentation.record_key(&key.key_without_modifiers(), key_text, mods);
+            self.ctx.mark_dirty();
+        }
+
         let text = key.text_with_all_modifiers().unwrap_or_default();
 
         // All key bindings are disabled while a hint is being selected.
@@ -45,6 +66,28 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
             return;
         }
 
+        // The first key selects the clipboard history entry which should be pasted.
+        //
+        // Modifiers on their own are ignored, since they might be required to type a digit.
+        if self.ctx.display().clipboard_history.is_some() {
+            let modifier = matches!(
+                key.logical_key,
+                Key::Named(
+                    NamedKey::Shift
+                        | NamedKey::Control
+                        | NamedKey::Alt
+                        | NamedKey::Super
+                        | NamedKey::Meta
+                        | NamedKey::Hyper
+                        | NamedKey::AltGraph
+                )
+            );
+            if !modifier {
+                self.ctx.clipboard_history_input(text.chars().next());
+            }
+            return;
+        }
+
         // First key after inline search is captured.
         let inline_state = self.ctx.inline_search_state();
         if inline_state.char_pending {
@@ -52,6 +95,16 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
             return;
         }
 
+        // First key after register selection names the register.
+        let copy_mode = self.ctx.copy_mode_state();
+        if copy_mode.register_pending {
+            match key.logical_key {
+                Key::Named(NamedKey::Escape) => copy_mode.register_pending = false,
+                _ => copy_mode.register_input(text),
+            }
+            return;
+        }
+
         // Reset search delay when the user is still typing.
         self.reset_search_delay();
 
@@ -77,6 +130,7 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
         let mods = if self.alt_send_esc(&key, text) { mods } else { mods & !ModifiersState::ALT };
 
         let build_key_sequence = Self::should_build_sequence(&key, text, mode, mods);
+        let snap_viewport = self.snaps_viewport(&key);
 
         let bytes = if build_key_sequence {
             build_sequence(key, mods, mode)
@@ -92,11 +146,31 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
 
         // Write only if we have something to write.
         if !bytes.is_empty() {
-            self.ctx.on_terminal_input_start();
+            self.on_key_input_start(snap_viewport);
             self.ctx.write_to_pty(bytes);
         }
     }
 
+    /// Handle beginning of terminal input for a key.
+    fn on_key_input_start(&mut self, snap_viewport: bool) {
+        if snap_viewport {
+            self.ctx.on_terminal_input_start();
+        } else {
+            self.ctx.on_typing_start();
+        }
+    }
+
+    /// Check whether input for the key should scroll the viewport to the bottom.
+    fn snaps_viewport(&mut self, key: &KeyEvent) -> bool {
+        let mods = self.ctx.modifiers().state();
+        let logical_key = binding_logical_key(key, mods);
+
+        !self.ctx.config().scrolling.snap_exceptions.iter().any(|exception| {
+            exception.key == binding_key(&exception.key, key, &logical_key)
+                && exception.mods.map_or(true, |exception_mods| exception_mods.into_inner() == mods)
+        })
+    }
+
     fn alt_send_esc(&mut self, key: &KeyEvent, text: &str) -> bool {
         #[cfg(not(target_os = "macos"))]
         let alt_send_esc = self.ctx.modifiers().state().alt_key();
@@ -154,53 +228,51 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
     /// The provided mode, mods, and key must match what is allowed by a binding
     /// for its action to be executed.
     fn process_key_bindings(&mut self, key: &KeyEvent) -> bool {
-        let mode = BindingMode::new(self.ctx.terminal().mode(), self.ctx.search_active());
+        let lock_keys = *self.ctx.lock_keys();
+        let mode =
+            BindingMode::new(self.ctx.terminal().mode(), self.ctx.search_active(), lock_keys);
         let mods = self.ctx.modifiers().state();
 
         // Don't suppress char if no bindings were triggered.
         let mut suppress_chars = None;
+        let mut send_csi_u = false;
+        let mut reveal_hidden = false;
 
-        // We don't want the key without modifier, because it means something else most of
-        // the time. However what we want is to manually lowercase the character to account
-        // for both small and capital letters on regular characters at the same time.
-        let logical_key = if let Key::Character(ch) = key.logical_key.as_ref() {
-            // Match `Alt` bindings without `Alt` being applied, otherwise they use the
-            // composed chars, which are not intuitive to bind.
-            //
-            // On Windows, the `Ctrl + Alt` mangles `logical_key` to unidentified values, thus
-            // preventing them from being used in bindings
-            //
-            // For more see https://github.com/rust-windowing/winit/issues/2945.
-            if (cfg!(target_os = "macos") || (cfg!(windows) && mods.control_key()))
-                && mods.alt_key()
-            {
-                key.key_without_modifiers()
-            } else {
-                Key::Character(ch.to_lowercase().into())
-            }
-        } else {
-            key.logical_key.clone()
-        };
+        let logical_key = binding_logical_key(key, mods);
 
         for i in 0..self.ctx.config().key_bindings().len() {
             let binding = &self.ctx.config().key_bindings()[i];
 
-            let key = match (&binding.trigger, &logical_key) {
-                (BindingKey::Scancode(_), _) => BindingKey::Scancode(key.physical_key),
-                (_, code) => {
-                    BindingKey::Keycode { key: code.clone(), location: key.location.into() }
-                },
-            };
+            let binding_key = binding_key(&binding.trigger, key, &logical_key);
 
-            if binding.is_triggered_by(mode, mods, &key) {
+            if binding.is_triggered_by(mode, mods, &binding_key) {
                 // Pass through the key if any of the bindings has the `ReceiveChar` action.
                 *suppress_chars.get_or_insert(true) &= binding.action != Action::ReceiveChar;
 
+                send_csi_u |= binding.action == Action::SendCsiU;
+                reveal_hidden |= binding.action == Action::RevealHidden;
+
                 // Binding was triggered; run the action.
                 binding.action.clone().execute(&mut self.ctx);
             }
         }
 
+        if reveal_hidden {
+            self.start_revealing_hidden(RevealTrigger::Key(key.physical_key));
+        }
+
+        // Disambiguate the key even if the application didn't request it, like `Ctrl + I` and
+        // `Tab`, using the encoding shared by fixterms and the kitty keyboard protocol.
+        if send_csi_u {
+            let mode = *self.ctx.terminal().mode() | TermMode::DISAMBIGUATE_ESC_CODES;
+            let bytes = build_sequence(key.clone(), mods, mode);
+            if !bytes.is_empty() {
+                let snap_viewport = self.snaps_viewport(key);
+                self.on_key_input_start(snap_viewport);
+                self.ctx.write_to_pty(bytes);
+            }
+        }
+
         suppress_chars.unwrap_or(false)
     }
 
@@ -244,6 +316,38 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
     }
 }
 
+/// Logical key used for matching against configured keys.
+fn binding_logical_key(key: &KeyEvent, mods: ModifiersState) -> Key {
+    // We don't want the key without modifier, because it means something else most of
+    // the time. However what we want is to manually lowercase the character to account
+    // for both small and capital letters on regular characters at the same time.
+    if let Key::Character(ch) = key.logical_key.as_ref() {
+        // Match `Alt` bindings without `Alt` being applied, otherwise they use the
+        // composed chars, which are not intuitive to bind.
+        //
+        // On Windows, the `Ctrl + Alt` mangles `logical_key` to unidentified values, thus
+        // preventing them from being used in bindings
+        //
+        // For more see https://github.com/rust-windowing/winit/issues/2945.
+        if (cfg!(target_os = "macos") || (cfg!(windows) && mods.control_key())) && mods.alt_key()
+        {
+            key.key_without_modifiers()
+        } else {
+            Key::Character(ch.to_lowercase().into())
+        }
+    } else {
+        key.logical_key.clone()
+    }
+}
+
+/// Key of the event in the representation used by the configured key.
+fn binding_key(configured: &BindingKey, key: &KeyEvent, logical_key: &Key) -> BindingKey {
+    match configured {
+        BindingKey::Scancode(_) => BindingKey::Scancode(key.physical_key),
+        _ => BindingKey::Keycode { key: logical_key.clone(), location: key.location.into() },
+    }
+}
+
 /// Build a key's keyboard escape sequence based on the given `key`, `mods`, and `mode`.
 ///
 /// The key sequences for `APP_KEYPAD` and alike are handled inside the bindings.
@@ -39,12 +39,13 @@ use crate::clipboard::Clipboard;
 #[cfg(target_os = "macos")]
 use crate::config::window::Decorations;
 use crate::config::{Action, BindingMode, MouseAction, SearchAction, UiConfig, ViAction};
-use crate::display::hint::HintMatch;
+use crate::display::hint::{self, HintMatch};
 use crate::display::window::Window;
-use crate::display::{Display, SizeInfo};
+use crate::display::{Display, RevealTrigger, SizeInfo};
 use crate::event::{
-    ClickState, Event, EventType, InlineSearchState, Mouse, TouchPurpose, TouchZoom,
+    ClickState, CopyModeState, Event, EventType, InlineSearchState, Mouse, TouchPurpose, TouchZoom,
 };
+use crate::lock_keys::LockKeys;
 use crate::message_bar::{self, Message};
 use crate::scheduler::{Scheduler, TimerId, Topic};
 
@@ -65,6 +66,9 @@ const SELECTION_SCROLLING_STEP: f64 = 20.;
 /// Distance before a touch input is considered a drag.
 const MAX_TAP_DISTANCE: f64 = 20.;
 
+/// Time a touch input has to be held in place to start a selection.
+const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+
 /// Threshold used for double_click/triple_click.
 const CLICK_THRESHOLD: Duration = Duration::from_millis(400);
 
@@ -82,6 +86,7 @@ pub trait ActionContext<T: EventListener> {
     fn mark_dirty(&mut self) {}
     fn size_info(&self) -> SizeInfo;
     fn copy_selection(&mut self, _ty: ClipboardType) {}
+    fn copy_lines(&mut self, _context: usize) {}
     fn start_selection(&mut self, _ty: SelectionType, _point: Point, _side: Side) {}
     fn toggle_selection(&mut self, _ty: SelectionType, _point: Point, _side: Side) {}
     fn update_selection(&mut self, _point: Point, _side: Side) {}
@@ -91,18 +96,25 @@ pub trait ActionContext<T: EventListener> {
     fn mouse(&self) -> &Mouse;
     fn touch_purpose(&mut self) -> &mut TouchPurpose;
     fn modifiers(&mut self) -> &mut Modifiers;
+    fn lock_keys(&mut self) -> &mut LockKeys;
     fn scroll(&mut self, _scroll: Scroll) {}
     fn window(&mut self) -> &mut Window;
     fn display(&mut self) -> &mut Display;
     fn terminal(&self) -> &Term<T>;
     fn terminal_mut(&mut self) -> &mut Term<T>;
     fn spawn_new_instance(&mut self) {}
+    fn restart_alacritty(&mut self) {}
+    fn resume_job(&mut self) {}
+    fn confirm_close(&mut self) -> bool {
+        true
+    }
     #[cfg(target_os = "macos")]
     fn create_new_window(&mut self, _tabbing_id: Option<String>) {}
     #[cfg(not(target_os = "macos"))]
     fn create_new_window(&mut self) {}
     fn change_font_size(&mut self, _delta: f32) {}
     fn reset_font_size(&mut self) {}
+    fn toggle_presentation_mode(&mut self) {}
     fn pop_message(&mut self) {}
     fn message(&self) -> Option<&Message>;
     fn config(&self) -> &UiConfig;
@@ -124,12 +136,16 @@ pub trait ActionContext<T: EventListener> {
     fn search_active(&self) -> bool;
     fn on_typing_start(&mut self) {}
     fn toggle_vi_mode(&mut self) {}
+    fn toggle_copy_mode(&mut self) {}
+    fn copy_mode_state(&mut self) -> &mut CopyModeState;
     fn inline_search_state(&mut self) -> &mut InlineSearchState;
     fn start_inline_search(&mut self, _direction: Direction, _stop_short: bool) {}
     fn inline_search_next(&mut self) {}
     fn inline_search_input(&mut self, _text: &str) {}
     fn inline_search_previous(&mut self) {}
     fn hint_input(&mut self, _character: char) {}
+    fn show_clipboard_history(&mut self) {}
+    fn clipboard_history_input(&mut self, _character: Option<char>) {}
     fn trigger_hint(&mut self, _hint: &HintMatch) {}
     fn expand_selection(&mut self) {}
     fn on_terminal_input_start(&mut self) {}
@@ -175,6 +191,10 @@ impl<T: EventListener> Execute<T> for Action {
                 ctx.on_typing_start();
                 ctx.toggle_vi_mode()
             },
+            Action::ToggleCopyMode => {
+                ctx.on_typing_start();
+                ctx.toggle_copy_mode()
+            },
             action @ (Action::ViMotion(_) | Action::Vi(_))
                 if !ctx.terminal().mode().contains(TermMode::VI) =>
             {
@@ -205,6 +225,13 @@ impl<T: EventListener> Execute<T> for Action {
                 }
                 ctx.display().vi_highlighted_hint = hint;
             },
+            Action::Vi(ViAction::OpenUnderCursor) => {
+                let point = ctx.terminal().vi_mode_cursor.point;
+                if let Some(hint) = hint::closest_on_line(ctx.terminal(), ctx.config(), point) {
+                    ctx.mouse_mut().block_hint_launcher = false;
+                    ctx.trigger_hint(&hint);
+                }
+            },
             Action::Vi(ViAction::SearchNext) => {
                 ctx.on_typing_start();
 
@@ -278,6 +305,14 @@ impl<T: EventListener> Execute<T> for Action {
             },
             Action::Vi(ViAction::InlineSearchNext) => ctx.inline_search_next(),
             Action::Vi(ViAction::InlineSearchPrevious) => ctx.inline_search_previous(),
+            Action::Vi(ViAction::SelectRegister) => ctx.copy_mode_state().register_pending = true,
+            Action::Vi(ViAction::PasteRegister) => match ctx.copy_mode_state().register.take() {
+                Some(register) => {
+                    let text = ctx.clipboard_mut().load_register(register);
+                    ctx.paste(&text, true);
+                },
+                None => debug!("Ignoring {self:?}: No register selected"),
+            },
             action @ Action::Search(_) if !ctx.search_active() => {
                 debug!("Ignoring {action:?}: Search mode inactive");
             },
@@ -304,15 +339,26 @@ impl<T: EventListener> Execute<T> for Action {
             Action::Copy => ctx.copy_selection(ClipboardType::Clipboard),
             #[cfg(not(any(target_os = "macos", windows)))]
             Action::CopySelection => ctx.copy_selection(ClipboardType::Selection),
+            Action::CopyLines(context) => ctx.copy_lines(*context),
             Action::ClearSelection => ctx.clear_selection(),
             Action::Paste => {
-                let text = ctx.clipboard_mut().load(ClipboardType::Clipboard);
+                let text = match ctx.copy_mode_state().register.take() {
+                    Some(register) => ctx.clipboard_mut().load_register(register),
+                    None => ctx.clipboard_mut().load(ClipboardType::Clipboard),
+                };
                 ctx.paste(&text, true);
             },
             Action::PasteSelection => {
                 let text = ctx.clipboard_mut().load(ClipboardType::Selection);
                 ctx.paste(&text, true);
             },
+            Action::PasteFromHistory(index) => {
+                if let Some(text) = ctx.clipboard_mut().load_history(*index) {
+                    let text = text.to_owned();
+                    ctx.paste(&text, true);
+                }
+            },
+            Action::ShowClipboardHistory => ctx.show_clipboard_history(),
             Action::ToggleFullscreen => ctx.window().toggle_fullscreen(),
             Action::ToggleMaximized => ctx.window().toggle_maximized(),
             #[cfg(target_os = "macos")]
@@ -324,40 +370,37 @@ impl<T: EventListener> Execute<T> for Action {
             #[cfg(not(target_os = "macos"))]
             Action::Hide => ctx.window().set_visible(false),
             Action::Minimize => ctx.window().set_minimized(true),
-            Action::Quit => {
+            Action::Quit if ctx.confirm_close() => {
                 ctx.window().hold = false;
                 ctx.terminal_mut().exit();
             },
             Action::IncreaseFontSize => ctx.change_font_size(FONT_SIZE_STEP),
             Action::DecreaseFontSize => ctx.change_font_size(-FONT_SIZE_STEP),
             Action::ResetFontSize => ctx.reset_font_size(),
+            Action::TogglePresentationMode => ctx.toggle_presentation_mode(),
             Action::ScrollPageUp
             | Action::ScrollPageDown
             | Action::ScrollHalfPageUp
             | Action::ScrollHalfPageDown => {
-                // Move vi mode cursor.
+                let page = ctx.config().scrolling.multiplier.page.as_f32();
                 let term = ctx.terminal_mut();
-                let (scroll, amount) = match self {
-                    Action::ScrollPageUp => (Scroll::PageUp, term.screen_lines() as i32),
-                    Action::ScrollPageDown => (Scroll::PageDown, -(term.screen_lines() as i32)),
-                    Action::ScrollHalfPageUp => {
-                        let amount = term.screen_lines() as i32 / 2;
-                        (Scroll::Delta(amount), amount)
-                    },
-                    Action::ScrollHalfPageDown => {
-                        let amount = -(term.screen_lines() as i32 / 2);
-                        (Scroll::Delta(amount), amount)
-                    },
+                let page_lines = (term.screen_lines() as f32 * page).round().max(1.) as i32;
+                let amount = match self {
+                    Action::ScrollPageUp => page_lines,
+                    Action::ScrollPageDown => -page_lines,
+                    Action::ScrollHalfPageUp => page_lines / 2,
+                    Action::ScrollHalfPageDown => -(page_lines / 2),
                     _ => unreachable!(),
                 };
 
+                // Move vi mode cursor.
                 let old_vi_cursor = term.vi_mode_cursor;
                 term.vi_mode_cursor = term.vi_mode_cursor.scroll(term, amount);
                 if old_vi_cursor != term.vi_mode_cursor {
                     ctx.mark_dirty();
                 }
 
-                ctx.scroll(scroll);
+                ctx.scroll(Scroll::Delta(amount));
             },
             Action::ScrollLineUp => ctx.scroll(Scroll::Delta(1)),
             Action::ScrollLineDown => ctx.scroll(Scroll::Delta(-1)),
@@ -382,11 +425,30 @@ impl<T: EventListener> Execute<T> for Action {
                 term.vi_motion(ViMotion::FirstOccupied);
                 ctx.mark_dirty();
             },
+            Action::JumpToPreviousPrompt | Action::JumpToNextPrompt => {
+                let direction = match self {
+                    Action::JumpToPreviousPrompt => Direction::Left,
+                    _ => Direction::Right,
+                };
+
+                if let Some(scroll) = ctx.terminal().prompt_scroll(direction) {
+                    ctx.scroll(scroll);
+                }
+            },
+            Action::SelectLastCommandOutput => {
+                if let Some(output) = ctx.terminal().last_command_output() {
+                    ctx.start_selection(SelectionType::Lines, *output.start(), Side::Left);
+                    ctx.update_selection(*output.end(), Side::Right);
+                    ctx.copy_selection(ClipboardType::Selection);
+                }
+            },
             Action::ClearHistory => ctx.terminal_mut().clear_screen(ClearMode::Saved),
             Action::ClearLogNotice => ctx.pop_message(),
             #[cfg(not(target_os = "macos"))]
             Action::CreateNewWindow => ctx.create_new_window(),
             Action::SpawnNewInstance => ctx.spawn_new_instance(),
+            Action::RestartAlacritty => ctx.restart_alacritty(),
+            Action::ResumeJob => ctx.resume_job(),
             #[cfg(target_os = "macos")]
             Action::CreateNewWindow => ctx.create_new_window(None),
             #[cfg(target_os = "macos")]
@@ -675,6 +737,8 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
     }
 
     fn on_mouse_release(&mut self, button: MouseButton) {
+        self.stop_revealing_hidden(RevealTrigger::Mouse(button));
+
         if !self.ctx.modifiers().state().shift_key() && self.ctx.mouse_mode() {
             let code = match button {
                 MouseButton::Left => 0,
@@ -712,7 +776,7 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
                 self.scroll_terminal(
                     new_scroll_px_x as f64,
                     new_scroll_px_y as f64,
-                    multiplier as f64,
+                    multiplier.wheel.as_f32() as f64,
                 );
             },
             MouseScrollDelta::PixelDelta(mut lpos) => {
@@ -730,7 +794,7 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
                             lpos.x = 0.;
                         }
 
-                        self.scroll_terminal(lpos.x, lpos.y, multiplier as f64);
+                        self.scroll_terminal(lpos.x, lpos.y, multiplier.touchpad.as_f32() as f64);
                     },
                     _ => (),
                 }
@@ -845,6 +909,33 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
                 TouchPurpose::Invalid(slots)
             },
         };
+
+        // Start selection if the finger is held in place.
+        if let TouchPurpose::Tap(_) = self.ctx.touch_purpose() {
+            let window_id = self.ctx.window().id();
+            let timer_id = TimerId::new(Topic::TouchLongPress, window_id);
+            let event = Event::new(EventType::TouchLongPress(touch.id), window_id);
+            self.ctx.scheduler_mut().unschedule(timer_id);
+            self.ctx.scheduler_mut().schedule(event, LONG_PRESS_DURATION, false, timer_id);
+        }
+    }
+
+    /// Handle touch input held in place.
+    pub fn on_touch_long_press(&mut self, id: u64) {
+        let touch_purpose = self.ctx.touch_purpose();
+        let start = match touch_purpose {
+            TouchPurpose::Tap(start) if start.id == id => *start,
+            _ => return,
+        };
+        *touch_purpose = TouchPurpose::Select(start);
+
+        // Simulate a double click, to select the word below the finger.
+        self.mouse_moved(start.location);
+        let mouse = self.ctx.mouse_mut();
+        mouse.click_state = ClickState::Click;
+        mouse.last_click_button = MouseButton::Left;
+        mouse.last_click_timestamp = Instant::now();
+        self.mouse_input(ElementState::Pressed, MouseButton::Left);
     }
 
     /// Handle touch input movement.
@@ -907,6 +998,11 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
                 self.mouse_moved(start_location);
                 self.mouse_input(ElementState::Pressed, MouseButton::Left);
                 self.mouse_input(ElementState::Released, MouseButton::Left);
+
+                // Bring back the virtual keyboard, in case it was dismissed.
+                if !self.ctx.terminal().mode().contains(TermMode::VI) {
+                    self.ctx.window().show_virtual_keyboard();
+                }
             },
             // Invalidate zoom once a finger was released.
             TouchPurpose::Zoom(zoom) => {
@@ -1003,7 +1099,9 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
     /// The provided mode, mods, and key must match what is allowed by a binding
     /// for its action to be executed.
     fn process_mouse_bindings(&mut self, button: MouseButton) {
-        let mode = BindingMode::new(self.ctx.terminal().mode(), self.ctx.search_active());
+        let lock_keys = *self.ctx.lock_keys();
+        let mode =
+            BindingMode::new(self.ctx.terminal().mode(), self.ctx.search_active(), lock_keys);
         let mouse_mode = self.ctx.mouse_mode();
         let mods = self.ctx.modifiers().state();
         let mouse_bindings = self.ctx.config().mouse_bindings().to_owned();
@@ -1012,9 +1110,12 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
         let fallback_allowed = mouse_mode && mods.contains(ModifiersState::SHIFT);
         let mut exact_match_found = false;
 
+        let mut reveal_hidden = false;
+
         for binding in &mouse_bindings {
             // Don't trigger normal bindings in mouse mode unless Shift is pressed.
             if binding.is_triggered_by(mode, mods, &button) && (fallback_allowed || !mouse_mode) {
+                reveal_hidden |= binding.action == Action::RevealHidden;
                 binding.action.execute(&mut self.ctx);
                 exact_match_found = true;
             }
@@ -1024,10 +1125,15 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
             let fallback_mods = mods & !ModifiersState::SHIFT;
             for binding in &mouse_bindings {
                 if binding.is_triggered_by(mode, fallback_mods, &button) {
+                    reveal_hidden |= binding.action == Action::RevealHidden;
                     binding.action.execute(&mut self.ctx);
                 }
             }
         }
+
+        if reveal_hidden {
+            self.start_revealing_hidden(RevealTrigger::Mouse(button));
+        }
     }
 
     /// Check mouse icon state in relation to the message bar.
@@ -1066,8 +1172,10 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
 
         if let Some(mouse_state) = self.message_bar_cursor_state() {
             mouse_state
-        } else if self.ctx.display().highlighted_hint.as_ref().map_or(false, hint_highlighted) {
+        } else if self.ctx.display().highlighted_hint.as_ref().is_some_and(hint_highlighted) {
             CursorIcon::Pointer
+        } else if let Some(icon) = self.ctx.terminal().mouse_cursor_icon() {
+            icon
         } else if !self.ctx.modifiers().state().shift_key() && self.ctx.mouse_mode() {
             CursorIcon::Default
         } else {
@@ -1075,6 +1183,20 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
         }
     }
 
+    /// Reveal concealed text, until the input which triggered the binding is released.
+    fn start_revealing_hidden(&mut self, trigger: RevealTrigger) {
+        self.ctx.display().reveal_hidden = Some(trigger);
+        self.ctx.mark_dirty();
+    }
+
+    /// Conceal hidden text again, once the reveal binding has been released.
+    fn stop_revealing_hidden(&mut self, trigger: RevealTrigger) {
+        if self.ctx.display().reveal_hidden == Some(trigger) {
+            self.ctx.display().reveal_hidden = None;
+            self.ctx.mark_dirty();
+        }
+    }
+
     /// Handle automatic scrolling when selecting above/below the window.
     fn update_selection_scrolling(&mut self, mouse_y: i32) {
         let scale_factor = self.ctx.window().scale_factor;
@@ -1136,8 +1258,11 @@ mod tests {
         pub clipboard: &'a mut Clipboard,
         pub message_buffer: &'a mut MessageBuffer,
         pub modifiers: Modifiers,
+        pub lock_keys: LockKeys,
         config: &'a UiConfig,
         inline_search_state: &'a mut InlineSearchState,
+        copy_mode: &'a mut CopyModeState,
+        pasted: String,
     }
 
     impl<T: EventListener> super::ActionContext<T> for ActionContext<'_, T> {
@@ -1158,6 +1283,10 @@ mod tests {
             self.inline_search_state
         }
 
+        fn copy_mode_state(&mut self) -> &mut CopyModeState {
+            self.copy_mode
+        }
+
         fn search_active(&self) -> bool {
             false
         }
@@ -1205,6 +1334,10 @@ mod tests {
             &mut self.modifiers
         }
 
+        fn lock_keys(&mut self) -> &mut LockKeys {
+            &mut self.lock_keys
+        }
+
         fn window(&mut self) -> &mut Window {
             unimplemented!();
         }
@@ -1229,6 +1362,10 @@ mod tests {
             self.clipboard
         }
 
+        fn paste(&mut self, text: &str, _bracketed: bool) {
+            self.pasted.push_str(text);
+        }
+
         #[cfg(target_os = "macos")]
         fn event_loop(&self) -> &ActiveEventLoop {
             unimplemented!();
@@ -1272,6 +1409,7 @@ mod tests {
                 };
 
                 let mut inline_search_state = InlineSearchState::default();
+                let mut copy_mode = CopyModeState::default();
                 let mut message_buffer = MessageBuffer::default();
 
                 let context = ActionContext {
@@ -1280,8 +1418,11 @@ mod tests {
                     size_info: &size,
                     clipboard: &mut clipboard,
                     modifiers: Default::default(),
+                    lock_keys: Default::default(),
                     message_buffer: &mut message_buffer,
                     inline_search_state: &mut inline_search_state,
+                    copy_mode: &mut copy_mode,
+                    pasted: Default::default(),
                     config: &cfg,
                 };
 
@@ -1515,4 +1656,58 @@ mod tests {
         mode: BindingMode::empty(),
         mods: ModifiersState::ALT | ModifiersState::SUPER,
     }
+
+    #[test]
+    fn paste_register() {
+        let mut clipboard = Clipboard::new_nop();
+        clipboard.store_register('a', String::from("register"));
+        let cfg = UiConfig::default();
+        let size = SizeInfo::new(21.0, 51.0, 3.0, 3.0, 0., 0., false);
+        let mut terminal = Term::new(cfg.term_options(), &size, MockEventProxy);
+        terminal.toggle_vi_mode();
+
+        let mut mouse = Mouse::default();
+        let mut inline_search_state = InlineSearchState::default();
+        let mut copy_mode = CopyModeState::default();
+        let mut message_buffer = MessageBuffer::default();
+        let mut context = ActionContext {
+            terminal: &mut terminal,
+            mouse: &mut mouse,
+            size_info: &size,
+            clipboard: &mut clipboard,
+            modifiers: Default::default(),
+            lock_keys: Default::default(),
+            message_buffer: &mut message_buffer,
+            inline_search_state: &mut inline_search_state,
+            copy_mode: &mut copy_mode,
+            pasted: Default::default(),
+            config: &cfg,
+        };
+
+        // Without a selected register, nothing is pasted.
+        Action::Vi(ViAction::PasteRegister).execute(&mut context);
+        assert_eq!(context.pasted, "");
+
+        // Keys without text keep waiting for the register name.
+        Action::Vi(ViAction::SelectRegister).execute(&mut context);
+        context.copy_mode.register_input("");
+        assert!(context.copy_mode.register_pending);
+
+        context.copy_mode.register_input("a");
+        assert_eq!(context.copy_mode.register, Some('a'));
+
+        // The register is only used once.
+        Action::Vi(ViAction::PasteRegister).execute(&mut context);
+        Action::Vi(ViAction::PasteRegister).execute(&mut context);
+        assert_eq!(context.pasted, "register");
+        assert_eq!(context.copy_mode.register, None);
+    }
+
+    #[test]
+    fn cancel_register_selection() {
+        let mut copy_mode = CopyModeState { register_pending: true, ..Default::default() };
+        copy_mode.register_input("\x1b");
+        assert!(!copy_mode.register_pending);
+        assert_eq!(copy_mode.register, None);
+    }
 }
@@ -1,23 +1,79 @@
 //! Alacritty socket IPC.
 
 use std::ffi::OsStr;
-use std::io::{BufRead, BufReader, Error as IoError, ErrorKind, Result as IoResult, Write};
+use std::io::{
+    self, BufRead, BufReader, BufWriter, Error as IoError, ErrorKind, Read, Result as IoResult,
+    Write,
+};
+use std::net::Shutdown;
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
 use std::os::unix::net::{UnixListener, UnixStream};
-use std::path::PathBuf;
-use std::{env, fs, process};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, SyncSender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use std::{env, fs, mem, process, ptr};
 
 use log::warn;
+use serde::Serialize;
 use winit::event_loop::EventLoopProxy;
 use winit::window::WindowId;
 
+use alacritty_terminal::grid::Dimensions;
+use alacritty_terminal::sync::FairMutex;
+use alacritty_terminal::term::{LineDamageBounds, Term, TermState};
 use alacritty_terminal::thread;
+use alacritty_terminal::vte::ansi::CursorShape;
 
-use crate::cli::{Options, SocketMessage};
-use crate::event::{Event, EventType};
+use crate::cli::{IpcAttach, IpcDetach, IpcSubscribe, Options, SocketMessage, SubscribeStream};
+use crate::display::color::Rgb;
+use crate::display::content::{RenderableCell, RenderableCursor};
+use crate::display::SizeInfo;
+use crate::event::{Event, EventProxy, EventType};
 
 /// Environment variable name for the IPC socket path.
 const ALACRITTY_SOCKET_ENV: &str = "ALACRITTY_SOCKET";
 
+/// Maximum number of lines queued for a single subscriber.
+const SUBSCRIBER_QUEUE_SIZE: usize = 1024;
+
+/// Maximum time a subscriber can stall the output before it is disconnected.
+const SUBSCRIBER_WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Interval for checking whether idle subscribers are still connected.
+const SUBSCRIBER_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Maximum time the receiving instance can take to attach a moved terminal.
+const ATTACH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum time a query reply can block Alacritty.
+const REPLY_WRITE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Maximum number of drawn frames waiting to be sent to subscribers.
+const FRAME_QUEUE_SIZE: usize = 4;
+
+/// Queue of drawn frames for the frame streaming thread.
+static FRAME_SENDER: OnceLock<SyncSender<(WindowId, IpcFrame)>> = OnceLock::new();
+
+/// Windows which dropped frames since their last sent frame.
+static DROPPED_FRAMES: Mutex<Vec<WindowId>> = Mutex::new(Vec::new());
+
+/// Path of this instance's IPC socket.
+static SOCKET_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Clients subscribed to terminal content.
+static SUBSCRIBERS: Mutex<Vec<Subscriber>> = Mutex::new(Vec::new());
+
+/// ID of the next subscriber.
+static NEXT_SUBSCRIBER_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Whether there is at least one output subscriber.
+static HAS_OUTPUT_SUBSCRIBERS: AtomicBool = AtomicBool::new(false);
+
+/// Whether there is at least one frame subscriber.
+static HAS_FRAME_SUBSCRIBERS: AtomicBool = AtomicBool::new(false);
+
 /// Create an IPC socket.
 pub fn spawn_ipc_socket(
     options: &Options,
@@ -32,6 +88,7 @@ pub fn spawn_ipc_socket(
     });
 
     let listener = UnixListener::bind(&socket_path)?;
+    let _ = SOCKET_PATH.set(socket_path.clone());
 
     env::set_var(ALACRITTY_SOCKET_ENV, socket_path.as_os_str());
     if options.daemon {
@@ -43,6 +100,13 @@ pub fn spawn_ipc_socket(
         let mut data = String::new();
         for stream in listener.incoming().filter_map(Result::ok) {
             data.clear();
+
+            // Receive the first byte separately, since it might carry a PTY.
+            let mut first = [0];
+            let (fd, stream) = match recv_fd(&stream, &mut first) {
+                Ok((0, _)) | Err(_) => continue,
+                Ok((_, fd)) => (fd, io::Cursor::new(first).chain(stream)),
+            };
             let mut stream = BufReader::new(stream);
 
             match stream.read_line(&mut data) {
@@ -73,6 +137,69 @@ pub fn spawn_ipc_socket(
                     let event = Event::new(EventType::IpcConfig(ipc_config), window_id);
                     let _ = event_proxy.send_event(event);
                 },
+                SocketMessage::PreviewTheme(preview) => {
+                    let window_id = preview
+                        .window_id
+                        .and_then(|id| u64::try_from(id).ok())
+                        .map(WindowId::from);
+                    let event = Event::new(EventType::IpcPreviewTheme(preview), window_id);
+                    let _ = event_proxy.send_event(event);
+                },
+                SocketMessage::Subscribe(options) => {
+                    if let Err(err) = subscribe(stream.into_inner().into_inner().1, options) {
+                        warn!("Unable to add IPC subscriber: {err}");
+                    }
+                },
+                SocketMessage::Get(get) => {
+                    let stream = stream.into_inner().into_inner().1;
+                    if let Err(err) = stream.set_write_timeout(Some(REPLY_WRITE_TIMEOUT)) {
+                        warn!("Unable to reply to IPC query: {err}");
+                        continue;
+                    }
+
+                    let window_id =
+                        get.window_id.and_then(|id| u64::try_from(id).ok()).map(WindowId::from);
+                    let event = Event::new(EventType::IpcGet(Arc::new(stream), get), window_id);
+                    let _ = event_proxy.send_event(event);
+                },
+                SocketMessage::Inject(inject) => {
+                    let window_id = inject
+                        .window_id
+                        .and_then(|id| u64::try_from(id).ok())
+                        .map(WindowId::from);
+                    let event = Event::new(EventType::IpcInject(inject.text), window_id);
+                    let _ = event_proxy.send_event(event);
+                },
+                SocketMessage::Detach(detach) => {
+                    let window_id = detach
+                        .window_id
+                        .and_then(|id| u64::try_from(id).ok())
+                        .map(WindowId::from);
+                    let event = Event::new(EventType::IpcDetach(detach), window_id);
+                    let _ = event_proxy.send_event(event);
+                },
+                SocketMessage::Attach(attach) => {
+                    let fd = match fd {
+                        Some(fd) => fd,
+                        None => {
+                            warn!("Detached window is missing its PTY");
+                            continue;
+                        },
+                    };
+
+                    let state = match serde_json::from_reader(&mut stream) {
+                        Ok(state) => state,
+                        Err(err) => {
+                            warn!("Failed to read detached terminal content: {}", err);
+                            continue;
+                        },
+                    };
+
+                    let source = stream.into_inner().into_inner().1;
+                    let terminal = DetachedTerminal { fd, pid: attach.pid, state, source };
+                    let event = Event::new(EventType::IpcAttach(Arc::new(terminal)), None);
+                    let _ = event_proxy.send_event(event);
+                },
             }
         }
     });
@@ -80,17 +207,584 @@ pub fn spawn_ipc_socket(
     Ok(socket_path)
 }
 
+/// Path of the socket serving performance metrics, next to the IPC socket.
+pub fn metrics_socket_path() -> PathBuf {
+    let mut path = socket_dir();
+    path.push(format!("{}-{}.metrics", socket_prefix(), process::id()));
+    path
+}
+
 /// Send a message to the active Alacritty socket.
 pub fn send_message(socket: Option<PathBuf>, message: SocketMessage) -> IoResult<()> {
     let mut socket = find_socket(socket)?;
 
+    let has_reply = matches!(message, SocketMessage::Subscribe(_) | SocketMessage::Get(_));
+
     let message = serde_json::to_string(&message)?;
     socket.write_all(message[..].as_bytes())?;
     let _ = socket.flush();
 
+    // Forward the reply until Alacritty closes the socket.
+    if has_reply {
+        socket.write_all(b"\n")?;
+        io::copy(&mut socket, &mut io::stdout())?;
+    }
+
+    Ok(())
+}
+
+/// Path of the IPC socket created by this instance.
+pub fn socket_path() -> Option<&'static Path> {
+    SOCKET_PATH.get().map(PathBuf::as_path)
+}
+
+/// Ask the instance being restarted to move all its windows to `target` and quit.
+///
+/// The `stream` must be connected to the IPC socket of the restarted instance.
+pub fn request_restart(mut stream: UnixStream, target: &Path) -> IoResult<()> {
+    let detach = IpcDetach { target: target.to_owned(), window_id: Some(-1), quit: true };
+    let message = serde_json::to_string(&SocketMessage::Detach(detach))?;
+    stream.write_all(message.as_bytes())?;
+    stream.flush()
+}
+
+/// Send a detaching terminal to another Alacritty instance.
+///
+/// The `stream` must be connected to the IPC socket of the receiving instance. This blocks until
+/// the receiving instance has created a window for the terminal, so it should not be called on
+/// the main thread.
+pub fn send_terminal(stream: UnixStream, terminal: DetachingTerminal) -> IoResult<()> {
+    let message = SocketMessage::Attach(IpcAttach { pid: terminal.pid });
+    let message = format!("{}\n", serde_json::to_string(&message)?);
+
+    let sent = send_fd(&stream, message.as_bytes(), terminal.fd.as_fd())?;
+
+    // Copy the terminal content, to avoid blocking the window while it is serialized.
+    let state = terminal.terminal.lock().state();
+
+    let mut writer = BufWriter::new(&stream);
+    writer.write_all(&message.as_bytes()[sent..])?;
+    serde_json::to_writer(&mut writer, &state)?;
+    writer.flush()?;
+    drop(writer);
+
+    // Wait for the receiving instance to take over the terminal.
+    stream.shutdown(Shutdown::Write)?;
+    stream.set_read_timeout(Some(ATTACH_TIMEOUT))?;
+    match (&stream).read(&mut [0])? {
+        0 => Err(IoError::other("terminal was rejected by the receiving instance")),
+        _ => Ok(()),
+    }
+}
+
+/// Check if any IPC client is subscribed to the terminal output.
+pub fn has_output_subscribers() -> bool {
+    HAS_OUTPUT_SUBSCRIBERS.load(Ordering::Relaxed)
+}
+
+/// Check if any IPC client is subscribed to the drawn frames.
+pub fn has_frame_subscribers() -> bool {
+    HAS_FRAME_SUBSCRIBERS.load(Ordering::Relaxed)
+}
+
+/// Send a finished line of terminal output to all its subscribers.
+///
+/// This is called with the terminal locked, so it never blocks. Subscribers which can't keep up
+/// with the output are disconnected.
+pub fn send_output(window_id: WindowId, line: &str) {
+    let mut subscribers = SUBSCRIBERS.lock().unwrap();
+    subscribers.retain(|subscriber| {
+        !subscriber.wants(SubscribeStream::Output, window_id)
+            || subscriber.sender.try_send(line.to_owned()).is_ok()
+    });
+    update_output_subscribers(&subscribers);
+}
+
+/// Send a window event to all its subscribers.
+///
+/// Subscribers which can't keep up with the events are disconnected.
+pub fn send_event(window_id: WindowId, event: IpcEvent) {
+    let message = IpcEventMessage { window_id: window_id.into(), event };
+    broadcast(SubscribeStream::Events, window_id, &message);
+}
+
+/// Send the updated cells of a drawn frame to all its subscribers.
+///
+/// Frames are converted and sent on a separate thread. When that thread falls behind, the frame
+/// is dropped and [`take_dropped_frame`] requests a full frame instead. Subscribers which can't
+/// keep up with the frames are disconnected.
+pub fn send_frame(window_id: WindowId, frame: IpcFrame) {
+    let sender = FRAME_SENDER.get_or_init(|| {
+        let (sender, receiver) = mpsc::sync_channel::<(WindowId, IpcFrame)>(FRAME_QUEUE_SIZE);
+        thread::spawn_named("IPC frames", move || {
+            for (window_id, frame) in receiver {
+                let message = IpcFrameMessage::new(window_id, frame);
+                broadcast(SubscribeStream::Frames, window_id, &message);
+            }
+        });
+        sender
+    });
+
+    if sender.try_send((window_id, frame)).is_err() {
+        DROPPED_FRAMES.lock().unwrap().push(window_id);
+    }
+}
+
+/// Check whether a frame of the window was dropped, requiring the next frame to be complete.
+pub fn take_dropped_frame(window_id: WindowId) -> bool {
+    let mut dropped_frames = DROPPED_FRAMES.lock().unwrap();
+    let len = dropped_frames.len();
+    dropped_frames.retain(|dropped| *dropped != window_id);
+    dropped_frames.len() != len
+}
+
+/// Reply to an IPC query with the state of a window.
+pub fn send_reply(mut stream: &UnixStream, state: &IpcWindowState) {
+    let reply = match serde_json::to_string(state) {
+        Ok(reply) => reply,
+        Err(err) => {
+            warn!("Unable to serialize IPC reply: {err}");
+            return;
+        },
+    };
+
+    if let Err(err) = writeln!(stream, "{reply}") {
+        warn!("Unable to reply to IPC query: {err}");
+    }
+}
+
+/// Write data to a socket, passing a file descriptor alongside it.
+///
+/// Returns the number of bytes written.
+fn send_fd(stream: &UnixStream, data: &[u8], fd: BorrowedFd<'_>) -> IoResult<usize> {
+    let mut iov = libc::iovec { iov_base: data.as_ptr() as *mut _, iov_len: data.len() };
+
+    // The control buffer uses `u64` to satisfy the alignment of `cmsghdr`.
+    let mut control = [0u64; 4];
+    // SAFETY: `CMSG_SPACE` only calculates a size.
+    let control_len = unsafe { libc::CMSG_SPACE(mem::size_of::<RawFd>() as u32) } as usize;
+    assert!(control_len <= mem::size_of_val(&control));
+
+    // SAFETY: `msghdr` only contains integers and nullable pointers.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = control_len as _;
+
+    // SAFETY: The control buffer is aligned and large enough for one header with a single file
+    // descriptor, as asserted above, so the first header is not null and its data is writable.
+    unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(mem::size_of::<RawFd>() as u32) as _;
+        ptr::write_unaligned(libc::CMSG_DATA(cmsg).cast::<RawFd>(), fd.as_raw_fd());
+    }
+
+    // SAFETY: The message only references `iov`, `data` and `control`, which outlive the call,
+    // and the kernel does not write through the data pointer when sending.
+    let sent = unsafe { libc::sendmsg(stream.as_raw_fd(), &msg, 0) };
+    if sent < 0 {
+        return Err(IoError::last_os_error());
+    }
+
+    Ok(sent as usize)
+}
+
+/// Read data from a socket, receiving the first file descriptor passed alongside it.
+///
+/// Returns the number of bytes read.
+fn recv_fd(stream: &UnixStream, buf: &mut [u8]) -> IoResult<(usize, Option<OwnedFd>)> {
+    let mut iov = libc::iovec { iov_base: buf.as_mut_ptr().cast(), iov_len: buf.len() };
+
+    // The control buffer uses `u64` to satisfy the alignment of `cmsghdr`.
+    let mut control = [0u64; 4];
+
+    // SAFETY: `msghdr` only contains integers and nullable pointers.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = mem::size_of_val(&control) as _;
+
+    #[cfg(not(target_os = "macos"))]
+    let flags = libc::MSG_CMSG_CLOEXEC;
+    #[cfg(target_os = "macos")]
+    let flags = 0;
+
+    // SAFETY: The message references `iov`, `buf` and `control`, which outlive the call, with
+    // lengths matching their buffers.
+    let read = unsafe { libc::recvmsg(stream.as_raw_fd(), &mut msg, flags) };
+    if read < 0 {
+        return Err(IoError::last_os_error());
+    }
+
+    // Take ownership of all received file descriptors, so unused ones are closed.
+    let mut fds = Vec::new();
+    // SAFETY: The kernel filled the control buffer with complete headers, up to the updated
+    // `msg_controllen`, and `CMSG_NXTHDR` returns null after the last one. File descriptors
+    // received with `SCM_RIGHTS` are new and owned by this process.
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+        while !cmsg.is_null() {
+            if (*cmsg).cmsg_level == libc::SOL_SOCKET && (*cmsg).cmsg_type == libc::SCM_RIGHTS {
+                let data = libc::CMSG_DATA(cmsg).cast::<RawFd>();
+                let len = (*cmsg).cmsg_len as usize - libc::CMSG_LEN(0) as usize;
+                for i in 0..len / mem::size_of::<RawFd>() {
+                    fds.push(OwnedFd::from_raw_fd(ptr::read_unaligned(data.add(i))));
+                }
+            }
+            cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+        }
+    }
+
+    // Prevent leaking the file descriptors into child processes.
+    #[cfg(target_os = "macos")]
+    for fd in &fds {
+        // SAFETY: The file descriptor is owned and open.
+        unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) };
+    }
+
+    Ok((read as usize, fds.into_iter().next()))
+}
+
+/// Send a JSON message to all subscribers of a stream, without blocking.
+fn broadcast<M: Serialize>(stream: SubscribeStream, window_id: WindowId, message: &M) {
+    let message = match serde_json::to_string(message) {
+        Ok(message) => message,
+        Err(err) => {
+            warn!("Unable to serialize IPC message: {err}");
+            return;
+        },
+    };
+
+    let mut subscribers = SUBSCRIBERS.lock().unwrap();
+    subscribers.retain(|subscriber| {
+        !subscriber.wants(stream, window_id) || subscriber.sender.try_send(message.clone()).is_ok()
+    });
+    update_output_subscribers(&subscribers);
+}
+
+/// Window events available to IPC subscribers.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum IpcEvent {
+    WindowCreated,
+    WindowClosed,
+    TitleChanged { title: String },
+    Bell,
+    CommandFinished,
+}
+
+/// Window state reported to IPC queries.
+#[derive(Serialize, Debug)]
+pub struct IpcWindowState {
+    window_id: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_directory: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<IpcDimensions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<toml::Table>,
+}
+
+impl IpcWindowState {
+    pub fn new(window_id: WindowId) -> Self {
+        Self {
+            window_id: window_id.into(),
+            working_directory: None,
+            dimensions: None,
+            title: None,
+            config: None,
+        }
+    }
+}
+
+/// Terminal size in cells and pixels.
+#[derive(Serialize, Debug)]
+pub struct IpcDimensions {
+    columns: usize,
+    lines: usize,
+    width: u32,
+    height: u32,
+    cell_width: u32,
+    cell_height: u32,
+}
+
+impl From<&SizeInfo> for IpcDimensions {
+    fn from(size_info: &SizeInfo) -> Self {
+        Self {
+            columns: size_info.columns(),
+            lines: size_info.screen_lines(),
+            width: size_info.width() as u32,
+            height: size_info.height() as u32,
+            cell_width: size_info.cell_width() as u32,
+            cell_height: size_info.cell_height() as u32,
+        }
+    }
+}
+
+/// Terminal which is being moved to another Alacritty instance.
+pub struct DetachingTerminal {
+    /// PTY of the terminal's shell.
+    pub fd: OwnedFd,
+
+    /// Process ID of the terminal's shell.
+    pub pid: u32,
+
+    /// Terminal content which is sent to the receiving instance.
+    pub terminal: Arc<FairMutex<Term<EventProxy>>>,
+}
+
+/// Terminal received from another Alacritty instance.
+#[derive(Debug)]
+pub struct DetachedTerminal {
+    /// PTY of the terminal's shell.
+    pub fd: OwnedFd,
+
+    /// Process ID of the terminal's shell.
+    pub pid: u32,
+
+    /// Grid content and scrollback history.
+    pub state: TermState,
+
+    /// Connection to the sending instance.
+    source: UnixStream,
+}
+
+impl DetachedTerminal {
+    /// Notify the sending instance that the terminal was taken over.
+    ///
+    /// The sending instance keeps the terminal if the connection is closed without this.
+    pub fn acknowledge(&self) {
+        if let Err(err) = (&self.source).write_all(b"\n") {
+            warn!("Unable to acknowledge attached terminal: {err}");
+        }
+    }
+}
+
+/// Window event with its origin.
+#[derive(Serialize)]
+struct IpcEventMessage {
+    window_id: u64,
+    #[serde(flatten)]
+    event: IpcEvent,
+}
+
+/// Drawn frame, converted for IPC subscribers on the frame streaming thread.
+pub struct IpcFrame {
+    /// Damaged lines, `None` when the entire viewport was redrawn.
+    damage: Option<Vec<LineDamageBounds>>,
+    columns: usize,
+    lines: usize,
+    background: Rgb,
+    cursor: RenderableCursor,
+    cells: Vec<RenderableCell>,
+}
+
+impl IpcFrame {
+    pub fn new(
+        damage: Option<Vec<LineDamageBounds>>,
+        size_info: &SizeInfo,
+        background: Rgb,
+        cursor: RenderableCursor,
+        cells: Vec<RenderableCell>,
+    ) -> Self {
+        let (columns, lines) = (size_info.columns(), size_info.screen_lines());
+        Self { damage, columns, lines, background, cursor, cells }
+    }
+}
+
+/// Damaged columns of a viewport line.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+struct IpcDamage {
+    line: usize,
+    left: usize,
+    right: usize,
+}
+
+/// Rendered content of a single cell.
+#[derive(Serialize, Debug, PartialEq)]
+struct IpcCell {
+    line: usize,
+    column: usize,
+    /// Cell character, followed by its zero-width characters.
+    text: String,
+    fg: String,
+    /// Cell background, unless it uses the default background.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bg: Option<String>,
+}
+
+impl IpcCell {
+    fn new(cell: &RenderableCell) -> Self {
+        let mut text = String::from(cell.character);
+        let zerowidth = cell.extra.as_ref().and_then(|extra| extra.zerowidth.as_ref());
+        text.extend(zerowidth.into_iter().flatten());
+
+        Self {
+            line: cell.point.line,
+            column: cell.point.column.0,
+            text,
+            fg: cell.fg.to_string(),
+            bg: (cell.bg_alpha > 0.).then(|| cell.bg.to_string()),
+        }
+    }
+}
+
+/// Visible terminal cursor.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+struct IpcCursor {
+    line: usize,
+    column: usize,
+    shape: &'static str,
+    color: String,
+}
+
+impl IpcCursor {
+    fn new(cursor: &RenderableCursor) -> Option<Self> {
+        let shape = match cursor.shape() {
+            CursorShape::Block => "block",
+            CursorShape::Underline => "underline",
+            CursorShape::Beam => "beam",
+            CursorShape::HollowBlock => "hollow_block",
+            CursorShape::Hidden => return None,
+        };
+
+        let point = cursor.point();
+        let color = cursor.color().to_string();
+        Some(Self { line: point.line, column: point.column.0, shape, color })
+    }
+}
+
+/// Cells updated by a drawn frame.
+#[derive(Serialize, Debug)]
+struct IpcFrameMessage {
+    window_id: u64,
+    /// Whether the entire viewport was redrawn.
+    full: bool,
+    columns: usize,
+    lines: usize,
+    /// Default background color.
+    background: String,
+    /// Cursor drawn on top of the cells, unless it is hidden.
+    cursor: Option<IpcCursor>,
+    /// Damaged regions, which should be cleared before drawing the cells.
+    damage: Vec<IpcDamage>,
+    /// Visible cells inside the damaged regions.
+    cells: Vec<IpcCell>,
+}
+
+impl IpcFrameMessage {
+    fn new(window_id: WindowId, frame: IpcFrame) -> Self {
+        let full = frame.damage.is_none();
+        let damage: Vec<_> = match frame.damage {
+            Some(damage) => damage
+                .iter()
+                .filter(|line| line.is_damaged())
+                .map(|line| IpcDamage { line: line.line, left: line.left, right: line.right })
+                .collect(),
+            None => {
+                let right = frame.columns.saturating_sub(1);
+                (0..frame.lines).map(|line| IpcDamage { line, left: 0, right }).collect()
+            },
+        };
+
+        // Index damaged columns by line, to filter cells in a single pass.
+        let mut damaged_columns = vec![None; frame.lines];
+        for damage in &damage {
+            if let Some(columns) = damaged_columns.get_mut(damage.line) {
+                *columns = Some(damage.left..=damage.right);
+            }
+        }
+
+        let cells = frame
+            .cells
+            .iter()
+            .filter(|cell| {
+                let columns = damaged_columns.get(cell.point.line).and_then(Option::as_ref);
+                columns.is_some_and(|columns| columns.contains(&cell.point.column.0))
+            })
+            .map(IpcCell::new)
+            .collect();
+
+        Self {
+            window_id: window_id.into(),
+            full,
+            columns: frame.columns,
+            lines: frame.lines,
+            background: frame.background.to_string(),
+            cursor: IpcCursor::new(&frame.cursor),
+            damage,
+            cells,
+        }
+    }
+}
+
+/// IPC client receiving terminal content.
+struct Subscriber {
+    id: u64,
+    window_id: Option<WindowId>,
+    stream: SubscribeStream,
+    sender: SyncSender<String>,
+}
+
+impl Subscriber {
+    /// Check if the subscriber is interested in a stream of a window.
+    fn wants(&self, stream: SubscribeStream, window_id: WindowId) -> bool {
+        self.stream == stream && self.window_id.map_or(true, |id| id == window_id)
+    }
+}
+
+/// Register a new subscriber for terminal content.
+fn subscribe(mut stream: UnixStream, options: IpcSubscribe) -> IoResult<()> {
+    stream.set_write_timeout(Some(SUBSCRIBER_WRITE_TIMEOUT))?;
+
+    let id = NEXT_SUBSCRIBER_ID.fetch_add(1, Ordering::Relaxed);
+    let (sender, receiver) = mpsc::sync_channel::<String>(SUBSCRIBER_QUEUE_SIZE);
+    thread::spawn_named("ipc subscriber", move || {
+        // Stop once the subscriber was disconnected or the client went away.
+        loop {
+            match receiver.recv_timeout(SUBSCRIBER_POLL_INTERVAL) {
+                Ok(line) if writeln!(stream, "{line}").is_ok() => (),
+                Err(RecvTimeoutError::Timeout) if is_connected(&stream) => (),
+                _ => break,
+            }
+        }
+
+        let mut subscribers = SUBSCRIBERS.lock().unwrap();
+        subscribers.retain(|subscriber| subscriber.id != id);
+        update_output_subscribers(&subscribers);
+    });
+
+    let window_id = options.window_id.and_then(|id| u64::try_from(id).ok()).map(WindowId::from);
+    let mut subscribers = SUBSCRIBERS.lock().unwrap();
+    subscribers.push(Subscriber { id, window_id, stream: options.stream, sender });
+    update_output_subscribers(&subscribers);
+
     Ok(())
 }
 
+/// Check if the client of a subscriber has not closed its end of the socket.
+fn is_connected(stream: &UnixStream) -> bool {
+    let mut buf = [0u8; 1];
+    let flags = libc::MSG_PEEK | libc::MSG_DONTWAIT;
+    // SAFETY: The buffer is valid for writes of one byte.
+    let read = unsafe { libc::recv(stream.as_raw_fd(), buf.as_mut_ptr().cast(), 1, flags) };
+
+    read > 0 || (read < 0 && IoError::last_os_error().kind() == ErrorKind::WouldBlock)
+}
+
+/// Update the cached state of terminal output and frame subscriptions.
+fn update_output_subscribers(subscribers: &[Subscriber]) {
+    let has_stream = |stream| subscribers.iter().any(|subscriber| subscriber.stream == stream);
+    HAS_OUTPUT_SUBSCRIBERS.store(has_stream(SubscribeStream::Output), Ordering::Relaxed);
+    HAS_FRAME_SUBSCRIBERS.store(has_stream(SubscribeStream::Frames), Ordering::Relaxed);
+}
+
 /// Directory for the IPC socket file.
 #[cfg(not(target_os = "macos"))]
 fn socket_dir() -> PathBuf {
@@ -171,3 +865,90 @@ fn socket_prefix() -> String {
 fn socket_prefix() -> String {
     String::from("Alacritty")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::num::NonZeroU32;
+
+    use alacritty_terminal::index::{Column, Point};
+    use alacritty_terminal::term::cell::Flags;
+
+    #[test]
+    fn fd_passing() {
+        let (sender, receiver) = UnixStream::pair().unwrap();
+        let (mut file_writer, file_reader) = UnixStream::pair().unwrap();
+
+        assert_eq!(send_fd(&sender, b"message", file_reader.as_fd()).unwrap(), 7);
+        drop(file_reader);
+
+        let mut buf = [0; 1];
+        let (read, fd) = recv_fd(&receiver, &mut buf).unwrap();
+        assert_eq!((read, &buf), (1, b"m"));
+
+        // The remaining data is received without a file descriptor.
+        let mut buf = [0; 16];
+        let (read, no_fd) = recv_fd(&receiver, &mut buf).unwrap();
+        assert_eq!(&buf[..read], b"essage");
+        assert!(no_fd.is_none());
+
+        // The received file descriptor refers to the original socket.
+        file_writer.write_all(b"passed").unwrap();
+        let mut passed = UnixStream::from(fd.unwrap());
+        let mut buf = [0; 6];
+        passed.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"passed");
+    }
+
+    #[test]
+    fn subscriber_disconnect() {
+        let (stream, client) = UnixStream::pair().unwrap();
+        assert!(is_connected(&stream));
+
+        drop(client);
+        assert!(!is_connected(&stream));
+    }
+
+    #[test]
+    fn frame_message() {
+        let cell = |line, column, character| RenderableCell {
+            character,
+            point: Point::new(line, Column(column)),
+            fg: Rgb::new(255, 255, 255),
+            bg: Rgb::new(0, 0, 0),
+            bg_alpha: 0.,
+            underline: Rgb::default(),
+            flags: Flags::empty(),
+            extra: None,
+        };
+
+        let point = Point::new(0, Column(1));
+        let width = NonZeroU32::new(1).unwrap();
+        let frame = IpcFrame {
+            damage: Some(vec![LineDamageBounds::new(0, 1, 1), LineDamageBounds::undamaged(1, 3)]),
+            columns: 3,
+            lines: 2,
+            background: Rgb::default(),
+            cursor: RenderableCursor::new(point, CursorShape::Beam, Rgb::default(), width),
+            cells: vec![cell(0, 0, 'a'), cell(0, 1, 'b'), cell(1, 1, 'c')],
+        };
+
+        let message = IpcFrameMessage::new(WindowId::from(1), frame);
+        assert!(!message.full);
+        assert_eq!(message.damage, [IpcDamage { line: 0, left: 1, right: 1 }]);
+        assert_eq!(message.cells, [IpcCell {
+            line: 0,
+            column: 1,
+            text: String::from("b"),
+            fg: String::from("#ffffff"),
+            bg: None,
+        }]);
+        assert_eq!(message.cursor, Some(IpcCursor {
+            line: 0,
+            column: 1,
+            shape: "beam",
+            color: String::from("#000000"),
+        }));
+    }
+}
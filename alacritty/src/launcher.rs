@@ -0,0 +1,231 @@
+//! Validation of URLs and paths before they are opened by external programs.
+
+use std::borrow::Cow;
+use std::fmt::{self, Display, Formatter};
+
+#[cfg(windows)]
+use alacritty_terminal::tty::wsl;
+
+use crate::config::ui_config::{Opener, Program};
+
+/// Reason for refusing to open a target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpenError {
+    /// Target contains control characters.
+    ControlCharacter,
+
+    /// Target could be interpreted as an option by the opener.
+    OptionLike,
+
+    /// Target is neither a URI nor an absolute path.
+    MissingScheme,
+
+    /// URI scheme is not in the list of allowed schemes.
+    ForbiddenScheme(String),
+}
+
+impl Display for OpenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ControlCharacter => f.write_str("target contains control characters"),
+            Self::OptionLike => f.write_str("target starts with a dash"),
+            Self::MissingScheme => f.write_str("target is neither a URI nor an absolute path"),
+            Self::ForbiddenScheme(scheme) => write!(f, "scheme \"{scheme}\" is not allowed"),
+        }
+    }
+}
+
+/// Find the program which should open a target.
+///
+/// The returned program must be launched directly with the target as its last argument, never
+/// through a shell.
+pub fn program_for<'a>(opener: &'a Opener, target: &str) -> Result<&'a Program, OpenError> {
+    validate_argument(target)?;
+
+    let scheme = scheme(target).ok_or(OpenError::MissingScheme)?;
+    if !opener.schemes.iter().any(|allowed| allowed.eq_ignore_ascii_case(&scheme)) {
+        return Err(OpenError::ForbiddenScheme(scheme));
+    }
+
+    // Schemes are case-insensitive, so the configured ones might not be lowercase.
+    let mut programs = opener.programs.iter();
+    let program = programs.find(|(configured, _)| configured.eq_ignore_ascii_case(&scheme));
+    Ok(program.map_or(&opener.program, |(_, program)| program))
+}
+
+/// Check if text can be safely passed to an external program as its last argument.
+pub fn validate_argument(text: &str) -> Result<(), OpenError> {
+    if text.chars().any(char::is_control) {
+        return Err(OpenError::ControlCharacter);
+    }
+
+    if text.starts_with('-') {
+        return Err(OpenError::OptionLike);
+    }
+
+    Ok(())
+}
+
+/// Path of a program, with a leading `~` expanded to the user's home directory.
+pub fn program_path(program: &Program) -> Cow<'_, str> {
+    let path = program.program();
+    let home = || home::home_dir().and_then(|home| home.to_str().map(str::to_owned));
+
+    match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => match home() {
+            Some(home) => Cow::Owned(home + rest),
+            None => Cow::Borrowed(path),
+        },
+        _ => Cow::Borrowed(path),
+    }
+}
+
+/// Check if opening a hyperlink should be confirmed by the user first.
+///
+/// Since the target of escape sequence hyperlinks is not visible, this is required for all
+/// non-web targets and files which don't appear to be located on this machine.
+pub fn needs_confirmation(uri: &str) -> bool {
+    match scheme(uri).as_deref() {
+        Some("http" | "https") => false,
+        Some("file") => !is_local_file(uri),
+        _ => true,
+    }
+}
+
+/// Check if a `file://` URI references the local machine.
+fn is_local_file(uri: &str) -> bool {
+    let host = match uri.get(..7) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("file://") => {
+            uri[7..].split('/').next().unwrap_or_default()
+        },
+        // Paths and URIs without authority are always local.
+        _ => return true,
+    };
+
+    host.is_empty()
+        || host.eq_ignore_ascii_case("localhost")
+        || hostname().is_some_and(|hostname| host.eq_ignore_ascii_case(&hostname))
+}
+
+/// Hostname of the local machine.
+#[cfg(unix)]
+fn hostname() -> Option<String> {
+    let mut buf = [0u8; 256];
+    // SAFETY: The buffer is valid for writes of its entire length.
+    let result = unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) };
+    if result != 0 {
+        return None;
+    }
+
+    let len = buf.iter().position(|byte| *byte == 0).unwrap_or(buf.len());
+    String::from_utf8(buf[..len].to_vec()).ok()
+}
+
+/// Hostname of the local machine.
+#[cfg(windows)]
+fn hostname() -> Option<String> {
+    std::env::var("COMPUTERNAME").ok()
+}
+
+/// Translate paths inside a WSL distribution, so they can be opened by Windows programs.
+#[cfg(windows)]
+pub fn wsl_target(target: String, distribution: Option<&str>) -> String {
+    let path = distribution.and_then(|distribution| wsl::to_windows_path(&target, distribution));
+    path.unwrap_or(target)
+}
+
+/// Lowercase URI scheme of a target, with absolute paths using the `file` scheme.
+fn scheme(target: &str) -> Option<String> {
+    // Paths starting with `~` are refused, since openers would receive them unexpanded.
+    if target.starts_with('/') {
+        return Some(String::from("file"));
+    }
+
+    // Single letters are drive letters on Windows.
+    let (scheme, _) = target.split_once(':')?;
+    if scheme.len() == 1 && cfg!(windows) {
+        return Some(String::from("file"));
+    }
+
+    let mut chars = scheme.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+
+    valid.then(|| scheme.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allowed_targets() {
+        let opener = Opener::default();
+
+        assert_eq!(program_for(&opener, "https://example.org"), Ok(&opener.program));
+        assert_eq!(program_for(&opener, "HTTP://example.org"), Ok(&opener.program));
+        assert_eq!(program_for(&opener, "mailto:user@example.org"), Ok(&opener.program));
+        assert_eq!(program_for(&opener, "/home/user/file"), Ok(&opener.program));
+    }
+
+    #[test]
+    fn refused_targets() {
+        let opener = Opener::default();
+
+        assert_eq!(program_for(&opener, "https://a.org/\x1b]"), Err(OpenError::ControlCharacter));
+        assert_eq!(program_for(&opener, "--help"), Err(OpenError::OptionLike));
+        assert_eq!(program_for(&opener, "relative/path"), Err(OpenError::MissingScheme));
+        assert_eq!(program_for(&opener, "~/file"), Err(OpenError::MissingScheme));
+        assert_eq!(program_for(&opener, "~user/file"), Err(OpenError::MissingScheme));
+        assert_eq!(program_for(&opener, "1x:foo"), Err(OpenError::MissingScheme));
+        assert_eq!(
+            program_for(&opener, "javascript:alert(1)"),
+            Err(OpenError::ForbiddenScheme(String::from("javascript")))
+        );
+    }
+
+    #[test]
+    fn command_arguments() {
+        assert_eq!(validate_argument("deadbeef"), Ok(()));
+        assert_eq!(validate_argument("--exec=sh"), Err(OpenError::OptionLike));
+        assert_eq!(validate_argument("a\nb"), Err(OpenError::ControlCharacter));
+    }
+
+    #[test]
+    fn home_program_path() {
+        let home = home::home_dir().unwrap();
+        let home = home.to_str().unwrap();
+
+        let program = Program::Just(String::from("~/bin/open"));
+        assert_eq!(program_path(&program), format!("{home}/bin/open"));
+
+        let program = Program::Just(String::from("~user/bin/open"));
+        assert_eq!(program_path(&program), "~user/bin/open");
+
+        let program = Program::Just(String::from("xdg-open"));
+        assert_eq!(program_path(&program), "xdg-open");
+    }
+
+    #[test]
+    fn hyperlink_confirmation() {
+        assert!(!needs_confirmation("https://example.org"));
+        assert!(!needs_confirmation("file:///etc/hosts"));
+        assert!(!needs_confirmation("file://localhost/etc/hosts"));
+        assert!(!needs_confirmation(&format!("file://{}/etc/hosts", hostname().unwrap())));
+
+        assert!(needs_confirmation("file://remote.example.org/etc/hosts"));
+        assert!(needs_confirmation("ssh:remote.example.org"));
+        assert!(needs_confirmation("mailto:user@example.org"));
+    }
+
+    #[test]
+    fn scheme_programs() {
+        let mut opener = Opener::default();
+        let mail = Program::Just(String::from("mail-client"));
+        opener.programs.insert(String::from("MailTo"), mail.clone());
+
+        assert_eq!(program_for(&opener, "mailto:user@example.org"), Ok(&mail));
+        assert_eq!(program_for(&opener, "MAILTO:user@example.org"), Ok(&mail));
+        assert_eq!(program_for(&opener, "https://example.org"), Ok(&opener.program));
+    }
+}
@@ -0,0 +1,147 @@
+//! Tracking of the keyboard's lock keys.
+
+use bitflags::bitflags;
+use winit::event::KeyEvent;
+use winit::keyboard::{Key, KeyCode, ModifiersState, NamedKey, PhysicalKey};
+
+bitflags! {
+    /// Active keyboard locks.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct LockKeys: u8 {
+        const CAPS_LOCK = 0b01;
+        const NUM_LOCK  = 0b10;
+    }
+}
+
+impl LockKeys {
+    /// Update the lock state based on a key press.
+    ///
+    /// Since winit doesn't expose the lock state, it is toggled by the lock keys and corrected
+    /// using the text produced by letter and keypad keys.
+    pub fn update(&mut self, key: &KeyEvent, mods: ModifiersState) {
+        let text = key.text.as_deref();
+        self.update_with(&key.logical_key, key.physical_key, text, key.repeat, mods);
+    }
+
+    fn update_with(
+        &mut self,
+        logical_key: &Key,
+        physical_key: PhysicalKey,
+        text: Option<&str>,
+        repeat: bool,
+        mods: ModifiersState,
+    ) {
+        match logical_key {
+            Key::Named(NamedKey::CapsLock) if !repeat => self.toggle(Self::CAPS_LOCK),
+            Key::Named(NamedKey::NumLock) if !repeat => self.toggle(Self::NUM_LOCK),
+            // Shift temporarily inverts the keypad and letter case on some platforms.
+            _ if mods.shift_key() => (),
+            logical_key if is_keypad_digit(physical_key) => {
+                self.set(Self::NUM_LOCK, matches!(logical_key, Key::Character(_)));
+            },
+            _ => {
+                let mut chars = text.unwrap_or_default().chars();
+                if let (Some(c), None) = (chars.next(), chars.next()) {
+                    if c.is_lowercase() || c.is_uppercase() {
+                        self.set(Self::CAPS_LOCK, c.is_uppercase());
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// Check if a key is a keypad digit, which changes its meaning with num lock.
+fn is_keypad_digit(key: PhysicalKey) -> bool {
+    matches!(
+        key,
+        PhysicalKey::Code(
+            KeyCode::Numpad0
+                | KeyCode::Numpad1
+                | KeyCode::Numpad2
+                | KeyCode::Numpad3
+                | KeyCode::Numpad4
+                | KeyCode::Numpad5
+                | KeyCode::Numpad6
+                | KeyCode::Numpad7
+                | KeyCode::Numpad8
+                | KeyCode::Numpad9
+                | KeyCode::NumpadDecimal
+        )
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Simulate pressing a key.
+    fn press(lock_keys: &mut LockKeys, key: Key, code: KeyCode, text: &str, mods: ModifiersState) {
+        let text = (!text.is_empty()).then_some(text);
+        lock_keys.update_with(&key, PhysicalKey::Code(code), text, false, mods);
+    }
+
+    #[test]
+    fn toggle_with_lock_keys() {
+        let mut lock_keys = LockKeys::empty();
+        let caps_lock = Key::Named(NamedKey::CapsLock);
+        let num_lock = Key::Named(NamedKey::NumLock);
+
+        press(&mut lock_keys, caps_lock.clone(), KeyCode::CapsLock, "", ModifiersState::empty());
+        assert_eq!(lock_keys, LockKeys::CAPS_LOCK);
+
+        press(&mut lock_keys, num_lock.clone(), KeyCode::NumLock, "", ModifiersState::empty());
+        assert_eq!(lock_keys, LockKeys::CAPS_LOCK | LockKeys::NUM_LOCK);
+
+        press(&mut lock_keys, caps_lock, KeyCode::CapsLock, "", ModifiersState::empty());
+        assert_eq!(lock_keys, LockKeys::NUM_LOCK);
+
+        // Holding a lock key must not toggle it repeatedly.
+        lock_keys.update_with(
+            &num_lock,
+            PhysicalKey::Code(KeyCode::NumLock),
+            None,
+            true,
+            ModifiersState::empty(),
+        );
+        assert_eq!(lock_keys, LockKeys::NUM_LOCK);
+    }
+
+    #[test]
+    fn caps_lock_from_letter_case() {
+        let mut lock_keys = LockKeys::empty();
+        let upper = Key::Character("A".into());
+        let lower = Key::Character("a".into());
+
+        press(&mut lock_keys, upper, KeyCode::KeyA, "A", ModifiersState::empty());
+        assert_eq!(lock_keys, LockKeys::CAPS_LOCK);
+
+        // Shift inverts the case, so it can't be used to infer the lock state.
+        press(&mut lock_keys, lower.clone(), KeyCode::KeyA, "a", ModifiersState::SHIFT);
+        assert_eq!(lock_keys, LockKeys::CAPS_LOCK);
+
+        // Text without case doesn't change the lock state.
+        press(&mut lock_keys, Key::Character("1".into()), KeyCode::Digit1, "1", Default::default());
+        assert_eq!(lock_keys, LockKeys::CAPS_LOCK);
+
+        press(&mut lock_keys, lower, KeyCode::KeyA, "a", ModifiersState::empty());
+        assert_eq!(lock_keys, LockKeys::empty());
+    }
+
+    #[test]
+    fn num_lock_from_keypad() {
+        let mut lock_keys = LockKeys::empty();
+        let digit = Key::Character("1".into());
+        let end = Key::Named(NamedKey::End);
+
+        press(&mut lock_keys, digit, KeyCode::Numpad1, "1", ModifiersState::empty());
+        assert_eq!(lock_keys, LockKeys::NUM_LOCK);
+
+        // Shift temporarily inverts the keypad's meaning.
+        press(&mut lock_keys, end.clone(), KeyCode::Numpad1, "", ModifiersState::SHIFT);
+        assert_eq!(lock_keys, LockKeys::NUM_LOCK);
+
+        press(&mut lock_keys, end, KeyCode::Numpad1, "", ModifiersState::empty());
+        assert_eq!(lock_keys, LockKeys::empty());
+    }
+}
@@ -15,6 +15,10 @@ compile_error!(r#"at least one of the "x11"/"wayland" features must be enabled"#
 use std::error::Error;
 use std::fmt::Write as _;
 use std::io::{self, Write};
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
 use std::path::PathBuf;
 use std::{env, fs};
 
@@ -27,25 +31,38 @@ use winit::raw_window_handle::{HasDisplayHandle, RawDisplayHandle};
 
 use alacritty_terminal::tty;
 
+mod accessibility;
+#[cfg(feature = "audio")]
+mod audio;
 mod cli;
 mod clipboard;
+mod completions;
 mod config;
 mod daemon;
 mod display;
 mod event;
+mod event_trace;
+mod hooks;
 mod input;
 #[cfg(unix)]
 mod ipc;
+mod launcher;
+mod lock_keys;
 mod logging;
 #[cfg(target_os = "macos")]
 mod macos;
 mod message_bar;
+mod metrics;
 mod migrate;
+mod notification;
 #[cfg(windows)]
 mod panic;
 mod renderer;
 mod scheduler;
+#[cfg(unix)]
+mod shell_integration;
 mod string;
+mod terminfo;
 mod window_context;
 
 mod gl {
@@ -54,9 +71,7 @@ mod gl {
 }
 
 #[cfg(unix)]
-use crate::cli::MessageOptions;
-#[cfg(not(any(target_os = "macos", windows)))]
-use crate::cli::SocketMessage;
+use crate::cli::{MessageOptions, SocketMessage};
 use crate::cli::{Options, Subcommands};
 use crate::config::monitor::ConfigMonitor;
 use crate::config::UiConfig;
@@ -83,6 +98,8 @@ fn main() -> Result<(), Box<dyn Error>> {
         #[cfg(unix)]
         Some(Subcommands::Msg(options)) => msg(options)?,
         Some(Subcommands::Migrate(options)) => migrate::migrate(options),
+        Some(Subcommands::Terminfo(options)) => terminfo::terminfo(options)?,
+        Some(Subcommands::Completions(options)) => completions::completions(options),
         None => alacritty(options)?,
     }
 
@@ -98,6 +115,12 @@ fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
         window_options.activation_token =
             env::var("XDG_ACTIVATION_TOKEN").or_else(|_| env::var("DESKTOP_STARTUP_ID")).ok();
     }
+    if let SocketMessage::PreviewTheme(preview) = &mut options.message {
+        preview.load_colors()?;
+    }
+    if let SocketMessage::Detach(detach) = &mut options.message {
+        detach.target = fs::canonicalize(&detach.target)?;
+    }
     ipc::send_message(options.socket, options.message).map_err(|err| err.into())
 }
 
@@ -107,6 +130,11 @@ fn msg(mut options: MessageOptions) -> Result<(), Box<dyn Error>> {
 struct TemporaryFiles {
     #[cfg(unix)]
     socket_path: Option<PathBuf>,
+    /// Inode of the IPC socket, to avoid removing a socket taken over by a restarted instance.
+    #[cfg(unix)]
+    socket_inode: Option<u64>,
+    #[cfg(unix)]
+    metrics_socket_path: Option<PathBuf>,
     log_file: Option<PathBuf>,
 }
 
@@ -115,7 +143,15 @@ impl Drop for TemporaryFiles {
         // Clean up the IPC socket file.
         #[cfg(unix)]
         if let Some(socket_path) = &self.socket_path {
-            let _ = fs::remove_file(socket_path);
+            if fs::metadata(socket_path).ok().map(|metadata| metadata.ino()) == self.socket_inode {
+                let _ = fs::remove_file(socket_path);
+            }
+        }
+
+        // Clean up the metrics socket file.
+        #[cfg(unix)]
+        if let Some(metrics_socket_path) = &self.metrics_socket_path {
+            let _ = fs::remove_file(metrics_socket_path);
         }
 
         // Clean up logfile.
@@ -172,6 +208,15 @@ fn alacritty(mut options: Options) -> Result<(), Box<dyn Error>> {
         env::set_var(key, value);
     }
 
+    // Select the GPU before any threads are spawned.
+    renderer::platform::setup_gpu_env(config.renderer.gpu_preference.as_ref());
+
+    // Check if the bundled terminfo is available for child processes.
+    #[cfg(not(windows))]
+    if !config.env.contains_key("TERM") {
+        terminfo::check_installed();
+    }
+
     // Switch to home directory.
     #[cfg(target_os = "macos")]
     env::set_current_dir(home::home_dir().unwrap()).unwrap();
@@ -180,12 +225,25 @@ fn alacritty(mut options: Options) -> Result<(), Box<dyn Error>> {
     #[cfg(target_os = "macos")]
     locale::set_locale_environment();
 
+    // Connect to the restarted instance, before taking over its socket path.
+    #[cfg(unix)]
+    let restart_stream = match &options.restart_from {
+        Some(restart_from) => {
+            let stream = UnixStream::connect(restart_from)?;
+            if options.socket.as_ref() == Some(restart_from) {
+                fs::remove_file(restart_from)?;
+            }
+            Some(stream)
+        },
+        None => None,
+    };
+
     // Create the IPC socket listener.
     #[cfg(unix)]
     let socket_path = if config.ipc_socket() {
         match ipc::spawn_ipc_socket(&options, window_event_loop.create_proxy()) {
             Ok(path) => Some(path),
-            Err(err) if options.daemon => return Err(err.into()),
+            Err(err) if options.daemon || restart_stream.is_some() => return Err(err.into()),
             Err(err) => {
                 log::warn!("Unable to create socket: {:?}", err);
                 None
@@ -195,14 +253,54 @@ fn alacritty(mut options: Options) -> Result<(), Box<dyn Error>> {
         None
     };
 
+    // Serve performance metrics.
+    #[cfg(unix)]
+    let metrics_socket_path = if config.debug.metrics_socket {
+        let path = ipc::metrics_socket_path();
+        match metrics::spawn_metrics_socket(&path) {
+            Ok(()) => Some(path),
+            Err(err) => {
+                log::warn!("Unable to create metrics socket: {err}");
+                None
+            },
+        }
+    } else {
+        None
+    };
+    #[cfg(not(unix))]
+    if config.debug.metrics_socket {
+        log::warn!("Metrics socket is not supported on this platform");
+    }
+    if let Some(port) = config.debug.metrics_port {
+        if let Err(err) = metrics::spawn_metrics_server(port) {
+            log::warn!("Unable to serve metrics on port {port}: {err}");
+        }
+    }
+
     // Setup automatic RAII cleanup for our files.
     let log_cleanup = log_file.filter(|_| !config.debug.persistent_logging);
     let _files = TemporaryFiles {
         #[cfg(unix)]
+        socket_inode: socket_path
+            .as_ref()
+            .and_then(|path| fs::metadata(path).ok())
+            .map(|metadata| metadata.ino()),
+        #[cfg(unix)]
         socket_path,
+        #[cfg(unix)]
+        metrics_socket_path,
         log_file: log_cleanup,
     };
 
+    // Take over all windows of the restarted instance.
+    #[cfg(unix)]
+    if let Some(stream) = restart_stream {
+        match &_files.socket_path {
+            Some(socket_path) => ipc::request_restart(stream, socket_path)?,
+            None => return Err("IPC socket is required to restart Alacritty".into()),
+        }
+    }
+
     // Event processor.
     let mut processor = Processor::new(config, options, &window_event_loop);
 
@@ -176,6 +176,12 @@ impl MessageBuffer {
             .collect();
     }
 
+    /// Check whether any message with a specific target is queued.
+    #[inline]
+    pub fn contains_target(&self, target: &str) -> bool {
+        self.messages.iter().any(|m| m.target().map(String::as_str) == Some(target))
+    }
+
     /// Add a new message to the queue.
     #[inline]
     pub fn push(&mut self, message: Message) {
@@ -317,7 +323,9 @@ mod tests {
             message_buffer.push(msg);
         }
 
+        assert!(message_buffer.contains_target("target"));
         message_buffer.remove_target("target");
+        assert!(!message_buffer.contains_target("target"));
 
         // Count number of messages.
         let mut num_messages = 0;
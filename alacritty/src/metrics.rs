@@ -0,0 +1,201 @@
+//! Performance metrics in the Prometheus text exposition format.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener};
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+#[cfg(unix)]
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use log::warn;
+
+use alacritty_terminal::thread;
+
+/// Maximum time a client can take to send its request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Number of frames drawn.
+static FRAMES: AtomicU64 = AtomicU64::new(0);
+
+/// Number of terminal lines redrawn.
+static DAMAGED_LINES: AtomicU64 = AtomicU64::new(0);
+
+/// Bytes written to the PTYs.
+static PTY_WRITTEN_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Glyphs currently cached by all windows.
+static CACHED_GLYPHS: AtomicU64 = AtomicU64::new(0);
+
+/// Bytes read from the PTYs.
+static PTY_READ_BYTES: OnceLock<Arc<AtomicU64>> = OnceLock::new();
+
+/// Record a drawn frame.
+pub fn frame_drawn(damaged_lines: usize) {
+    FRAMES.fetch_add(1, Ordering::Relaxed);
+    DAMAGED_LINES.fetch_add(damaged_lines as u64, Ordering::Relaxed);
+}
+
+/// Record bytes written to a PTY.
+pub fn pty_written(bytes: usize) {
+    PTY_WRITTEN_BYTES.fetch_add(bytes as u64, Ordering::Relaxed);
+}
+
+/// Update the glyph count reported for a window.
+pub fn update_cached_glyphs(reported: &mut usize, glyphs: usize) {
+    if glyphs >= *reported {
+        CACHED_GLYPHS.fetch_add((glyphs - *reported) as u64, Ordering::Relaxed);
+    } else {
+        CACHED_GLYPHS.fetch_sub((*reported - glyphs) as u64, Ordering::Relaxed);
+    }
+    *reported = glyphs;
+}
+
+/// Counter for bytes read from the PTYs.
+pub fn pty_read_counter() -> Arc<AtomicU64> {
+    PTY_READ_BYTES.get_or_init(Default::default).clone()
+}
+
+/// Serve metrics on a unix socket only accessible by the current user.
+#[cfg(unix)]
+pub fn spawn_metrics_socket(path: &Path) -> io::Result<()> {
+    let listener = UnixListener::bind(path)?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    log::info!("Serving metrics on {}", path.display());
+
+    thread::spawn_named("metrics socket", move || {
+        for stream in listener.incoming().filter_map(Result::ok) {
+            let result =
+                stream.set_read_timeout(Some(REQUEST_TIMEOUT)).and_then(|_| respond(stream));
+            if let Err(err) = result {
+                warn!("Unable to serve metrics: {err}");
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Serve metrics on the local port.
+///
+/// Unlike the metrics socket, the port is accessible by every user on the machine.
+pub fn spawn_metrics_server(port: u16) -> io::Result<()> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+    warn!("Serving metrics to all local users on http://{}/metrics", listener.local_addr()?);
+
+    thread::spawn_named("metrics server", move || {
+        for stream in listener.incoming().filter_map(Result::ok) {
+            let result =
+                stream.set_read_timeout(Some(REQUEST_TIMEOUT)).and_then(|_| respond(stream));
+            if let Err(err) = result {
+                warn!("Unable to serve metrics: {err}");
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Answer a single HTTP request.
+fn respond<S: Read + Write>(stream: S) -> io::Result<()> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+
+    // Skip headers, since the request has no body.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut stream = reader.into_inner();
+    let mut parts = request.split_whitespace();
+    if parts.next() != Some("GET") || parts.next() != Some("/metrics") {
+        let response = "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+        return stream.write_all(response.as_bytes());
+    }
+
+    let body = metrics();
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: \
+         {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// Current metrics in the Prometheus text format.
+fn metrics() -> String {
+    let pty_read = PTY_READ_BYTES.get().map_or(0, |counter| counter.load(Ordering::Relaxed));
+    let metrics = [
+        ("alacritty_frames_total", "counter", "Frames drawn.", FRAMES.load(Ordering::Relaxed)),
+        (
+            "alacritty_damaged_lines_total",
+            "counter",
+            "Terminal lines redrawn.",
+            DAMAGED_LINES.load(Ordering::Relaxed),
+        ),
+        ("alacritty_pty_read_bytes_total", "counter", "Bytes read from the PTYs.", pty_read),
+        (
+            "alacritty_pty_written_bytes_total",
+            "counter",
+            "Bytes written to the PTYs.",
+            PTY_WRITTEN_BYTES.load(Ordering::Relaxed),
+        ),
+        (
+            "alacritty_glyph_cache_glyphs",
+            "gauge",
+            "Glyphs cached by all windows.",
+            CACHED_GLYPHS.load(Ordering::Relaxed),
+        ),
+    ];
+
+    let mut text = String::new();
+    for (name, ty, help, value) in metrics {
+        text.push_str(&format!("# HELP {name} {help}\n# TYPE {name} {ty}\n{name} {value}\n"));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exposition_format() {
+        let mut reported = 0;
+        update_cached_glyphs(&mut reported, 3);
+        update_cached_glyphs(&mut reported, 2);
+        assert_eq!(reported, 2);
+
+        let text = metrics();
+        assert!(text.contains("# TYPE alacritty_frames_total counter\n"));
+        assert!(text.contains("\nalacritty_glyph_cache_glyphs 2\n"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn socket_permissions() {
+        use std::os::unix::net::UnixStream;
+
+        let path =
+            std::env::temp_dir().join(format!("alacritty-test-{}.metrics", std::process::id()));
+        spawn_metrics_socket(&path).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+
+        let mut stream = UnixStream::connect(&path).unwrap();
+        stream.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("\nalacritty_frames_total "));
+    }
+}
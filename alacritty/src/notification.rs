@@ -0,0 +1,143 @@
+//! Desktop notification delivery using the platform's notification facilities.
+
+use std::io;
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::{debug, warn};
+
+#[cfg(windows)]
+use windows_sys::Win32::System::Threading::CREATE_NO_WINDOW;
+
+/// Application name shown with notifications.
+#[cfg(not(any(target_os = "macos", windows)))]
+const APP_NAME: &str = "Alacritty";
+
+/// PowerShell script showing a toast notification.
+///
+/// Title and body are passed through the environment to avoid quoting issues.
+#[cfg(windows)]
+const TOAST_SCRIPT: &str = "\
+    [Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, \
+     ContentType = WindowsRuntime] | Out-Null; \
+    $template = [Windows.UI.Notifications.ToastTemplateType]::ToastText02; \
+    $xml = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent($template); \
+    $text = $xml.GetElementsByTagName('text'); \
+    $text.Item(0).AppendChild($xml.CreateTextNode($env:ALACRITTY_NOTIFICATION_TITLE)) | Out-Null; \
+    $text.Item(1).AppendChild($xml.CreateTextNode($env:ALACRITTY_NOTIFICATION_BODY)) | Out-Null; \
+    $toast = [Windows.UI.Notifications.ToastNotification]::new($xml); \
+    [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('Alacritty').Show($toast)";
+
+/// Minimum time between two notifications of the same window.
+const MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Rate limited desktop notifications of a window.
+#[derive(Default)]
+pub struct Notifications {
+    /// Time the last notification was accepted.
+    last_notification: Option<Instant>,
+
+    /// Whether the notifier of the last notification is still running.
+    in_flight: Arc<AtomicBool>,
+}
+
+impl Notifications {
+    /// Check if a new notification should be delivered.
+    ///
+    /// Notifications are dropped while the previous one is still being delivered, or was sent
+    /// less than [`MIN_INTERVAL`] ago.
+    pub fn accept(&mut self) -> bool {
+        let now = Instant::now();
+        let too_soon = self.last_notification.is_some_and(|last| now - last < MIN_INTERVAL);
+        if too_soon || self.in_flight.load(Ordering::Relaxed) {
+            return false;
+        }
+
+        self.last_notification = Some(now);
+        true
+    }
+
+    /// Show a desktop notification.
+    pub fn notify(&mut self, title: &str, body: &str) {
+        match spawn_notifier(&mut command(title, body), self.in_flight.clone()) {
+            Ok(()) => debug!("Sent desktop notification: {title:?}, {body:?}"),
+            Err(err) => warn!("Unable to send desktop notification: {err}"),
+        }
+    }
+}
+
+/// Notification command for the freedesktop notification service.
+#[cfg(not(any(target_os = "macos", windows)))]
+fn command(title: &str, body: &str) -> Command {
+    let mut command = Command::new("notify-send");
+    command.arg(format!("--app-name={APP_NAME}")).arg("--").arg(title).arg(body);
+    command
+}
+
+/// Notification command for the macOS notification center.
+#[cfg(target_os = "macos")]
+fn command(title: &str, body: &str) -> Command {
+    let mut command = Command::new("osascript");
+    command
+        .args(["-e", "on run argv"])
+        .args(["-e", "display notification (item 2 of argv) with title (item 1 of argv)"])
+        .args(["-e", "end run"])
+        .arg(title)
+        .arg(body);
+    command
+}
+
+/// Notification command for Windows toast notifications.
+#[cfg(windows)]
+fn command(title: &str, body: &str) -> Command {
+    let mut command = Command::new("powershell");
+    command
+        .args(["-NoProfile", "-NonInteractive", "-Command", TOAST_SCRIPT])
+        .env("ALACRITTY_NOTIFICATION_TITLE", title)
+        .env("ALACRITTY_NOTIFICATION_BODY", body)
+        .creation_flags(CREATE_NO_WINDOW);
+    command
+}
+
+/// Launch the notification command in the background.
+///
+/// The `in_flight` flag is set until the notifier has exited.
+fn spawn_notifier(command: &mut Command, in_flight: Arc<AtomicBool>) -> io::Result<()> {
+    let mut child =
+        command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).spawn()?;
+    in_flight.store(true, Ordering::Relaxed);
+
+    // Reap the notifier once it's done.
+    thread::spawn(move || {
+        let _ = child.wait();
+        in_flight.store(false, Ordering::Relaxed);
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limit() {
+        let mut notifications = Notifications::default();
+        assert!(notifications.accept());
+        assert!(!notifications.accept());
+
+        // Notifications are accepted again after the minimum interval.
+        notifications.last_notification = Some(Instant::now() - MIN_INTERVAL);
+        assert!(notifications.accept());
+
+        // Notifications are dropped while the previous one is being delivered.
+        notifications.last_notification = None;
+        notifications.in_flight.store(true, Ordering::Relaxed);
+        assert!(!notifications.accept());
+    }
+}
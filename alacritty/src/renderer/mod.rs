@@ -20,10 +20,12 @@ use crate::display::color::Rgb;
 use crate::display::content::RenderableCell;
 use crate::display::SizeInfo;
 use crate::gl;
+use crate::renderer::post_process::PostProcess;
 use crate::renderer::rects::{RectRenderer, RenderRect};
 use crate::renderer::shader::ShaderError;
 
 pub mod platform;
+mod post_process;
 pub mod rects;
 mod shader;
 mod text;
@@ -97,6 +99,8 @@ enum TextRendererProvider {
 pub struct Renderer {
     text_renderer: TextRendererProvider,
     rect_renderer: RectRenderer,
+    post_process: Option<PostProcess>,
+    shader_version: ShaderVersion,
     robustness: bool,
 }
 
@@ -158,16 +162,15 @@ impl Renderer {
             None => (shader_version.as_ref() >= "3.3" && !is_gles_context, true),
         };
 
-        let (text_renderer, rect_renderer) = if use_glsl3 {
+        let (text_renderer, shader_version) = if use_glsl3 {
             let text_renderer = TextRendererProvider::Glsl3(Glsl3Renderer::new()?);
-            let rect_renderer = RectRenderer::new(ShaderVersion::Glsl3)?;
-            (text_renderer, rect_renderer)
+            (text_renderer, ShaderVersion::Glsl3)
         } else {
             let text_renderer =
                 TextRendererProvider::Gles2(Gles2Renderer::new(allow_dsb, is_gles_context)?);
-            let rect_renderer = RectRenderer::new(ShaderVersion::Gles2)?;
-            (text_renderer, rect_renderer)
+            (text_renderer, ShaderVersion::Gles2)
         };
+        let rect_renderer = RectRenderer::new(shader_version)?;
 
         // Enable debug logging for OpenGL as well.
         if log::max_level() >= LevelFilter::Debug && GlExtensions::contains("GL_KHR_debug") {
@@ -179,7 +182,7 @@ impl Renderer {
             }
         }
 
-        Ok(Self { text_renderer, rect_renderer, robustness })
+        Ok(Self { text_renderer, rect_renderer, post_process: None, shader_version, robustness })
     }
 
     pub fn draw_cells<I: Iterator<Item = RenderableCell>>(
@@ -272,6 +275,49 @@ impl Renderer {
         }
     }
 
+    /// Replace the shader applied to every rendered frame.
+    ///
+    /// The previous shader is removed even if the new one fails to compile.
+    pub fn set_post_process_shader(
+        &mut self,
+        shader: Option<&str>,
+        size_info: &SizeInfo,
+    ) -> Result<(), Error> {
+        self.post_process = None;
+        if let Some(shader) = shader {
+            self.post_process = Some(PostProcess::new(self.shader_version, shader, size_info)?);
+        }
+        Ok(())
+    }
+
+    /// Check if the post-processing shader requires continuous redraws.
+    pub fn is_animated(&self) -> bool {
+        self.post_process.as_ref().is_some_and(PostProcess::is_animated)
+    }
+
+    /// Check if rendered frames are post-processed.
+    #[cfg(not(any(target_os = "macos", windows)))]
+    pub fn has_post_process(&self) -> bool {
+        self.post_process.is_some()
+    }
+
+    /// Prepare drawing a new frame.
+    pub fn begin_frame(&self) {
+        if let Some(post_process) = &self.post_process {
+            post_process.bind();
+        }
+    }
+
+    /// Finish drawing the frame, applying post-processing.
+    ///
+    /// The shader's time is fixed at zero unless `animate` is set.
+    pub fn end_frame(&self, size_info: &SizeInfo, animate: bool) {
+        if let Some(post_process) = &self.post_process {
+            post_process.draw(size_info, animate);
+            self.set_viewport(size_info);
+        }
+    }
+
     /// Fill the window with `color` and `alpha`.
     pub fn clear(&self, color: Rgb, alpha: f32) {
         unsafe {
@@ -350,6 +396,9 @@ impl Renderer {
     /// Resize the renderer.
     pub fn resize(&self, size_info: &SizeInfo) {
         self.set_viewport(size_info);
+        if let Some(post_process) = &self.post_process {
+            post_process.resize(size_info);
+        }
         match &self.text_renderer {
             TextRendererProvider::Gles2(renderer) => renderer.resize(size_info),
             TextRendererProvider::Glsl3(renderer) => renderer.resize(size_info),
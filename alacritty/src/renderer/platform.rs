@@ -1,6 +1,12 @@
 //! The graphics platform that is used by the renderer.
 
+#[cfg(not(any(target_os = "macos", windows)))]
+use std::env;
 use std::num::NonZeroU32;
+#[cfg(not(any(target_os = "macos", windows)))]
+use std::process::Command;
+#[cfg(not(any(target_os = "macos", windows)))]
+use std::sync::OnceLock;
 
 use glutin::config::{ColorBufferType, Config, ConfigTemplateBuilder, GetGlConfig};
 use glutin::context::{
@@ -17,6 +23,23 @@ use winit::dpi::PhysicalSize;
 use winit::platform::x11;
 use winit::raw_window_handle::{RawDisplayHandle, RawWindowHandle};
 
+#[cfg(not(any(target_os = "macos", windows)))]
+use alacritty_terminal::tty::Options as PtyOptions;
+
+use crate::config::renderer::GpuPreference;
+
+/// Mesa environment variable selecting the GPU.
+#[cfg(not(any(target_os = "macos", windows)))]
+const DRI_PRIME_ENV: &str = "DRI_PRIME";
+
+/// NVIDIA environment variable enabling PRIME render offload.
+#[cfg(not(any(target_os = "macos", windows)))]
+const NV_PRIME_RENDER_OFFLOAD_ENV: &str = "__NV_PRIME_RENDER_OFFLOAD";
+
+/// GLVND environment variable selecting the GLX vendor library.
+#[cfg(not(any(target_os = "macos", windows)))]
+const GLX_VENDOR_LIBRARY_NAME_ENV: &str = "__GLX_VENDOR_LIBRARY_NAME";
+
 /// Create the GL display.
 pub fn create_gl_display(
     raw_display_handle: RawDisplayHandle,
@@ -45,9 +68,88 @@ pub fn create_gl_display(
 
     let display = unsafe { Display::new(raw_display_handle, preference)? };
     log::info!("Using {}", { display.version_string() });
+
+    #[cfg(not(target_os = "macos"))]
+    if let Some(device) = match &display {
+        Display::Egl(display) => display.device().ok(),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    } {
+        log::info!("Using GPU {:?} by {:?}", device.name(), device.vendor());
+    }
+
     Ok(display)
 }
 
+/// Driver environment variables changed for GPU selection, with their original values.
+#[cfg(not(any(target_os = "macos", windows)))]
+static ORIGINAL_GPU_ENV: OnceLock<Vec<(&'static str, Option<String>)>> = OnceLock::new();
+
+/// Set the driver environment selecting the preferred GPU.
+///
+/// Since this modifies the process environment, it must be called before any threads are spawned.
+#[cfg(not(any(target_os = "macos", windows)))]
+pub fn setup_gpu_env(preference: Option<&GpuPreference>) {
+    let vars: &[(&str, &str)] = match preference {
+        Some(GpuPreference::LowPower) => &[(DRI_PRIME_ENV, "0")],
+        Some(GpuPreference::HighPerformance) => &[
+            (DRI_PRIME_ENV, "1"),
+            (NV_PRIME_RENDER_OFFLOAD_ENV, "1"),
+            (GLX_VENDOR_LIBRARY_NAME_ENV, "nvidia"),
+        ],
+        Some(GpuPreference::Adapter(adapter)) => &[(DRI_PRIME_ENV, adapter.as_str())],
+        None => return,
+    };
+
+    let mut original = Vec::new();
+    for (var, value) in vars {
+        original.push((*var, env::var(var).ok()));
+        env::set_var(var, value);
+    }
+
+    let _ = ORIGINAL_GPU_ENV.set(original);
+}
+
+/// Set the driver environment selecting the preferred GPU.
+///
+/// GPU selection is not supported, since macOS and Windows only allow choosing the GPU globally.
+#[cfg(any(target_os = "macos", windows))]
+pub fn setup_gpu_env(preference: Option<&GpuPreference>) {
+    match preference {
+        // macOS already prefers the integrated GPU when possible.
+        #[cfg(target_os = "macos")]
+        Some(GpuPreference::LowPower) => (),
+        Some(_) => log::warn!("GPU preference is not supported on this platform"),
+        None => (),
+    }
+}
+
+/// Restore the driver environment changed by [`setup_gpu_env`] for a child process.
+///
+/// This prevents the GPU preference from leaking into the shell and applications started by it.
+#[cfg(not(any(target_os = "macos", windows)))]
+pub fn restore_gpu_env(pty_config: &mut PtyOptions) {
+    for (var, value) in ORIGINAL_GPU_ENV.get().into_iter().flatten() {
+        match value {
+            Some(value) => {
+                pty_config.env.entry(var.to_string()).or_insert_with(|| value.clone());
+            },
+            None => pty_config.env_remove.push(var.to_string()),
+        }
+    }
+}
+
+/// Restore the driver environment changed by [`setup_gpu_env`] for a child process.
+#[cfg(not(any(target_os = "macos", windows)))]
+pub fn restore_gpu_env_command(command: &mut Command) {
+    for (var, value) in ORIGINAL_GPU_ENV.get().into_iter().flatten() {
+        match value {
+            Some(value) => command.env(var, value),
+            None => command.env_remove(var),
+        };
+    }
+}
+
 pub fn pick_gl_config(
     gl_display: &Display,
     raw_window_handle: Option<RawWindowHandle>,
@@ -0,0 +1,219 @@
+use std::time::Instant;
+use std::{mem, ptr};
+
+use crate::display::SizeInfo;
+use crate::gl;
+use crate::gl::types::*;
+use crate::renderer::shader::{ShaderProgram, ShaderVersion};
+use crate::renderer::{self, cstr};
+
+/// Shader sources for the post-processing program.
+///
+/// The fragment shader only contains declarations, which are prepended to the user's shader.
+const POST_PROCESS_SHADER_F: &str = include_str!("../../res/post_process.f.glsl");
+const POST_PROCESS_SHADER_V: &str = include_str!("../../res/post_process.v.glsl");
+
+/// Texture unit of the rendered frame.
+///
+/// This avoids invalidating the atlas texture bound by the text renderers.
+const FRAME_TEXTURE_UNIT: GLuint = 1;
+
+/// Vertices of two triangles covering the entire viewport.
+const QUAD: [f32; 12] = [-1., -1., 1., -1., -1., 1., -1., 1., 1., -1., 1., 1.];
+
+/// Final render pass applying a user shader to the rendered frame.
+#[derive(Debug)]
+pub struct PostProcess {
+    program: ShaderProgram,
+
+    /// Window size uniform.
+    u_resolution: Option<GLint>,
+
+    /// Time uniform, unavailable if it is unused by the shader.
+    u_time: Option<GLint>,
+
+    // GL buffer objects.
+    vao: GLuint,
+    vbo: GLuint,
+
+    /// Framebuffer the terminal is drawn into.
+    framebuffer: GLuint,
+
+    /// Texture storing the framebuffer's color.
+    texture: GLuint,
+
+    /// Reference point of the time uniform.
+    start: Instant,
+}
+
+impl PostProcess {
+    pub fn new(
+        shader_version: ShaderVersion,
+        shader: &str,
+        size_info: &SizeInfo,
+    ) -> Result<Self, renderer::Error> {
+        let fragment_shader = format!("{POST_PROCESS_SHADER_F}{shader}");
+        let program =
+            ShaderProgram::new(shader_version, None, POST_PROCESS_SHADER_V, &fragment_shader)?;
+
+        let u_frame = program.get_uniform_location(cstr!("frame"))?;
+        let u_resolution = program.get_uniform_location(cstr!("resolution")).ok();
+        let u_time = program.get_uniform_location(cstr!("time")).ok();
+
+        let mut vao: GLuint = 0;
+        let mut vbo: GLuint = 0;
+        let mut framebuffer: GLuint = 0;
+        let mut texture: GLuint = 0;
+
+        unsafe {
+            gl::UseProgram(program.id());
+            gl::Uniform1i(u_frame, FRAME_TEXTURE_UNIT as GLint);
+            gl::UseProgram(0);
+
+            // Upload the static viewport quad.
+            gl::GenVertexArrays(1, &mut vao);
+            gl::GenBuffers(1, &mut vbo);
+
+            gl::BindVertexArray(vao);
+            gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                mem::size_of_val(&QUAD) as isize,
+                QUAD.as_ptr() as *const _,
+                gl::STATIC_DRAW,
+            );
+            gl::VertexAttribPointer(
+                0,
+                2,
+                gl::FLOAT,
+                gl::FALSE,
+                (mem::size_of::<f32>() * 2) as i32,
+                ptr::null(),
+            );
+            gl::EnableVertexAttribArray(0);
+
+            gl::BindVertexArray(0);
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+
+            // Create the framebuffer with a texture for its color.
+            gl::GenTextures(1, &mut texture);
+            gl::GenFramebuffers(1, &mut framebuffer);
+        }
+
+        let post_process = Self {
+            program,
+            u_resolution,
+            u_time,
+            vao,
+            vbo,
+            framebuffer,
+            texture,
+            start: Instant::now(),
+        };
+        post_process.resize(size_info);
+
+        // Ensure the framebuffer can be drawn into.
+        let status = unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, post_process.framebuffer);
+            let status = gl::CheckFramebufferStatus(gl::FRAMEBUFFER);
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+            status
+        };
+        if status != gl::FRAMEBUFFER_COMPLETE {
+            return Err(format!("Incomplete post-processing framebuffer: {status:#x}").into());
+        }
+
+        Ok(post_process)
+    }
+
+    /// Check if the shader changes over time.
+    pub fn is_animated(&self) -> bool {
+        self.u_time.is_some()
+    }
+
+    /// Redirect all drawing into the post-processing framebuffer.
+    pub fn bind(&self) {
+        unsafe { gl::BindFramebuffer(gl::FRAMEBUFFER, self.framebuffer) };
+    }
+
+    /// Draw the rendered frame to the window using the post-processing shader.
+    pub fn draw(&self, size_info: &SizeInfo, animate: bool) {
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+
+            // Replace the window's content, including its alpha.
+            gl::Viewport(0, 0, size_info.width() as i32, size_info.height() as i32);
+            gl::Disable(gl::BLEND);
+
+            gl::UseProgram(self.program.id());
+            if let Some(u_resolution) = self.u_resolution {
+                gl::Uniform2f(u_resolution, size_info.width(), size_info.height());
+            }
+            if let Some(u_time) = self.u_time {
+                let time = if animate { self.start.elapsed().as_secs_f32() } else { 0. };
+                gl::Uniform1f(u_time, time);
+            }
+
+            gl::ActiveTexture(gl::TEXTURE0 + FRAME_TEXTURE_UNIT);
+            gl::BindTexture(gl::TEXTURE_2D, self.texture);
+
+            gl::BindVertexArray(self.vao);
+            gl::DrawArrays(gl::TRIANGLES, 0, (QUAD.len() / 2) as i32);
+
+            // Reset state used by the terminal renderers.
+            gl::BindVertexArray(0);
+            gl::BindTexture(gl::TEXTURE_2D, 0);
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::UseProgram(0);
+            gl::Enable(gl::BLEND);
+        }
+    }
+
+    /// Resize the framebuffer to the window size.
+    pub fn resize(&self, size_info: &SizeInfo) {
+        unsafe {
+            gl::ActiveTexture(gl::TEXTURE0 + FRAME_TEXTURE_UNIT);
+            gl::BindTexture(gl::TEXTURE_2D, self.texture);
+            gl::TexImage2D(
+                gl::TEXTURE_2D,
+                0,
+                gl::RGBA as i32,
+                size_info.width() as i32,
+                size_info.height() as i32,
+                0,
+                gl::RGBA,
+                gl::UNSIGNED_BYTE,
+                ptr::null(),
+            );
+
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+
+            gl::BindFramebuffer(gl::FRAMEBUFFER, self.framebuffer);
+            gl::FramebufferTexture2D(
+                gl::FRAMEBUFFER,
+                gl::COLOR_ATTACHMENT0,
+                gl::TEXTURE_2D,
+                self.texture,
+                0,
+            );
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+
+            gl::BindTexture(gl::TEXTURE_2D, 0);
+            gl::ActiveTexture(gl::TEXTURE0);
+        }
+    }
+}
+
+impl Drop for PostProcess {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteFramebuffers(1, &self.framebuffer);
+            gl::DeleteTextures(1, &self.texture);
+            gl::DeleteBuffers(1, &self.vbo);
+            gl::DeleteVertexArrays(1, &self.vao);
+        }
+    }
+}
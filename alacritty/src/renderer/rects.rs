@@ -83,17 +83,20 @@ impl RenderLine {
                 let top_pos = 0.25 * metrics.descent;
                 let bottom_pos = 0.75 * metrics.descent;
 
-                rects.push(Self::create_rect(
-                    size,
-                    metrics.descent,
-                    start,
-                    end,
-                    top_pos,
-                    metrics.underline_thickness,
-                    color,
-                ));
-
-                (bottom_pos, metrics.underline_thickness, RectKind::Normal)
+                let thickness = metrics.underline_thickness;
+                let descent = metrics.descent;
+                let mut top =
+                    Self::create_rect(size, descent, start, end, top_pos, thickness, color);
+                let bottom =
+                    Self::create_rect(size, descent, start, end, bottom_pos, thickness, color);
+
+                // Keep a gap between both lines after snapping them to the pixel grid.
+                let line_top = start.line as f32 * size.cell_height() + size.padding_y();
+                top.y = top.y.min(bottom.y - 2. * bottom.height).max(line_top);
+
+                rects.push(top);
+                rects.push(bottom);
+                return;
             },
             // Make undercurl occupy the entire descent area.
             Flags::UNDERCURL => (metrics.descent, metrics.descent.abs(), RectKind::Undercurl),
@@ -133,17 +136,19 @@ impl RenderLine {
         let end_x = (end.column.0 + 1) as f32 * size.cell_width();
         let width = end_x - start_x;
 
-        // Make sure lines are always visible.
-        thickness = thickness.max(1.);
+        // Snap lines to whole pixels, to avoid blurry lines at fractional scale factors, while
+        // making sure they are always visible.
+        thickness = thickness.round().max(1.);
 
-        let line_bottom = (start.line as f32 + 1.) * size.cell_height();
+        // Position is calculated from the unrounded metrics, to avoid accumulating rounding
+        // errors before snapping to the pixel grid.
+        let line_top = start.line as f32 * size.cell_height();
+        let line_bottom = line_top + size.cell_height();
         let baseline = line_bottom + descent;
 
-        let mut y = (baseline - position - thickness / 2.).round();
-        let max_y = line_bottom - thickness;
-        if y > max_y {
-            y = max_y;
-        }
+        // Keep the line inside its cell, to avoid clipping it with the next line's content.
+        let y = (baseline - position - thickness / 2.).round();
+        let y = y.min(line_bottom - thickness).max(line_top);
 
         RenderRect::new(
             start_x + size.padding_x(),
@@ -495,3 +500,73 @@ impl RectShaderProgram {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Metrics of a typical monospace font at a scale factor of 1.
+    const METRICS: Metrics = Metrics {
+        average_advance: 7.2,
+        line_height: 16.4,
+        descent: -3.7,
+        underline_position: -1.6,
+        underline_thickness: 0.8,
+        strikeout_position: 4.1,
+        strikeout_thickness: 0.8,
+    };
+
+    /// Vertical position and height of all rects for a line on the second row.
+    fn line_rects(flag: Flags, scale_factor: f32) -> Vec<(f32, f32)> {
+        let metrics = Metrics {
+            average_advance: METRICS.average_advance * f64::from(scale_factor),
+            line_height: METRICS.line_height * f64::from(scale_factor),
+            descent: METRICS.descent * scale_factor,
+            underline_position: METRICS.underline_position * scale_factor,
+            underline_thickness: METRICS.underline_thickness * scale_factor,
+            strikeout_position: METRICS.strikeout_position * scale_factor,
+            strikeout_thickness: METRICS.strikeout_thickness * scale_factor,
+        };
+        let cell_width = metrics.average_advance.floor() as f32;
+        let cell_height = metrics.line_height.floor() as f32;
+        let size = SizeInfo::new(800., 600., cell_width, cell_height, 0., 0., false);
+
+        let line = RenderLine {
+            start: Point::new(1, Column(0)),
+            end: Point::new(1, Column(2)),
+            color: Rgb::default(),
+        };
+
+        line.rects(flag, &metrics, &size).iter().map(|rect| (rect.y, rect.height)).collect()
+    }
+
+    #[test]
+    fn line_snapshots() {
+        let snapshots = [
+            (1., [vec![(29., 1.)], vec![(29., 1.), (31., 1.)], vec![(24., 1.)]]),
+            (1.25, [vec![(37., 1.)], vec![(36., 1.), (38., 1.)], vec![(30., 1.)]]),
+            (1.5, [vec![(44., 1.)], vec![(43., 1.), (46., 1.)], vec![(36., 1.)]]),
+            (1.75, [vec![(52., 1.)], vec![(51., 1.), (54., 1.)], vec![(42., 1.)]]),
+            (2., [vec![(59., 2.)], vec![(57., 2.), (61., 2.)], vec![(47., 2.)]]),
+        ];
+
+        for (scale_factor, [underline, double_underline, strikeout]) in snapshots {
+            assert_eq!(line_rects(Flags::UNDERLINE, scale_factor), underline);
+            assert_eq!(line_rects(Flags::DOUBLE_UNDERLINE, scale_factor), double_underline);
+            assert_eq!(line_rects(Flags::STRIKEOUT, scale_factor), strikeout);
+        }
+    }
+
+    #[test]
+    fn lines_stay_inside_cell() {
+        for scale_factor in [1., 1.1, 1.25, 1.333, 1.5, 1.75, 2., 2.5, 3.] {
+            let cell_height = (METRICS.line_height * f64::from(scale_factor)).floor() as f32;
+            let flags = [Flags::UNDERLINE, Flags::DOUBLE_UNDERLINE, Flags::STRIKEOUT];
+            for (y, height) in flags.into_iter().flat_map(|flag| line_rects(flag, scale_factor)) {
+                assert_eq!(y.fract(), 0.);
+                assert_eq!(height.fract(), 0.);
+                assert!(y >= cell_height && y + height <= 2. * cell_height);
+            }
+        }
+    }
+}
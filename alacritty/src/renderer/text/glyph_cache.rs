@@ -2,13 +2,13 @@ use std::collections::HashMap;
 
 use ahash::RandomState;
 use crossfont::{
-    Error as RasterizerError, FontDesc, FontKey, GlyphKey, Metrics, Rasterize, RasterizedGlyph,
-    Rasterizer, Size, Slant, Style, Weight,
+    BitmapBuffer, Error as RasterizerError, FontDesc, FontKey, GlyphKey, Metrics, Rasterize,
+    RasterizedGlyph, Rasterizer, Size, Slant, Style, Weight,
 };
 use log::{error, info};
 use unicode_width::UnicodeWidthChar;
 
-use crate::config::font::{Font, FontDescription};
+use crate::config::font::{Font, FontDescription, GlyphOffsetRange, Subpixel};
 use crate::config::ui_config::Delta;
 use crate::gl::types::*;
 
@@ -71,11 +71,17 @@ pub struct GlyphCache {
     /// Glyph offset.
     glyph_offset: Delta<i8>,
 
+    /// Additional glyph offsets for codepoint ranges.
+    glyph_offset_ranges: Vec<GlyphOffsetRange>,
+
     /// Font metrics.
     metrics: Metrics,
 
     /// Whether to use the built-in font for box drawing characters.
     builtin_box_drawing: bool,
+
+    /// Subpixel order of the display.
+    subpixel: Subpixel,
 }
 
 impl GlyphCache {
@@ -99,8 +105,10 @@ impl GlyphCache {
             bold_italic_key: bold_italic,
             font_offset: font.offset,
             glyph_offset: font.glyph_offset,
+            glyph_offset_ranges: font.glyph_offset_ranges.clone(),
             metrics,
             builtin_box_drawing: font.builtin_box_drawing,
+            subpixel: font.subpixel,
         })
     }
 
@@ -245,6 +253,14 @@ impl GlyphCache {
         glyph.top += i32::from(self.glyph_offset.y);
         glyph.top -= self.metrics.descent as i32;
 
+        // Apply the offset of the first range containing this character.
+        let range_offset =
+            self.glyph_offset_ranges.iter().find_map(|range| range.offset(glyph.character));
+        if let Some(offset) = range_offset {
+            glyph.left += i32::from(offset.x);
+            glyph.top += i32::from(offset.y);
+        }
+
         // The metrics of zero-width characters are based on rendering
         // the character after the current cell, with the anchor at the
         // right side of the preceding character. Since we render the
@@ -254,10 +270,29 @@ impl GlyphCache {
             glyph.left += self.metrics.average_advance as i32;
         }
 
+        // Override the subpixel order, rasterizers normalize masks to the system subpixel order.
+        if let BitmapBuffer::Rgb(buffer) = &mut glyph.buffer {
+            match self.subpixel {
+                Subpixel::Reverse => buffer.chunks_exact_mut(3).for_each(|rgb| rgb.swap(0, 2)),
+                Subpixel::None => {
+                    for rgb in buffer.chunks_exact_mut(3) {
+                        let sum = rgb.iter().map(|&channel| u16::from(channel)).sum::<u16>();
+                        rgb.fill((sum / 3) as u8);
+                    }
+                },
+                Subpixel::System => (),
+            }
+        }
+
         // Add glyph to cache.
         loader.load_glyph(&glyph)
     }
 
+    /// Number of cached glyphs.
+    pub fn glyph_count(&self) -> usize {
+        self.cache.len()
+    }
+
     /// Reset currently cached data in both GL and the registry to default state.
     pub fn reset_glyph_cache<L: LoadGlyph>(&mut self, loader: &mut L) {
         loader.clear();
@@ -274,6 +309,7 @@ impl GlyphCache {
         // Update dpi scaling.
         self.font_offset = font.offset;
         self.glyph_offset = font.glyph_offset;
+        self.glyph_offset_ranges = font.glyph_offset_ranges.clone();
 
         // Recompute font keys.
         let (regular, bold, italic, bold_italic) =
@@ -295,6 +331,7 @@ impl GlyphCache {
         self.bold_italic_key = bold_italic;
         self.metrics = metrics;
         self.builtin_box_drawing = font.builtin_box_drawing;
+        self.subpixel = font.subpixel;
 
         Ok(())
     }
@@ -28,6 +28,13 @@ pub enum Topic {
     DelayedSearch,
     BlinkCursor,
     BlinkTimeout,
+    MouseIdle,
+    TouchLongPress,
+    #[cfg(unix)]
+    ThemePreview,
+    TitleUpdate,
+    #[cfg(unix)]
+    StoppedJob,
     Frame,
 }
 
@@ -0,0 +1,124 @@
+//! Automatic shell integration.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use log::{debug, warn};
+use tempfile::NamedTempFile;
+
+use alacritty_terminal::tty::{Options as PtyOptions, Shell};
+
+/// Shell integration scripts, relative to the integration directory.
+const SCRIPTS: [(&str, &str); 4] = [
+    ("bash/alacritty.bash", include_str!("../res/shell-integration/bash/alacritty.bash")),
+    ("zsh/.zshenv", include_str!("../res/shell-integration/zsh/.zshenv")),
+    (
+        "zsh/alacritty-integration.zsh",
+        include_str!("../res/shell-integration/zsh/alacritty-integration.zsh"),
+    ),
+    (
+        "fish/vendor_conf.d/alacritty.fish",
+        include_str!("../res/shell-integration/fish/vendor_conf.d/alacritty.fish"),
+    ),
+];
+
+/// Fallback for `XDG_DATA_DIRS` defined by the XDG base directory specification.
+const DEFAULT_DATA_DIRS: &str = "/usr/local/share:/usr/share";
+
+/// Set up the PTY's environment to load the shell integration scripts.
+pub fn inject(pty_config: &mut PtyOptions) {
+    let program = match &pty_config.shell {
+        Some(shell) => shell.program().to_owned(),
+        None => match env::var("SHELL") {
+            Ok(shell) => shell,
+            Err(_) => return,
+        },
+    };
+
+    let name = Path::new(&program).file_name().and_then(|name| name.to_str());
+    let name = match name {
+        Some(name @ ("bash" | "zsh" | "fish")) => name.to_owned(),
+        _ => return debug!("No shell integration available for {program:?}"),
+    };
+
+    // Bash can only be injected through its POSIX mode, which requires control over its arguments.
+    //
+    // Since the default shell is started through `login` on macOS, it can't be modified there.
+    let custom_args = match &pty_config.shell {
+        Some(shell) => !shell.args().is_empty(),
+        None => cfg!(target_os = "macos"),
+    };
+    if name == "bash" && custom_args {
+        return debug!("Skipping shell integration for bash with custom arguments");
+    }
+
+    let dir = match install_scripts() {
+        Ok(dir) => dir,
+        Err(err) => return warn!("Unable to install shell integration: {err}"),
+    };
+
+    let env = &mut pty_config.env;
+    match name.as_str() {
+        "bash" => {
+            pty_config.shell = Some(Shell::new(program, vec![String::from("--posix")]));
+            let script = dir.join("bash/alacritty.bash");
+            env.insert(String::from("ENV"), script.to_string_lossy().into());
+            env.insert(String::from("ALACRITTY_BASH_INJECT"), String::from("1"));
+        },
+        "zsh" => {
+            if let Some(zdotdir) = original_var(env, "ZDOTDIR") {
+                env.insert(String::from("ALACRITTY_ZDOTDIR"), zdotdir);
+            }
+            env.insert(String::from("ZDOTDIR"), dir.join("zsh").to_string_lossy().into());
+        },
+        _ => {
+            let dir = dir.join("fish").to_string_lossy().into_owned();
+            let data_dirs = original_var(env, "XDG_DATA_DIRS").filter(|dirs| !dirs.is_empty());
+            let data_dirs = data_dirs.as_deref().unwrap_or(DEFAULT_DATA_DIRS);
+            env.insert(String::from("XDG_DATA_DIRS"), format!("{dir}:{data_dirs}"));
+            env.insert(String::from("ALACRITTY_SHELL_INTEGRATION_DIR"), dir);
+        },
+    }
+}
+
+/// Value of an environment variable the shell would inherit.
+fn original_var(env: &HashMap<String, String>, var: &str) -> Option<String> {
+    env.get(var).cloned().or_else(|| env::var(var).ok())
+}
+
+/// Write the shell integration scripts to disk.
+fn install_scripts() -> Result<PathBuf, Box<dyn Error>> {
+    let dir = integration_dir()?;
+
+    for (path, content) in SCRIPTS {
+        let path = dir.join(path);
+        if fs::read(&path).is_ok_and(|current| current == content.as_bytes()) {
+            continue;
+        }
+
+        // Replace scripts atomically, since other instances might be reading them.
+        let parent = path.parent().unwrap_or(&dir);
+        fs::create_dir_all(parent)?;
+        let mut file = NamedTempFile::new_in(parent)?;
+        file.write_all(content.as_bytes())?;
+        file.persist(&path)?;
+    }
+
+    Ok(dir)
+}
+
+/// Private directory for the shell integration scripts.
+#[cfg(not(target_os = "macos"))]
+fn integration_dir() -> Result<PathBuf, Box<dyn Error>> {
+    let xdg = xdg::BaseDirectories::with_prefix("alacritty")?;
+    Ok(xdg.get_runtime_directory()?.join("shell-integration"))
+}
+
+/// Private directory for the shell integration scripts.
+#[cfg(target_os = "macos")]
+fn integration_dir() -> Result<PathBuf, Box<dyn Error>> {
+    Ok(env::temp_dir().join("alacritty-shell-integration"))
+}
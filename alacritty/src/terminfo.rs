@@ -0,0 +1,167 @@
+//! Installation of the bundled terminfo entries.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::{env, thread};
+
+use log::{debug, warn};
+use tempfile::NamedTempFile;
+
+use crate::cli::TerminfoOptions;
+
+/// Terminfo source for all Alacritty entries.
+const TERMINFO: &str = include_str!("../extra/alacritty.info");
+
+/// Terminfo entries installed by Alacritty.
+const ENTRIES: &str = "alacritty,alacritty-direct";
+
+/// Handle the terminfo subcommand.
+pub fn terminfo(options: TerminfoOptions) -> Result<(), Box<dyn Error>> {
+    if options.print {
+        io::stdout().write_all(TERMINFO.as_bytes())?;
+        return Ok(());
+    }
+
+    let dir = match options.output.or_else(default_dir) {
+        Some(dir) => dir,
+        None => return Err("unable to determine terminfo directory".into()),
+    };
+
+    let mut source = NamedTempFile::new()?;
+    source.write_all(TERMINFO.as_bytes())?;
+
+    let status = Command::new("tic")
+        .args(["-x", "-e", ENTRIES, "-o"])
+        .arg(&dir)
+        .arg(source.path())
+        .status()
+        .map_err(|err| format!("unable to run tic: {err}"))?;
+
+    if !status.success() {
+        return Err(format!("tic failed with {status}").into());
+    }
+
+    println!("Installed terminfo to {}", dir.display());
+
+    Ok(())
+}
+
+/// Warn if the bundled terminfo is missing or outdated.
+///
+/// This must be called after the `TERM` for child processes has been set up.
+#[cfg_attr(windows, allow(dead_code))]
+pub fn check_installed() {
+    if env::var("TERM").ok().as_deref() != Some("alacritty") {
+        warn!("Alacritty terminfo is not installed, run `alacritty terminfo` to install it");
+        return;
+    }
+
+    // Query the installed entry in the background, to avoid delaying startup.
+    let _ = thread::Builder::new().name(String::from("terminfo check")).spawn(|| {
+        let output = match Command::new("infocmp")
+            .args(["-x", "-1", "alacritty"])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+        {
+            Ok(output) if output.status.success() => output,
+            Ok(output) => return debug!("Unable to query installed terminfo: {}", output.status),
+            Err(err) => return debug!("Unable to query installed terminfo: {err}"),
+        };
+
+        let installed = String::from_utf8_lossy(&output.stdout);
+        let installed = capabilities(&installed, "alacritty");
+        let mut bundled = capabilities(TERMINFO, "alacritty+common");
+        for capability in capabilities(TERMINFO, "alacritty") {
+            match capability.strip_suffix('@') {
+                Some(cancelled) => bundled.remove(cancelled),
+                None => bundled.insert(capability),
+            };
+        }
+
+        let mut missing: Vec<_> = bundled.difference(&installed).copied().collect();
+        if !missing.is_empty() {
+            missing.sort_unstable();
+            debug!("Capabilities missing from installed terminfo: {}", missing.join(", "));
+            warn!("Alacritty terminfo is outdated, run `alacritty terminfo` to update it");
+        }
+    });
+}
+
+/// Default terminfo installation directory.
+fn default_dir() -> Option<PathBuf> {
+    env::var_os("TERMINFO")
+        .map(PathBuf::from)
+        .or_else(|| home::home_dir().map(|home| home.join(".terminfo")))
+}
+
+/// Names of the capabilities set by a terminfo source entry.
+///
+/// Capabilities inherited through `use` are ignored, cancelled capabilities retain their `@`.
+#[cfg_attr(windows, allow(dead_code))]
+fn capabilities<'a>(source: &'a str, entry: &str) -> HashSet<&'a str> {
+    let mut capabilities = HashSet::new();
+
+    let mut in_entry = false;
+    for line in source.lines().filter(|line| !line.trim_start().starts_with('#')) {
+        // Entry headers are the only lines without indentation.
+        if !line.starts_with(char::is_whitespace) {
+            in_entry = line.split(',').next().unwrap_or_default().split('|').next() == Some(entry);
+            continue;
+        }
+
+        if !in_entry {
+            continue;
+        }
+
+        // Fields wrapped across multiple lines will contain no name before their separator.
+        for field in line.split(',').map(str::trim) {
+            let name_end = field.find(['=', '#', '@']).unwrap_or(field.len());
+            let name = &field[..name_end];
+            if name.is_empty()
+                || name == "use"
+                || !name.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+            {
+                continue;
+            }
+
+            if field[name_end..].starts_with('@') {
+                capabilities.insert(&field[..=name_end]);
+            } else {
+                capabilities.insert(name);
+            }
+        }
+    }
+
+    capabilities
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_capabilities() {
+        let common = capabilities(TERMINFO, "alacritty+common");
+        assert!(common.contains("kbs"));
+        assert!(common.contains("smcup"));
+
+        let alacritty = capabilities(TERMINFO, "alacritty");
+        assert!(alacritty.contains("initc"));
+        assert!(alacritty.contains("colors"));
+        assert!(!alacritty.contains("use"));
+        assert!(alacritty.contains("setb@"));
+        assert!(!alacritty.contains("setb"));
+        assert!(!alacritty.contains("smcup"));
+    }
+
+    #[test]
+    fn installed_capabilities() {
+        let installed = "alacritty|alacritty terminal emulator,\n\tam,\n\tcolors#0x100,\n\tkbs=^?,\n";
+        let installed = capabilities(installed, "alacritty");
+        assert_eq!(installed, HashSet::from(["am", "colors", "kbs"]));
+    }
+}
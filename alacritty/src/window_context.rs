@@ -2,47 +2,80 @@
 
 use std::error::Error;
 use std::fs::File;
-use std::io::Write;
+use std::io::{self, ErrorKind, Write};
 use std::mem;
+#[cfg(windows)]
+use std::num::NonZeroU32;
+#[cfg(unix)]
+use std::os::fd::BorrowedFd;
 #[cfg(not(windows))]
 use std::os::unix::io::{AsRawFd, RawFd};
 use std::rc::Rc;
 use std::sync::Arc;
+#[cfg(unix)]
+use std::thread::JoinHandle;
+#[cfg(unix)]
+use std::time::Duration;
+use std::time::Instant;
 
 use glutin::config::Config as GlutinConfig;
 use glutin::display::GetGlDisplay;
 #[cfg(all(feature = "x11", not(any(target_os = "macos", windows))))]
 use glutin::platform::x11::X11GlConfigExt;
-use log::info;
+use log::{info, warn};
 use serde_json as json;
 use winit::event::{Event as WinitEvent, Modifiers, WindowEvent};
 use winit::event_loop::{ActiveEventLoop, EventLoopProxy};
 use winit::raw_window_handle::HasDisplayHandle;
 use winit::window::WindowId;
 
-use alacritty_terminal::event::Event as TerminalEvent;
+#[cfg(unix)]
+use alacritty_terminal::event::OnResize;
+use alacritty_terminal::event::{Event as TerminalEvent, WindowSize};
+#[cfg(unix)]
+use alacritty_terminal::event_loop::State as PtyState;
 use alacritty_terminal::event_loop::{EventLoop as PtyEventLoop, Msg, Notifier};
 use alacritty_terminal::grid::{Dimensions, Scroll};
 use alacritty_terminal::index::Direction;
 use alacritty_terminal::sync::FairMutex;
 use alacritty_terminal::term::test::TermSize;
 use alacritty_terminal::term::{Term, TermMode};
-use alacritty_terminal::tty;
+use alacritty_terminal::tty::{self, Options as PtyOptions, Shell};
 
+#[cfg(unix)]
+use crate::cli::{IpcGet, IpcGetField};
 use crate::cli::{ParsedOptions, WindowOptions};
 use crate::clipboard::Clipboard;
+#[cfg(unix)]
+use crate::config::terminal::ShellIntegration;
 use crate::config::UiConfig;
+#[cfg(unix)]
+use crate::daemon::foreground_process_path;
 use crate::display::window::Window;
 use crate::display::Display;
 use crate::event::{
-    ActionContext, Event, EventProxy, InlineSearchState, Mouse, SearchState, TouchPurpose,
+    ActionContext, CopyModeState, Event, EventProxy, EventType, InlineSearchState, Mouse,
+    SearchState, TouchPurpose,
+};
+use crate::hooks::Hook;
+#[cfg(unix)]
+use crate::ipc::{
+    self, DetachedTerminal, DetachingTerminal, IpcDimensions, IpcEvent, IpcWindowState,
 };
 #[cfg(unix)]
 use crate::logging::LOG_TARGET_IPC_CONFIG;
 use crate::message_bar::MessageBuffer;
-use crate::scheduler::Scheduler;
+use crate::metrics;
+use crate::notification::Notifications;
+use crate::scheduler::{Scheduler, TimerId, Topic};
+#[cfg(unix)]
+use crate::shell_integration;
 use crate::{input, renderer};
 
+/// Interval for checking whether a job of the shell is stopped.
+#[cfg(unix)]
+const STOPPED_JOB_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Event context for one individual Alacritty window.
 pub struct WindowContext {
     pub message_buffer: MessageBuffer,
@@ -53,17 +86,27 @@ pub struct WindowContext {
     cursor_blink_timed_out: bool,
     modifiers: Modifiers,
     inline_search_state: InlineSearchState,
+    copy_mode: CopyModeState,
+    output_paused: bool,
     search_state: SearchState,
     notifier: Notifier,
     mouse: Mouse,
     touch: TouchPurpose,
     occluded: bool,
     preserve_title: bool,
+    pending_title: Option<String>,
+    title_updated: Option<Instant>,
+    notifications: Notifications,
     #[cfg(not(windows))]
     master_fd: RawFd,
-    #[cfg(not(windows))]
     shell_pid: u32,
+    #[cfg(unix)]
+    io_thread: Option<JoinHandle<(PtyEventLoop<tty::Pty, EventProxy>, PtyState)>>,
+    #[cfg(unix)]
+    detaching_event_loop: Option<PtyEventLoop<tty::Pty, EventProxy>>,
     window_config: ParsedOptions,
+    #[cfg(unix)]
+    theme_preview: ParsedOptions,
     config: Rc<UiConfig>,
 }
 
@@ -74,6 +117,7 @@ impl WindowContext {
         proxy: EventLoopProxy<Event>,
         config: Rc<UiConfig>,
         mut options: WindowOptions,
+        #[cfg(unix)] detached: Option<&DetachedTerminal>,
     ) -> Result<Self, Box<dyn Error>> {
         let raw_display_handle = event_loop.display_handle().unwrap().as_raw();
 
@@ -113,7 +157,14 @@ impl WindowContext {
 
         let display = Display::new(window, gl_context, &config, false)?;
 
-        Self::new(display, config, options, proxy)
+        Self::new(
+            display,
+            config,
+            options,
+            proxy,
+            #[cfg(unix)]
+            detached,
+        )
     }
 
     /// Create additional context with the graphics platform other windows are using.
@@ -124,6 +175,7 @@ impl WindowContext {
         config: Rc<UiConfig>,
         mut options: WindowOptions,
         config_overrides: ParsedOptions,
+        #[cfg(unix)] detached: Option<&DetachedTerminal>,
     ) -> Result<Self, Box<dyn Error>> {
         let gl_display = gl_config.display();
 
@@ -152,7 +204,14 @@ impl WindowContext {
 
         let display = Display::new(window, gl_context, &config, tabbed)?;
 
-        let mut window_context = Self::new(display, config, options, proxy)?;
+        let mut window_context = Self::new(
+            display,
+            config,
+            options,
+            proxy,
+            #[cfg(unix)]
+            detached,
+        )?;
 
         // Set the config overrides at startup.
         //
@@ -168,9 +227,15 @@ impl WindowContext {
         config: Rc<UiConfig>,
         options: WindowOptions,
         proxy: EventLoopProxy<Event>,
+        #[cfg(unix)] detached: Option<&DetachedTerminal>,
     ) -> Result<Self, Box<dyn Error>> {
         let mut pty_config = config.pty_config();
         options.terminal_options.override_pty_config(&mut pty_config);
+        #[cfg(not(any(target_os = "macos", windows)))]
+        renderer::platform::restore_gpu_env(&mut pty_config);
+
+        // Commands passed through the CLI are never replaced with another shell.
+        let shell_fallback = options.terminal_options.command().is_none();
 
         let preserve_title = options.window_identity.title.is_some();
 
@@ -187,20 +252,49 @@ impl WindowContext {
         // This object contains all of the state about what's being displayed. It's
         // wrapped in a clonable mutex since both the I/O loop and display need to
         // access it.
-        let terminal = Term::new(config.term_options(), &display.size_info, event_proxy.clone());
-        let terminal = Arc::new(FairMutex::new(terminal));
+        #[cfg_attr(windows, allow(unused_mut))]
+        let mut terminal =
+            Term::new(config.term_options(), &display.size_info, event_proxy.clone());
 
         // Create the PTY.
         //
         // The PTY forks a process to run the shell on the slave side of the
         // pseudoterminal. A file descriptor for the master side is retained for
         // reading/writing to the shell.
-        let pty = tty::new(&pty_config, display.size_info.into(), display.window.id().into())?;
+        #[cfg(unix)]
+        let pty = match detached {
+            Some(detached) => {
+                terminal.restore_state(detached.state.clone())?;
+
+                let mut pty = tty::adopt(detached.fd.try_clone()?, detached.pid)?;
+                pty.on_resize(display.size_info.into());
+                pty
+            },
+            None => spawn_shell(
+                &config,
+                pty_config.clone(),
+                shell_fallback,
+                display.size_info.into(),
+                display.window.id().into(),
+            )?,
+        };
+        #[cfg(not(unix))]
+        let pty = spawn_shell(
+            &config,
+            pty_config.clone(),
+            shell_fallback,
+            display.size_info.into(),
+            display.window.id().into(),
+        )?;
+
+        let terminal = Arc::new(FairMutex::new(terminal));
 
         #[cfg(not(windows))]
         let master_fd = pty.file().as_raw_fd();
         #[cfg(not(windows))]
-        let shell_pid = pty.child().id();
+        let shell_pid = pty.pid();
+        #[cfg(windows)]
+        let shell_pid = pty.child_watcher().pid().map_or(0, NonZeroU32::get);
 
         // Create the pseudoterminal I/O loop.
         //
@@ -208,19 +302,25 @@ impl WindowContext {
         // renderer and input processing. Note that access to the terminal state is
         // synchronized since the I/O loop updates the state, and the display
         // consumes it periodically.
-        let event_loop = PtyEventLoop::new(
+        let mut event_loop = PtyEventLoop::new(
             Arc::clone(&terminal),
             event_proxy.clone(),
             pty,
             pty_config.drain_on_exit,
             config.debug.ref_test,
         )?;
+        if config.debug.metrics_socket || config.debug.metrics_port.is_some() {
+            event_loop.set_read_counter(metrics::pty_read_counter());
+        }
 
         // The event loop channel allows write requests from the event processor
         // to be sent to the pty loop and ultimately written to the pty.
         let loop_tx = event_loop.channel();
 
         // Kick off the I/O thread.
+        #[cfg(unix)]
+        let io_thread = Some(event_loop.spawn());
+        #[cfg(not(unix))]
         let _io_thread = event_loop.spawn();
 
         // Start cursor blinking, in case `Focused` isn't sent on startup.
@@ -235,14 +335,24 @@ impl WindowContext {
             display,
             #[cfg(not(windows))]
             master_fd,
-            #[cfg(not(windows))]
             shell_pid,
+            #[cfg(unix)]
+            io_thread,
+            #[cfg(unix)]
+            detaching_event_loop: None,
             config,
             notifier: Notifier(loop_tx),
             cursor_blink_timed_out: Default::default(),
             inline_search_state: Default::default(),
+            copy_mode: Default::default(),
+            output_paused: Default::default(),
             message_buffer: Default::default(),
+            pending_title: Default::default(),
+            title_updated: Default::default(),
+            notifications: Default::default(),
             window_config: Default::default(),
+            #[cfg(unix)]
+            theme_preview: Default::default(),
             search_state: Default::default(),
             event_queue: Default::default(),
             modifiers: Default::default(),
@@ -259,6 +369,10 @@ impl WindowContext {
 
         // Apply ipc config if there are overrides.
         self.config = self.window_config.override_config_rc(self.config.clone());
+        #[cfg(unix)]
+        {
+            self.config = self.theme_preview.override_config_rc(self.config.clone());
+        }
 
         self.display.update_config(&self.config);
         self.terminal.lock().set_options(self.config.term_options());
@@ -291,6 +405,11 @@ impl WindowContext {
             self.display.pending_update.dirty = true;
         }
 
+        // Fit the font size to the new grid dimensions.
+        if window_config.fit_grid() != self.config.window.fit_grid() {
+            self.display.pending_update.set_dimensions(self.display.window.inner_size());
+        }
+
         // Update title on config reload according to the following table.
         //
         // │cli │ dynamic_title │ current_title == old_config ││ set_title │
@@ -352,6 +471,106 @@ impl WindowContext {
         self.update_config(config);
     }
 
+    /// Temporarily override the window config with a theme.
+    #[cfg(unix)]
+    pub fn preview_theme(&mut self, config: Rc<UiConfig>, options: ParsedOptions) {
+        // Clear previous window errors.
+        self.message_buffer.remove_target(LOG_TARGET_IPC_CONFIG);
+
+        self.theme_preview = options;
+
+        // Reload current config to pull the previewed theme.
+        self.update_config(config);
+    }
+
+    /// Remove the previewed theme.
+    #[cfg(unix)]
+    pub fn revert_theme_preview(&mut self, config: Rc<UiConfig>) {
+        if self.theme_preview.is_empty() {
+            return;
+        }
+
+        self.theme_preview.clear();
+        self.update_config(config);
+    }
+
+    /// Get the window state requested by an IPC query.
+    #[cfg(unix)]
+    pub fn ipc_state(&self, get: &IpcGet) -> IpcWindowState {
+        let mut state = IpcWindowState::new(self.id());
+
+        if get.wants(IpcGetField::WorkingDirectory) {
+            state.working_directory = foreground_process_path(self.master_fd, self.shell_pid).ok();
+        }
+
+        if get.wants(IpcGetField::Dimensions) {
+            state.dimensions = Some(IpcDimensions::from(&self.display.size_info));
+        }
+
+        if get.wants(IpcGetField::Title) {
+            state.title = Some(self.display.window.title().to_owned());
+        }
+
+        if get.wants(IpcGetField::Config) {
+            state.config = Some(self.config.config_value.clone());
+        }
+
+        state
+    }
+
+    /// Display text as if it was written by the shell.
+    #[cfg(unix)]
+    pub fn inject(&self, text: String) {
+        self.notifier.inject(text.into_bytes());
+    }
+
+    /// Stop the terminal's I/O, to hand its shell over to another process.
+    ///
+    /// The PTY is kept until the transfer is complete, see [`Self::finish_detach`] and
+    /// [`Self::cancel_detach`].
+    #[cfg(unix)]
+    pub fn start_detach(&mut self) -> Result<DetachingTerminal, Box<dyn Error>> {
+        let io_thread = self.io_thread.take().ok_or("terminal is already detached")?;
+
+        let _ = self.notifier.0.send(Msg::Shutdown);
+        let (event_loop, _) = io_thread.join().map_err(|_| "PTY reader panicked")?;
+
+        let fd = match unsafe { BorrowedFd::borrow_raw(self.master_fd) }.try_clone_to_owned() {
+            Ok(fd) => fd,
+            Err(err) => {
+                self.io_thread = Some(event_loop.spawn());
+                return Err(err.into());
+            },
+        };
+        self.detaching_event_loop = Some(event_loop);
+
+        Ok(DetachingTerminal { fd, pid: self.shell_pid, terminal: self.terminal.clone() })
+    }
+
+    /// Release the shell after it was taken over by another process.
+    #[cfg(unix)]
+    pub fn finish_detach(&mut self) {
+        if let Some(event_loop) = self.detaching_event_loop.take() {
+            if let Err(err) = event_loop.into_pty().detach() {
+                warn!("Unable to release detached PTY: {err}");
+            }
+        }
+    }
+
+    /// Resume the terminal's I/O after its shell could not be handed over.
+    #[cfg(unix)]
+    pub fn cancel_detach(&mut self) {
+        if let Some(event_loop) = self.detaching_event_loop.take() {
+            self.io_thread = Some(event_loop.spawn());
+        }
+    }
+
+    /// Keep the previewed theme as window config override.
+    #[cfg(unix)]
+    pub fn confirm_theme_preview(&mut self) {
+        self.window_config.append(&mut self.theme_preview);
+    }
+
     /// Draw the window.
     pub fn draw(&mut self, scheduler: &mut Scheduler) {
         self.display.window.requested_redraw = false;
@@ -376,6 +595,19 @@ impl WindowContext {
             }
         }
 
+        // Keep redrawing until animations have finished, or forever for animated shaders.
+        if !self.display.resize_overlay.completed()
+            || !self.display.presentation.completed()
+            || !self.display.color_transition.completed()
+            || (self.display.has_animated_shader() && !self.config.general.reduce_motion())
+        {
+            if self.display.window.has_frame {
+                self.display.window.request_redraw();
+            } else {
+                self.dirty = true;
+            }
+        }
+
         // Redraw the window.
         let terminal = self.terminal.lock();
         self.display.draw(
@@ -385,6 +617,11 @@ impl WindowContext {
             &self.config,
             &mut self.search_state,
         );
+
+        // Redraw once the lost OpenGL context could be recreated.
+        if self.display.is_context_lost() {
+            self.dirty = true;
+        }
     }
 
     /// Process events for this terminal window.
@@ -420,6 +657,7 @@ impl WindowContext {
             cursor_blink_timed_out: &mut self.cursor_blink_timed_out,
             message_buffer: &mut self.message_buffer,
             inline_search_state: &mut self.inline_search_state,
+            copy_mode: &mut self.copy_mode,
             search_state: &mut self.search_state,
             modifiers: &mut self.modifiers,
             notifier: &mut self.notifier,
@@ -431,9 +669,10 @@ impl WindowContext {
             terminal: &mut terminal,
             #[cfg(not(windows))]
             master_fd: self.master_fd,
-            #[cfg(not(windows))]
             shell_pid: self.shell_pid,
             preserve_title: self.preserve_title,
+            pending_title: &mut self.pending_title,
+            notifications: &mut self.notifications,
             config: &self.config,
             event_proxy,
             #[cfg(target_os = "macos")]
@@ -447,6 +686,12 @@ impl WindowContext {
             processor.handle_event(event);
         }
 
+        // Stop reading terminal output while copy mode is active.
+        if self.copy_mode.frozen != self.output_paused {
+            self.output_paused = self.copy_mode.frozen;
+            let _ = self.notifier.0.send(Msg::Pause(self.output_paused));
+        }
+
         // Process DisplayUpdate events.
         if self.display.pending_update.dirty {
             Self::submit_display_update(
@@ -480,6 +725,67 @@ impl WindowContext {
         {
             self.display.window.request_redraw();
         }
+
+        // Apply only the last title change of all processed events.
+        drop(terminal);
+        self.update_title(scheduler);
+
+        #[cfg(unix)]
+        self.update_stopped_job_polling(scheduler);
+    }
+
+    /// Start or stop polling for stopped jobs of the shell.
+    #[cfg(unix)]
+    fn update_stopped_job_polling(&mut self, scheduler: &mut Scheduler) {
+        let timer_id = TimerId::new(Topic::StoppedJob, self.id());
+        // Stopped jobs can only be detected on Linux.
+        let enabled = cfg!(any(target_os = "linux", target_os = "android"))
+            && self.config.terminal.mode_indicator.stopped_job;
+        if enabled == scheduler.scheduled(timer_id) {
+            return;
+        }
+
+        if enabled {
+            let event = Event::new(EventType::StoppedJob, self.id());
+            scheduler.schedule(event, STOPPED_JOB_POLL_INTERVAL, true, timer_id);
+        } else {
+            scheduler.unschedule(timer_id);
+        }
+    }
+
+    /// Apply the pending title change, unless the title was changed too recently.
+    fn update_title(&mut self, scheduler: &mut Scheduler) {
+        let timer_id = TimerId::new(Topic::TitleUpdate, self.id());
+        if self.pending_title.is_none() || scheduler.scheduled(timer_id) {
+            return;
+        }
+
+        // Retry once the minimum interval since the last title change has passed.
+        let min_interval = self.config.window.title_updates.min_interval();
+        let elapsed = self.title_updated.map_or(min_interval, |updated| updated.elapsed());
+        if elapsed < min_interval {
+            let event = Event::new(EventType::TitleUpdate, self.id());
+            scheduler.schedule(event, min_interval - elapsed, false, timer_id);
+            return;
+        }
+
+        if let Some(title) = self.pending_title.take() {
+            self.display.window.set_title(title);
+            self.title_updated = Some(Instant::now());
+
+            let title = self.display.window.title().to_owned();
+            Hook::Title(title.clone()).run(
+                &self.config.hooks,
+                self.id(),
+                #[cfg(not(windows))]
+                self.master_fd,
+                #[cfg(not(windows))]
+                self.shell_pid,
+            );
+
+            #[cfg(unix)]
+            ipc::send_event(self.id(), IpcEvent::TitleChanged { title });
+        }
     }
 
     /// ID of this terminal context.
@@ -555,3 +861,46 @@ impl Drop for WindowContext {
         let _ = self.notifier.0.send(Msg::Shutdown);
     }
 }
+
+/// Spawn the shell, falling back to the default shell and `/bin/sh` if it doesn't exist.
+#[cfg_attr(windows, allow(unused_variables))]
+fn spawn_shell(
+    config: &UiConfig,
+    mut pty_config: PtyOptions,
+    fallback: bool,
+    window_size: WindowSize,
+    window_id: u64,
+) -> io::Result<tty::Pty> {
+    let mut fallbacks = Vec::new();
+    if fallback {
+        if pty_config.shell.is_some() {
+            fallbacks.push(None);
+        }
+        #[cfg(unix)]
+        fallbacks.push(Some(Shell::new("/bin/sh".into(), Vec::new())));
+    }
+    let mut fallbacks = fallbacks.into_iter();
+
+    loop {
+        #[allow(unused_mut)]
+        let mut shell_config = pty_config.clone();
+        #[cfg(unix)]
+        if config.terminal.shell_integration == ShellIntegration::Auto {
+            shell_integration::inject(&mut shell_config);
+        }
+
+        match tty::new(&shell_config, window_size, window_id) {
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                let shell = match fallbacks.next() {
+                    Some(shell) => shell,
+                    None => return Err(err),
+                };
+
+                let name = shell.as_ref().map_or("the default shell", Shell::program);
+                warn!("{err}; falling back to {name}");
+                pty_config.shell = shell;
+            },
+            result => return result,
+        }
+    }
+}
@@ -4,10 +4,18 @@ use std::path::PathBuf;
 
 use log::LevelFilter;
 use serde::Deserialize;
-use toml::Value;
+use toml::{Table, Value};
 
 pub trait SerdeReplace {
     fn replace(&mut self, value: Value) -> Result<(), Box<dyn Error>>;
+
+    /// Dotted paths of all nested keys which can be replaced individually.
+    fn keys() -> Vec<String>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
 }
 
 #[macro_export]
@@ -33,6 +41,7 @@ impl_replace!(
     String,
     PathBuf,
     LevelFilter,
+    Table,
 );
 
 fn replace_simple<'de, D>(data: &mut D, value: Value) -> Result<(), Box<dyn Error>>
@@ -57,6 +66,10 @@ impl<'de, T: SerdeReplace + Deserialize<'de>> SerdeReplace for Option<T> {
             None => replace_simple(self, value),
         }
     }
+
+    fn keys() -> Vec<String> {
+        T::keys()
+    }
 }
 
 impl<'de, T: Deserialize<'de>> SerdeReplace for HashMap<String, T> {
@@ -48,6 +48,7 @@ pub fn derive_recursive<T>(
         Err(e) => return e.to_compile_error(),
         Ok(replace_arms) => replace_arms,
     };
+    let keys = field_keys(&fields);
 
     quote! {
         #[allow(clippy::extra_unused_lifetimes)]
@@ -73,8 +74,55 @@ pub fn derive_recursive<T>(
 
                 Ok(())
             }
+
+            fn keys() -> Vec<String> {
+                let mut keys = Vec::new();
+                #keys
+                keys
+            }
+        }
+    }
+}
+
+/// Create statements collecting the nested keys of all fields.
+fn field_keys<T>(fields: &Punctuated<Field, T>) -> TokenStream2 {
+    let mut stream = TokenStream2::default();
+
+    for field in fields {
+        let ident = field.ident.as_ref().expect("unreachable tuple struct");
+        let literal = ident.to_string();
+        let ty = &field.ty;
+
+        let attrs: Vec<_> = field
+            .attrs
+            .iter()
+            .filter(|attr| (*attr).path().is_ident("config"))
+            .filter_map(|attr| attr.parse_args::<Attr>().ok())
+            .map(|parsed| parsed.ident)
+            .collect();
+
+        // Hide fields which are not part of the configuration file.
+        if attrs.iter().any(|attr| matches!(attr.as_str(), "skip" | "deprecated" | "removed")) {
+            continue;
+        }
+
+        if attrs.iter().any(|attr| attr == "flatten") {
+            stream.extend(quote! {
+                keys.extend(<#ty as alacritty_config::SerdeReplace>::keys());
+            });
+        } else {
+            stream.extend(quote! {
+                let nested = <#ty as alacritty_config::SerdeReplace>::keys();
+                if nested.is_empty() {
+                    keys.push(String::from(#literal));
+                } else {
+                    keys.extend(nested.into_iter().map(|key| format!("{}.{}", #literal, key)));
+                }
+            });
         }
     }
+
+    stream
 }
 
 /// Create SerdeReplace recursive match arms.
@@ -241,3 +241,20 @@ fn replace_flatten_using_alias() {
 
     assert_eq!(test.flatten.flatty, 7);
 }
+
+#[test]
+fn derive_keys() {
+    let keys = <Test as alacritty_config::SerdeReplace>::keys();
+
+    assert_eq!(keys, [
+        "field3",
+        "nesting.field1",
+        "nesting.field2",
+        "nesting.field4",
+        "nesting.newtype",
+        "flatty",
+        "enom_small",
+        "enom_big",
+        "multiple_alias_field",
+    ]);
+}
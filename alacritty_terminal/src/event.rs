@@ -50,11 +50,22 @@ pub enum Event {
     /// Terminal bell ring.
     Bell,
 
+    /// Desktop notification request with an optional title and a body.
+    Notification(Option<String>, String),
+
     /// Shutdown request.
     Exit,
 
     /// Child process exited with an error code.
     ChildExit(i32),
+
+    /// Text of a line which was terminated by a line feed.
+    ///
+    /// This is only sent when [`EventListener::wants_committed_lines`] returns `true`.
+    LineCommit(String),
+
+    /// Shell command finished, as reported by its shell integration using `OSC 133 ; D`.
+    CommandFinished,
 }
 
 impl Debug for Event {
@@ -71,8 +82,11 @@ impl Debug for Event {
             Event::ResetTitle => write!(f, "ResetTitle"),
             Event::Wakeup => write!(f, "Wakeup"),
             Event::Bell => write!(f, "Bell"),
+            Event::Notification(title, body) => write!(f, "Notification({title:?}, {body})"),
             Event::Exit => write!(f, "Exit"),
             Event::ChildExit(code) => write!(f, "ChildExit({code})"),
+            Event::LineCommit(text) => write!(f, "LineCommit({text})"),
+            Event::CommandFinished => write!(f, "CommandFinished"),
         }
     }
 }
@@ -101,6 +115,11 @@ pub trait OnResize {
 /// Event Loop for notifying the renderer about terminal events.
 pub trait EventListener {
     fn send_event(&self, _event: Event) {}
+
+    /// Whether [`Event::LineCommit`] should be sent for every finished line.
+    fn wants_committed_lines(&self) -> bool {
+        false
+    }
 }
 
 /// Null sink for events.
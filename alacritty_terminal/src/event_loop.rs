@@ -6,6 +6,7 @@ use std::fmt::{self, Display, Formatter};
 use std::fs::File;
 use std::io::{self, ErrorKind, Read, Write};
 use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
 use std::sync::Arc;
 use std::thread::JoinHandle;
@@ -16,6 +17,7 @@ use polling::{Event as PollingEvent, Events, PollMode};
 
 use crate::event::{self, Event, EventListener, WindowSize};
 use crate::sync::FairMutex;
+use crate::term::osc::OscParser;
 use crate::term::Term;
 use crate::{thread, tty};
 use vte::ansi;
@@ -37,6 +39,14 @@ pub enum Msg {
 
     /// Instruction to resize the PTY.
     Resize(WindowSize),
+
+    /// Stop or resume reading from the PTY.
+    Pause(bool),
+
+    /// Data that should be processed as if it was read from the PTY.
+    ///
+    /// This is never written to the PTY, so the child process will not observe it.
+    Inject(Cow<'static, [u8]>),
 }
 
 /// The main event loop.
@@ -52,6 +62,9 @@ pub struct EventLoop<T: tty::EventedPty, U: EventListener> {
     event_proxy: U,
     drain_on_exit: bool,
     ref_test: bool,
+    read_bytes: Option<Arc<AtomicU64>>,
+    paused: bool,
+    paused_injections: VecDeque<Cow<'static, [u8]>>,
 }
 
 impl<T, U> EventLoop<T, U>
@@ -78,9 +91,22 @@ where
             event_proxy,
             drain_on_exit,
             ref_test,
+            read_bytes: None,
+            paused: false,
+            paused_injections: VecDeque::new(),
         })
     }
 
+    /// Add the number of bytes read from the PTY to a shared counter.
+    pub fn set_read_counter(&mut self, counter: Arc<AtomicU64>) {
+        self.read_bytes = Some(counter);
+    }
+
+    /// Consume the event loop, returning its PTY.
+    pub fn into_pty(self) -> T {
+        self.pty
+    }
+
     pub fn channel(&self) -> EventLoopSender {
         EventLoopSender { sender: self.tx.clone(), poller: self.poll.clone() }
     }
@@ -93,6 +119,10 @@ where
             match msg {
                 Msg::Input(input) => state.write_list.push_back(input),
                 Msg::Resize(window_size) => self.pty.on_resize(window_size),
+                Msg::Pause(paused) => self.set_paused(state, paused),
+                // Terminal updates are frozen while paused, so injections must wait too.
+                Msg::Inject(bytes) if self.paused => self.paused_injections.push_back(bytes),
+                Msg::Inject(bytes) => self.inject(state, &bytes),
                 Msg::Shutdown => return false,
             }
         }
@@ -100,6 +130,32 @@ where
         true
     }
 
+    /// Stop or resume reading from the PTY, processing injections queued while paused.
+    fn set_paused(&mut self, state: &mut State, paused: bool) {
+        self.paused = paused;
+
+        if !paused {
+            while let Some(bytes) = self.paused_injections.pop_front() {
+                self.inject(state, &bytes);
+            }
+        }
+    }
+
+    /// Process synthetic output.
+    ///
+    /// A separate parser is used, to avoid corrupting escape sequences split across PTY reads.
+    /// Since the output did not originate from the child, any replies it would trigger are
+    /// dropped instead of being written to the PTY.
+    fn inject(&mut self, state: &mut State, bytes: &[u8]) {
+        let mut terminal = self.terminal.lock();
+        terminal.suppress_replies = true;
+        state.inject_osc_parser.advance(&mut state.inject_parser, &mut *terminal, bytes);
+        terminal.suppress_replies = false;
+        drop(terminal);
+
+        self.event_proxy.send_event(Event::Wakeup);
+    }
+
     #[inline]
     fn pty_read<X>(
         &mut self,
@@ -120,9 +176,19 @@ where
         loop {
             // Read from the PTY.
             match self.pty.reader().read(&mut buf[unprocessed..]) {
+                // Adopted processes can only be observed through the PTY hanging up.
+                #[cfg(not(windows))]
+                Ok(0) if unprocessed == 0 && self.pty.is_adopted() => {
+                    return Err(ErrorKind::UnexpectedEof.into());
+                },
                 // This is received on Windows/macOS when no more data is readable from the PTY.
                 Ok(0) if unprocessed == 0 => break,
-                Ok(got) => unprocessed += got,
+                Ok(got) => {
+                    if let Some(read_bytes) = &self.read_bytes {
+                        read_bytes.fetch_add(got as u64, Ordering::Relaxed);
+                    }
+                    unprocessed += got;
+                },
                 Err(err) => match err.kind() {
                     ErrorKind::Interrupted | ErrorKind::WouldBlock => {
                         // Go back to mio if we're caught up on parsing and the PTY would block.
@@ -151,13 +217,13 @@ where
             }
 
             // Parse the incoming bytes.
-            state.parser.advance(&mut **terminal, &buf[..unprocessed]);
+            state.osc_parser.advance(&mut state.parser, &mut **terminal, &buf[..unprocessed]);
 
             processed += unprocessed;
             unprocessed = 0;
 
-            // Assure we're not blocking the terminal too long unnecessarily.
-            if processed >= MAX_LOCKED_READ {
+            // Assure we're not blocking the terminal too long unnecessarily, or delaying input.
+            if processed >= MAX_LOCKED_READ || self.rx.peek().is_some() {
                 break;
             }
         }
@@ -170,6 +236,18 @@ where
         Ok(())
     }
 
+    /// Shut down the terminal after the PTY of an adopted process was hung up.
+    fn hangup<X>(&mut self, state: &mut State, buf: &mut [u8], writer: Option<&mut X>)
+    where
+        X: Write,
+    {
+        if self.drain_on_exit {
+            let _ = self.pty_read(state, buf, writer);
+        }
+        self.terminal.lock().exit();
+        self.event_proxy.send_event(Event::Wakeup);
+    }
+
     #[inline]
     fn pty_write(&mut self, state: &mut State) -> io::Result<()> {
         state.ensure_next();
@@ -243,7 +321,8 @@ where
 
                 // Handle synchronized update timeout.
                 if events.is_empty() && self.rx.peek().is_none() {
-                    state.parser.stop_sync(&mut *self.terminal.lock());
+                    let terminal = &mut *self.terminal.lock();
+                    state.osc_parser.stop_sync(&mut state.parser, terminal);
                     self.event_proxy.send_event(Event::Wakeup);
                     continue;
                 }
@@ -253,6 +332,14 @@ where
                     break;
                 }
 
+                // Write input right away, instead of waiting for write readiness behind output.
+                if state.needs_write() {
+                    if let Err(err) = self.pty_write(&mut state) {
+                        error!("Error writing to PTY in event loop: {}", err);
+                        break 'event_loop;
+                    }
+                }
+
                 for event in events.iter() {
                     match event.key {
                         tty::PTY_CHILD_EVENT_TOKEN => {
@@ -272,13 +359,31 @@ where
 
                         tty::PTY_READ_WRITE_TOKEN => {
                             if event.is_interrupt() {
+                                if self.pty.is_adopted() {
+                                    self.hangup(&mut state, &mut buf, pipe.as_mut());
+                                    break 'event_loop;
+                                }
+
                                 // Don't try to do I/O on a dead PTY.
                                 continue;
                             }
 
+                            // Prioritize input over output.
+                            if event.writable {
+                                if let Err(err) = self.pty_write(&mut state) {
+                                    error!("Error writing to PTY in event loop: {}", err);
+                                    break 'event_loop;
+                                }
+                            }
+
                             if event.readable {
                                 if let Err(err) = self.pty_read(&mut state, &mut buf, pipe.as_mut())
                                 {
+                                    if self.pty.is_adopted() && is_hangup(&err) {
+                                        self.hangup(&mut state, &mut buf, pipe.as_mut());
+                                        break 'event_loop;
+                                    }
+
                                     // On Linux, a `read` on the master side of a PTY can fail
                                     // with `EIO` if the client side hangs up.  In that case,
                                     // just loop back round for the inevitable `Exited` event.
@@ -293,21 +398,16 @@ where
                                     break 'event_loop;
                                 }
                             }
-
-                            if event.writable {
-                                if let Err(err) = self.pty_write(&mut state) {
-                                    error!("Error writing to PTY in event loop: {}", err);
-                                    break 'event_loop;
-                                }
-                            }
                         },
                         _ => (),
                     }
                 }
 
-                // Register write interest if necessary.
+                // Register read and write interest if necessary.
+                let needs_read = !self.paused;
                 let needs_write = state.needs_write();
-                if needs_write != interest.writable {
+                if needs_read != interest.readable || needs_write != interest.writable {
+                    interest.readable = needs_read;
                     interest.writable = needs_write;
 
                     // Re-register with new interest.
@@ -323,6 +423,16 @@ where
     }
 }
 
+/// Check if a PTY read error was caused by the PTY being hung up.
+fn is_hangup(err: &io::Error) -> bool {
+    #[cfg(target_os = "linux")]
+    if err.raw_os_error() == Some(libc::EIO) {
+        return true;
+    }
+
+    err.kind() == ErrorKind::UnexpectedEof
+}
+
 /// Helper type which tracks how much of a buffer has been written.
 struct Writing {
     source: Cow<'static, [u8]>,
@@ -331,6 +441,19 @@ struct Writing {
 
 pub struct Notifier(pub EventLoopSender);
 
+impl Notifier {
+    /// Process bytes as if they were read from the PTY, without sending them to the child.
+    pub fn inject<B>(&self, bytes: B)
+    where
+        B: Into<Cow<'static, [u8]>>,
+    {
+        let bytes = bytes.into();
+        if !bytes.is_empty() {
+            let _ = self.0.send(Msg::Inject(bytes));
+        }
+    }
+}
+
 impl event::Notify for Notifier {
     fn notify<B>(&self, bytes: B)
     where
@@ -338,7 +461,7 @@ impl event::Notify for Notifier {
     {
         let bytes = bytes.into();
         // Terminal hangs if we send 0 bytes through.
-        if bytes.len() == 0 {
+        if bytes.is_empty() {
             return;
         }
 
@@ -401,6 +524,9 @@ pub struct State {
     write_list: VecDeque<Cow<'static, [u8]>>,
     writing: Option<Writing>,
     parser: ansi::Processor,
+    inject_parser: ansi::Processor,
+    osc_parser: OscParser,
+    inject_osc_parser: OscParser,
 }
 
 impl State {
@@ -483,3 +609,170 @@ impl<T> PeekableReceiver<T> {
         }
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use crate::term::test::TermSize;
+    use crate::term::Config;
+    use crate::tty::{Options, Shell};
+
+    /// Listener forwarding all committed lines.
+    #[derive(Clone)]
+    struct LineListener(Sender<String>);
+
+    impl EventListener for LineListener {
+        fn send_event(&self, event: Event) {
+            if let Event::LineCommit(text) = event {
+                let _ = self.0.send(text);
+            }
+        }
+
+        fn wants_committed_lines(&self) -> bool {
+            true
+        }
+    }
+
+    /// Sender shutting down the event loop when dropped.
+    ///
+    /// This terminates the shell and its jobs even when a test fails.
+    struct ShutdownOnDrop(EventLoopSender);
+
+    impl Drop for ShutdownOnDrop {
+        fn drop(&mut self) {
+            let _ = self.0.send(Msg::Shutdown);
+        }
+    }
+
+    type IoThread = JoinHandle<(EventLoop<tty::Pty, LineListener>, State)>;
+
+    /// Run a shell script in a new PTY, forwarding all committed lines.
+    fn spawn_shell(script: &str) -> (ShutdownOnDrop, Receiver<String>, IoThread) {
+        let shell = Shell::new("sh".into(), vec!["-c".into(), script.into()]);
+        let options = Options { shell: Some(shell), ..Options::default() };
+        let window_size = WindowSize { num_lines: 24, num_cols: 80, cell_width: 1, cell_height: 1 };
+        let pty = tty::new(&options, window_size, 0).unwrap();
+
+        spawn_event_loop(pty, false)
+    }
+
+    /// Run an event loop for a PTY, forwarding all committed lines.
+    fn spawn_event_loop(
+        pty: tty::Pty,
+        drain_on_exit: bool,
+    ) -> (ShutdownOnDrop, Receiver<String>, IoThread) {
+        let (tx, rx) = mpsc::channel();
+        let listener = LineListener(tx);
+        let size = TermSize::new(80, 24);
+        let terminal =
+            Arc::new(FairMutex::new(Term::new(Config::default(), &size, listener.clone())));
+        let event_loop = EventLoop::new(terminal, listener, pty, drain_on_exit, false).unwrap();
+        let sender = ShutdownOnDrop(event_loop.channel());
+
+        (sender, rx, event_loop.spawn())
+    }
+
+    #[test]
+    fn input_during_output_flood() {
+        // Echo a line of input, while flooding the terminal with output.
+        let (sender, rx, io_thread) =
+            spawn_shell("yes flood & read line; echo \"got $line\"; kill $!");
+
+        // Wait for the flood to start.
+        while rx.recv_timeout(Duration::from_secs(5)).unwrap() != "flood" {}
+
+        // Input written only once the flood is drained would never be echoed.
+        sender.0.send(Msg::Input(Cow::Borrowed(b"marker\n"))).unwrap();
+        while !rx.recv_timeout(Duration::from_secs(5)).unwrap().contains("got marker") {}
+
+        drop(sender);
+        io_thread.join().unwrap();
+    }
+
+    #[test]
+    fn adopted_pty() {
+        let (sender, _rx, io_thread) = spawn_shell("read line; echo \"got $line\"");
+
+        // Stop the original event loop without hanging up the shell.
+        drop(sender);
+        let pty = io_thread.join().unwrap().0.into_pty();
+        let pid = pty.pid();
+        let pty = tty::adopt(pty.detach().unwrap(), pid).unwrap();
+
+        let (sender, rx, io_thread) = spawn_event_loop(pty, true);
+
+        sender.0.send(Msg::Input(Cow::Borrowed(b"marker\n"))).unwrap();
+        while !rx.recv_timeout(Duration::from_secs(5)).unwrap().contains("got marker") {}
+
+        // The event loop stops once the shell hangs up.
+        let start = Instant::now();
+        while !io_thread.is_finished() {
+            assert!(start.elapsed() < Duration::from_secs(5), "PTY hangup was not detected");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn inject_output() {
+        let (sender, rx, io_thread) =
+            spawn_shell("echo ready; read line; echo \"got $line\"; read line");
+
+        // Wait for the shell to start.
+        while rx.recv_timeout(Duration::from_secs(5)).unwrap() != "ready" {}
+
+        sender.0.send(Msg::Inject(Cow::Borrowed(b"\x1b[1minjected\x1b[0m\r\n"))).unwrap();
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)).unwrap(), "injected");
+
+        // The child only receives the regular input.
+        sender.0.send(Msg::Input(Cow::Borrowed(b"marker\n"))).unwrap();
+        let mut line = String::new();
+        while !line.starts_with("got") {
+            line = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        }
+        assert_eq!(line, "got marker");
+
+        drop(sender);
+        io_thread.join().unwrap();
+    }
+
+    #[test]
+    fn pause_output() {
+        let (sender, rx, io_thread) = spawn_shell("echo ready; read line; echo output; read line");
+
+        // Wait for the shell to start.
+        while rx.recv_timeout(Duration::from_secs(5)).unwrap() != "ready" {}
+
+        // Output is not read while paused, but input is still written.
+        sender.0.send(Msg::Pause(true)).unwrap();
+        sender.0.send(Msg::Input(Cow::Borrowed(b"\r"))).unwrap();
+        assert!(rx.recv_timeout(Duration::from_millis(200)).is_err());
+
+        sender.0.send(Msg::Pause(false)).unwrap();
+        while rx.recv_timeout(Duration::from_secs(5)).unwrap() != "output" {}
+
+        drop(sender);
+        io_thread.join().unwrap();
+    }
+
+    #[test]
+    fn inject_while_paused() {
+        let (sender, rx, io_thread) = spawn_shell("echo ready; read line");
+
+        // Wait for the shell to start.
+        while rx.recv_timeout(Duration::from_secs(5)).unwrap() != "ready" {}
+
+        // Injections are deferred until the terminal is resumed.
+        sender.0.send(Msg::Pause(true)).unwrap();
+        sender.0.send(Msg::Inject(Cow::Borrowed(b"injected\r\n"))).unwrap();
+        assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+
+        sender.0.send(Msg::Pause(false)).unwrap();
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)).unwrap(), "injected");
+
+        drop(sender);
+        io_thread.join().unwrap();
+    }
+}
@@ -31,6 +31,7 @@ pub trait GridCell: Sized {
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Cursor<T> {
     /// The location of this cursor.
     pub point: Point,
@@ -39,6 +40,7 @@ pub struct Cursor<T> {
     pub template: T,
 
     /// Currently configured graphic character sets.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub charsets: Charsets,
 
     /// Tracks if the next call to input will need to first handle wrapping.
@@ -150,6 +152,20 @@ impl<T: GridCell + Default + PartialEq> Grid<T> {
         }
     }
 
+    /// Check that the grid's dimensions and cursors are consistent, after it was deserialized.
+    #[cfg(feature = "serde")]
+    pub(crate) fn is_consistent(&self, cursors: &[&Cursor<T>]) -> bool {
+        self.columns > 0
+            && self.lines > 0
+            && self.raw.is_consistent(self.lines, self.columns)
+            && self.display_offset <= self.history_size()
+            && cursors.iter().all(|cursor| {
+                cursor.point.line.0 >= 0
+                    && cursor.point.line.0 < self.lines as i32
+                    && cursor.point.column.0 < self.columns
+            })
+    }
+
     /// Update the size of the scrollback history.
     pub fn update_history(&mut self, history_size: usize) {
         let current_history_size = self.history_size();
@@ -248,14 +264,32 @@ impl<T: GridCell + Default + PartialEq> Grid<T> {
 
     /// Move lines at the bottom toward the top.
     ///
-    /// This is the performance-sensitive part of scrolling.
+    /// Lines leaving the region are moved into history if the region starts at the top of the
+    /// screen.
+    #[inline]
     pub fn scroll_up<D>(&mut self, region: &Range<Line>, positions: usize)
     where
         T: ResetDiscriminant<D>,
         D: PartialEq,
     {
-        // When rotating the entire region with fixed lines at the top, just reset everything.
-        if region.end - region.start <= positions && region.start != 0 {
+        self.scroll_up_with_history(region, positions, region.start == 0);
+    }
+
+    /// Move lines at the bottom toward the top, optionally moving lines leaving the region into
+    /// history.
+    ///
+    /// This is the performance-sensitive part of scrolling.
+    pub fn scroll_up_with_history<D>(
+        &mut self,
+        region: &Range<Line>,
+        positions: usize,
+        save_history: bool,
+    ) where
+        T: ResetDiscriminant<D>,
+        D: PartialEq,
+    {
+        // When rotating the entire region without saving it, just reset everything.
+        if region.end - region.start <= positions && !save_history {
             for i in (region.start.0..region.end.0).map(Line::from) {
                 self.raw[i].reset(&self.cursor.template);
             }
@@ -268,21 +302,22 @@ impl<T: GridCell + Default + PartialEq> Grid<T> {
             self.display_offset = min(self.display_offset + positions, self.max_scroll_limit);
         }
 
-        // Only rotate the entire history if the active region starts at the top.
-        if region.start == 0 {
+        // Only rotate the entire history if the lines leaving the region should be saved.
+        if save_history {
             // Create scrollback for the new lines.
             self.increase_scroll_limit(positions);
 
-            // Swap the lines fixed at the top to their target positions after rotation.
-            //
-            // Since we've made sure that the rotation will never rotate away the entire region, we
-            // know that the position of the fixed lines before the rotation must already be
-            // visible.
+            // Move the lines fixed at the top below the lines leaving the region, so they end up
+            // at the top again after the rotation.
             //
-            // We need to start from the bottom, to make sure the fixed lines aren't swapped with
-            // each other.
-            for i in (0..region.start.0).rev().map(Line::from) {
-                self.raw.swap(i, i + positions);
+            // This rotates the fixed lines and the lines leaving the region by reversing both
+            // individually, followed by reversing them together.
+            if region.start != 0 {
+                let start = region.start.0;
+                let end = start + positions as i32;
+                self.reverse_lines(0..start);
+                self.reverse_lines(start..end);
+                self.reverse_lines(0..end);
             }
 
             // Rotate the entire line buffer upward.
@@ -306,6 +341,16 @@ impl<T: GridCell + Default + PartialEq> Grid<T> {
         }
     }
 
+    /// Reverse the order of the lines in a range.
+    fn reverse_lines(&mut self, range: Range<i32>) {
+        let (mut top, mut bottom) = (range.start, range.end - 1);
+        while top < bottom {
+            self.raw.swap(Line(top), Line(bottom));
+            top += 1;
+            bottom -= 1;
+        }
+    }
+
     pub fn clear_viewport<D>(&mut self)
     where
         T: ResetDiscriminant<D>,
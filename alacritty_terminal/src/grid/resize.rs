@@ -182,6 +182,7 @@ impl<T: GridCell + Default + PartialEq> Grid<T> {
                 let line_delta = self.cursor.point.line - target.line;
 
                 if line_delta != 0 && row.is_clear() {
+                    last_row.marks.insert(row.marks);
                     continue;
                 }
 
@@ -198,6 +199,7 @@ impl<T: GridCell + Default + PartialEq> Grid<T> {
                 }
 
                 // Don't push line into the new buffer.
+                last_row.marks.insert(row.marks);
                 continue;
             }
 
@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 use crate::grid::GridCell;
 use crate::index::Column;
 use crate::term::cell::ResetDiscriminant;
+use crate::term::prompt::PromptMarks;
 
 /// A row in the grid.
 #[derive(Default, Clone, Debug)]
@@ -22,6 +23,10 @@ pub struct Row<T> {
     /// This is the upper bound on the number of elements in the row, which have been modified
     /// since the last reset. All cells after this point are guaranteed to be equal.
     pub(crate) occ: usize,
+
+    /// Command boundaries on this row.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) marks: PromptMarks,
 }
 
 impl<T: PartialEq> PartialEq for Row<T> {
@@ -52,7 +57,7 @@ impl<T: Default> Row<T> {
             inner.set_len(columns);
         }
 
-        Row { inner, occ: 0 }
+        Row { inner, occ: 0, marks: PromptMarks::empty() }
     }
 
     /// Increase the number of columns in the row.
@@ -111,6 +116,7 @@ impl<T: Default> Row<T> {
         }
 
         self.occ = 0;
+        self.marks = PromptMarks::empty();
     }
 }
 
@@ -118,7 +124,7 @@ impl<T: Default> Row<T> {
 impl<T> Row<T> {
     #[inline]
     pub fn from_vec(vec: Vec<T>, occ: usize) -> Row<T> {
-        Row { inner: vec, occ }
+        Row { inner: vec, occ, marks: PromptMarks::empty() }
     }
 
     #[inline]
@@ -126,6 +132,12 @@ impl<T> Row<T> {
         self.inner.len()
     }
 
+    /// Command boundaries on this row.
+    #[inline]
+    pub fn marks(&self) -> PromptMarks {
+        self.marks
+    }
+
     #[inline]
     pub fn last(&self) -> Option<&T> {
         self.inner.last()
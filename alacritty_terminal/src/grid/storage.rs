@@ -12,6 +12,13 @@ use crate::index::Line;
 /// Maximum number of buffered lines outside of the grid for performance optimization.
 const MAX_CACHE_SIZE: usize = 1_000;
 
+/// Size of a [`Row`] in qwords.
+///
+/// Prompt marks are stored inline, since rows are moved around by rotation, scrolling regions and
+/// reflow, which a separate table would have to replicate. The extra qword is negligible compared
+/// to the cells of a row.
+const ROW_QWORDS: usize = 5;
+
 /// A ring buffer for optimizing indexing and rotation.
 ///
 /// The [`Storage::rotate`] and [`Storage::rotate_down`] functions are fast modular additions on
@@ -151,7 +158,7 @@ impl<T> Storage<T> {
     /// instructions. This implementation achieves the swap in only 8 movups
     /// instructions.
     pub fn swap(&mut self, a: Line, b: Line) {
-        debug_assert_eq!(mem::size_of::<Row<T>>(), mem::size_of::<usize>() * 4);
+        debug_assert_eq!(mem::size_of::<Row<T>>(), mem::size_of::<usize>() * ROW_QWORDS);
 
         let a = self.compute_index(a);
         let b = self.compute_index(b);
@@ -167,7 +174,7 @@ impl<T> Storage<T> {
             //
             // The optimizer unrolls this loop and vectorizes it.
             let mut tmp: MaybeUninit<usize>;
-            for i in 0..4 {
+            for i in 0..ROW_QWORDS as isize {
                 tmp = *a_ptr.offset(i);
                 *a_ptr.offset(i) = *b_ptr.offset(i);
                 *b_ptr.offset(i) = tmp;
@@ -202,6 +209,16 @@ impl<T> Storage<T> {
         self.zero = 0;
     }
 
+    /// Check that the storage matches the grid dimensions, after it was deserialized.
+    #[cfg(feature = "serde")]
+    pub(crate) fn is_consistent(&self, lines: usize, columns: usize) -> bool {
+        self.visible_lines == lines
+            && self.len >= lines
+            && self.len <= self.inner.len()
+            && self.zero < self.inner.len()
+            && self.inner.iter().all(|row| row.len() == columns && row.occ <= columns)
+    }
+
     /// Remove all rows from storage.
     #[inline]
     pub fn take_all(&mut self) -> Vec<Row<T>> {
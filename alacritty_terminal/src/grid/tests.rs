@@ -54,6 +54,46 @@ fn scroll_up() {
     assert_eq!(grid[Line(9)].occ, 0);
 }
 
+// Scroll up below fixed lines moves the lines leaving the region into history.
+#[test]
+fn scroll_up_with_history_below_fixed_lines() {
+    let mut grid = Grid::<usize>::new(6, 1, 10);
+    for i in 0..6 {
+        grid[Line(i as i32)][Column(0)] = i + 1;
+    }
+
+    // Fixed lines `1` at the top and `6` at the bottom.
+    grid.scroll_up_with_history::<usize>(&(Line(1)..Line(5)), 3, true);
+
+    assert_eq!(grid.history_size(), 3);
+    assert_eq!(grid[Line(-3)][Column(0)], 2);
+    assert_eq!(grid[Line(-2)][Column(0)], 3);
+    assert_eq!(grid[Line(-1)][Column(0)], 4);
+    assert_eq!(grid[Line(0)][Column(0)], 1);
+    assert_eq!(grid[Line(1)][Column(0)], 5);
+    assert_eq!(grid[Line(2)][Column(0)], 0);
+    assert_eq!(grid[Line(3)][Column(0)], 0);
+    assert_eq!(grid[Line(4)][Column(0)], 0);
+    assert_eq!(grid[Line(5)][Column(0)], 6);
+}
+
+// Scroll up at the top of the screen can skip history.
+#[test]
+fn scroll_up_without_history() {
+    let mut grid = Grid::<usize>::new(4, 1, 10);
+    for i in 0..4 {
+        grid[Line(i as i32)][Column(0)] = i + 1;
+    }
+
+    grid.scroll_up_with_history::<usize>(&(Line(0)..Line(3)), 1, false);
+
+    assert_eq!(grid.history_size(), 0);
+    assert_eq!(grid[Line(0)][Column(0)], 2);
+    assert_eq!(grid[Line(1)][Column(0)], 3);
+    assert_eq!(grid[Line(2)][Column(0)], 0);
+    assert_eq!(grid[Line(3)][Column(0)], 4);
+}
+
 // Scroll down moves lines downward.
 #[test]
 fn scroll_down() {
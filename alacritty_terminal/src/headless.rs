@@ -0,0 +1,432 @@
+//! Terminal emulation without a window or PTY.
+//!
+//! This allows embedding the terminal emulation into applications which manage the child process
+//! themselves, like terminal multiplexers. Instead of a PTY, the terminal is driven by an
+//! arbitrary [`Read`]/[`Write`] pair and its state can be inspected through [`Term`].
+//!
+//! This module is only available with the `headless` feature.
+//!
+//! ```
+//! use alacritty_terminal::event::VoidListener;
+//! use alacritty_terminal::headless::Builder;
+//! use alacritty_terminal::term::test::TermSize;
+//!
+//! let output = &b"hello\r\nworld"[..];
+//! let size = TermSize::new(80, 24);
+//! let mut terminal = Builder::new().build(&size, VoidListener, output, Vec::new());
+//! terminal.run().unwrap();
+//!
+//! let text: String = terminal.renderable_content().display_iter.map(|cell| cell.c).collect();
+//! assert!(text.starts_with("hello"));
+//! ```
+
+use std::io::{self, ErrorKind, Read, Write};
+use std::mem;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Instant;
+
+use crate::event::{Event, EventListener, WindowSize};
+use crate::grid::Dimensions;
+use crate::term::color::Colors;
+use crate::term::osc::OscParser;
+use crate::term::{Config, RenderableContent, Term};
+use crate::vte::ansi::{self, NamedColor, Rgb};
+
+/// Bytes read from the reader at once.
+const READ_BUFFER_SIZE: usize = 0x1_0000;
+
+/// Default colors of the first 16 palette entries, matching XTerm.
+const ANSI_COLORS: [u32; 16] = [
+    0x000000, 0xcd0000, 0x00cd00, 0xcdcd00, 0x0000ee, 0xcd00cd, 0x00cdcd, 0xe5e5e5, 0x7f7f7f,
+    0xff0000, 0x00ff00, 0xffff00, 0x5c5cff, 0xff00ff, 0x00ffff, 0xffffff,
+];
+
+/// Factor for the computation of dim colors.
+const DIM_FACTOR: f32 = 0.66;
+
+/// Builder for a [`Headless`] terminal.
+pub struct Builder {
+    config: Config,
+    colors: Colors,
+    cell_width: u16,
+    cell_height: u16,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self { config: Default::default(), colors: default_colors(), cell_width: 0, cell_height: 0 }
+    }
+}
+
+impl Builder {
+    /// Create a builder with the default configuration and XTerm's default colors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the configuration of the terminal.
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Set the colors reported for palette entries which weren't changed by the application.
+    ///
+    /// Requests for unset colors are ignored.
+    pub fn colors(mut self, colors: Colors) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Set the cell size in pixels reported to the application.
+    ///
+    /// Defaults to `0`, which reports the text area size in pixels as unknown.
+    pub fn cell_size(mut self, width: u16, height: u16) -> Self {
+        self.cell_width = width;
+        self.cell_height = height;
+        self
+    }
+
+    /// Create the terminal.
+    ///
+    /// The `reader` provides the output of the application running inside the terminal, while
+    /// the `writer` receives the terminal's replies to escape sequences and all input written
+    /// using [`Headless::write`].
+    pub fn build<T, R, W, D>(
+        self,
+        dimensions: &D,
+        listener: T,
+        reader: R,
+        writer: W,
+    ) -> Headless<T, R, W>
+    where
+        T: EventListener,
+        R: Read,
+        W: Write,
+        D: Dimensions,
+    {
+        let listener = HeadlessListener { listener, requests: Default::default() };
+        let requests = listener.requests.clone();
+
+        Headless {
+            term: Term::new(self.config, dimensions, listener),
+            parser: ansi::Processor::new(),
+            osc_parser: OscParser::new(),
+            buffer: vec![0; READ_BUFFER_SIZE],
+            colors: self.colors,
+            cell_width: self.cell_width,
+            cell_height: self.cell_height,
+            requests,
+            reader,
+            writer,
+        }
+    }
+}
+
+/// Terminal driven by a [`Read`]/[`Write`] pair.
+pub struct Headless<T, R, W> {
+    term: Term<HeadlessListener<T>>,
+    parser: ansi::Processor,
+    osc_parser: OscParser,
+    buffer: Vec<u8>,
+    colors: Colors,
+    cell_width: u16,
+    cell_height: u16,
+    requests: Arc<Mutex<Vec<Event>>>,
+    reader: R,
+    writer: W,
+}
+
+impl<T, R, W> Headless<T, R, W>
+where
+    T: EventListener,
+    R: Read,
+    W: Write,
+{
+    /// Process a single chunk of data from the reader.
+    ///
+    /// Returns the number of bytes processed, which is `0` once the reader is exhausted.
+    pub fn read(&mut self) -> io::Result<usize> {
+        loop {
+            match self.reader.read(&mut self.buffer) {
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+                Ok(0) => {
+                    // Don't lose the content of unfinished synchronized updates.
+                    self.stop_sync()?;
+                    return Ok(0);
+                },
+                Ok(read) => {
+                    self.expire_sync();
+                    let bytes = &self.buffer[..read];
+                    self.osc_parser.advance(&mut self.parser, &mut self.term, bytes);
+                    self.flush_replies()?;
+                    return Ok(read);
+                },
+            }
+        }
+    }
+
+    /// Process data from the reader until it is exhausted.
+    pub fn run(&mut self) -> io::Result<()> {
+        while self.read()? != 0 {}
+        Ok(())
+    }
+
+    /// Process data which was not received through the reader.
+    pub fn advance(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.expire_sync();
+        self.osc_parser.advance(&mut self.parser, &mut self.term, bytes);
+        self.flush_replies()
+    }
+
+    /// Deadline of the active synchronized update.
+    ///
+    /// Content received during a synchronized update is only applied to the terminal once the
+    /// update ends. Without new data, [`Self::stop_sync`] must be called to apply it once the
+    /// deadline has passed.
+    pub fn sync_timeout(&self) -> Option<Instant> {
+        self.parser.sync_timeout().sync_timeout()
+    }
+
+    /// End the active synchronized update, applying all of its content.
+    pub fn stop_sync(&mut self) -> io::Result<()> {
+        if self.sync_timeout().is_none() {
+            return Ok(());
+        }
+
+        self.osc_parser.stop_sync(&mut self.parser, &mut self.term);
+        self.flush_replies()
+    }
+
+    /// Write input for the application to the writer.
+    pub fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.writer.write_all(bytes)?;
+        self.writer.flush()
+    }
+
+    /// Resize the terminal grid.
+    pub fn resize<D: Dimensions>(&mut self, dimensions: D) {
+        self.term.resize(dimensions);
+    }
+
+    /// Snapshot of the visible terminal content.
+    pub fn renderable_content(&self) -> RenderableContent<'_> {
+        self.term.renderable_content()
+    }
+
+    #[inline]
+    pub fn term(&self) -> &Term<HeadlessListener<T>> {
+        &self.term
+    }
+
+    #[inline]
+    pub fn term_mut(&mut self) -> &mut Term<HeadlessListener<T>> {
+        &mut self.term
+    }
+
+    #[inline]
+    pub fn writer(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// End the active synchronized update if its deadline has passed.
+    fn expire_sync(&mut self) {
+        if self.sync_timeout().is_some_and(|timeout| timeout <= Instant::now()) {
+            self.osc_parser.stop_sync(&mut self.parser, &mut self.term);
+        }
+    }
+
+    /// Write pending replies to escape sequences.
+    fn flush_replies(&mut self) -> io::Result<()> {
+        let requests =
+            mem::take(&mut *self.requests.lock().unwrap_or_else(PoisonError::into_inner));
+
+        let mut replies = String::new();
+        for request in requests {
+            match request {
+                Event::PtyWrite(text) => replies.push_str(&text),
+                Event::ColorRequest(index, format) => {
+                    if let Some(color) = self.term.colors()[index].or(self.colors[index]) {
+                        replies.push_str(&format(color));
+                    }
+                },
+                Event::TextAreaSizeRequest(format) => {
+                    let window_size = WindowSize {
+                        num_lines: self.term.screen_lines() as u16,
+                        num_cols: self.term.columns() as u16,
+                        cell_width: self.cell_width,
+                        cell_height: self.cell_height,
+                    };
+                    replies.push_str(&format(window_size));
+                },
+                _ => (),
+            }
+        }
+
+        if replies.is_empty() {
+            return Ok(());
+        }
+
+        self.write(replies.as_bytes())
+    }
+}
+
+/// XTerm's default colors.
+///
+/// The cursor color is left unset, since it defaults to the color of the cell below it.
+fn default_colors() -> Colors {
+    let mut colors = Colors::default();
+
+    for (index, color) in ANSI_COLORS.iter().enumerate() {
+        let [_, r, g, b] = color.to_be_bytes();
+        colors[index] = Some(Rgb { r, g, b });
+    }
+
+    // Color cube.
+    let component = |value: usize| if value == 0 { 0 } else { value as u8 * 40 + 55 };
+    for index in 16..232 {
+        let cube_index = index - 16;
+        let r = component(cube_index / 36);
+        let g = component(cube_index / 6 % 6);
+        let b = component(cube_index % 6);
+        colors[index] = Some(Rgb { r, g, b });
+    }
+
+    // Grayscale ramp.
+    for index in 232..256 {
+        let value = (index - 232) as u8 * 10 + 8;
+        colors[index] = Some(Rgb { r: value, g: value, b: value });
+    }
+
+    let foreground = colors[NamedColor::White];
+    colors[NamedColor::Foreground] = foreground;
+    colors[NamedColor::Background] = colors[NamedColor::Black];
+    colors[NamedColor::BrightForeground] = colors[NamedColor::BrightWhite];
+    colors[NamedColor::DimForeground] = foreground.map(|color| color * DIM_FACTOR);
+    for index in 0..8 {
+        let dim = NamedColor::DimBlack as usize + index;
+        colors[dim] = colors[index].map(|color| color * DIM_FACTOR);
+    }
+
+    colors
+}
+
+/// Event listener of a [`Headless`] terminal.
+///
+/// Replies to escape sequences are written to the terminal's writer, all other events are
+/// forwarded to the wrapped listener.
+pub struct HeadlessListener<T> {
+    listener: T,
+    requests: Arc<Mutex<Vec<Event>>>,
+}
+
+impl<T> HeadlessListener<T> {
+    #[inline]
+    pub fn inner(&self) -> &T {
+        &self.listener
+    }
+}
+
+impl<T: EventListener> EventListener for HeadlessListener<T> {
+    fn send_event(&self, event: Event) {
+        match event {
+            Event::PtyWrite(_) | Event::ColorRequest(..) | Event::TextAreaSizeRequest(_) => {
+                self.requests.lock().unwrap_or_else(PoisonError::into_inner).push(event)
+            },
+            event => self.listener.send_event(event),
+        }
+    }
+
+    fn wants_committed_lines(&self) -> bool {
+        self.listener.wants_committed_lines()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::event::VoidListener;
+    use crate::index::{Column, Line, Point};
+    use crate::term::test::TermSize;
+
+    #[test]
+    fn read_until_exhausted() {
+        let output = &b"hello\r\nworld"[..];
+        let size = TermSize::new(10, 5);
+        let mut terminal = Builder::new().build(&size, VoidListener, output, Vec::new());
+
+        terminal.run().unwrap();
+
+        let grid = terminal.term().grid();
+        assert_eq!(grid[Line(0)][Column(4)].c, 'o');
+        assert_eq!(grid[Line(1)][Column(0)].c, 'w');
+        assert_eq!(grid.cursor.point, Point::new(Line(1), Column(5)));
+    }
+
+    #[test]
+    fn replies_written() {
+        let size = TermSize::new(10, 5);
+        let mut terminal = Builder::new().build(&size, VoidListener, io::empty(), Vec::new());
+
+        // Request the cursor position.
+        terminal.advance(b"\x1b[2;3H\x1b[6n").unwrap();
+        terminal.write(b"input").unwrap();
+
+        assert_eq!(terminal.writer(), b"\x1b[2;3Rinput");
+    }
+
+    #[test]
+    fn color_and_size_requests() {
+        let size = TermSize::new(10, 5);
+        let mut terminal =
+            Builder::new().cell_size(8, 16).build(&size, VoidListener, io::empty(), Vec::new());
+
+        // Request the default and a changed color, followed by the text area size.
+        terminal.advance(b"\x1b]4;1;?\x07").unwrap();
+        terminal.advance(b"\x1b]4;2;#010203\x07\x1b]4;2;?\x07").unwrap();
+        terminal.advance(b"\x1b[14t").unwrap();
+
+        let replies = String::from_utf8(terminal.writer().clone()).unwrap();
+        assert_eq!(
+            replies,
+            "\x1b]4;1;rgb:cdcd/0000/0000\x07\x1b]4;2;rgb:0101/0202/0303\x07\x1b[4;80;80t"
+        );
+    }
+
+    #[test]
+    fn default_palette() {
+        let colors = default_colors();
+        assert_eq!(colors[16], Some(Rgb { r: 0, g: 0, b: 0 }));
+        assert_eq!(colors[231], Some(Rgb { r: 255, g: 255, b: 255 }));
+        assert_eq!(colors[232], Some(Rgb { r: 8, g: 8, b: 8 }));
+        assert_eq!(colors[NamedColor::Cursor], None);
+    }
+
+    #[test]
+    fn synchronized_update() {
+        let size = TermSize::new(10, 5);
+        let mut terminal = Builder::new().build(&size, VoidListener, io::empty(), Vec::new());
+
+        terminal.advance(b"\x1b[?2026ha").unwrap();
+        assert!(terminal.sync_timeout().is_some());
+        assert_eq!(terminal.term().grid()[Line(0)][Column(0)].c, ' ');
+
+        terminal.advance(b"\x1b[?2026l").unwrap();
+        assert!(terminal.sync_timeout().is_none());
+        assert_eq!(terminal.term().grid()[Line(0)][Column(0)].c, 'a');
+    }
+
+    #[test]
+    fn unfinished_synchronized_update() {
+        let output = &b"\x1b[?2026ha"[..];
+        let size = TermSize::new(10, 5);
+        let mut terminal = Builder::new().build(&size, VoidListener, output, Vec::new());
+
+        terminal.run().unwrap();
+
+        assert!(terminal.sync_timeout().is_none());
+        assert_eq!(terminal.term().grid()[Line(0)][Column(0)].c, 'a');
+    }
+}
@@ -7,6 +7,8 @@
 pub mod event;
 pub mod event_loop;
 pub mod grid;
+#[cfg(feature = "headless")]
+pub mod headless;
 pub mod index;
 pub mod selection;
 pub mod sync;
@@ -310,8 +310,15 @@ impl Selection {
             }
         }
 
-        let start = term.semantic_search_left(start);
-        let end = term.semantic_search_right(end);
+        // Prefer semantic patterns over escape char separated words.
+        let start = match term.semantic_pattern_match(start) {
+            Some(semantic_match) => *semantic_match.start(),
+            None => term.semantic_search_left(start),
+        };
+        let end = match term.semantic_pattern_match(end) {
+            Some(semantic_match) => *semantic_match.end(),
+            None => term.semantic_search_right(end),
+        };
 
         SelectionRange { start, end, is_block: false }
     }
@@ -0,0 +1,115 @@
+//! Enumeration of OSC 8 hyperlinks.
+
+use std::ops::RangeInclusive;
+
+use crate::index::Point;
+use crate::term::cell::Hyperlink;
+use crate::term::Term;
+
+/// Hyperlink visible in the viewport.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewportHyperlink {
+    /// Hyperlink with its ID and URI.
+    pub hyperlink: Hyperlink,
+
+    /// Visible fragments of the hyperlink, in the order they appear in.
+    pub ranges: Vec<RangeInclusive<Point>>,
+}
+
+impl<T> Term<T> {
+    /// All hyperlinks in the viewport.
+    ///
+    /// Fragments of a hyperlink sharing the same ID and URI are grouped together, with hyperlinks
+    /// ordered by their first appearance.
+    pub fn hyperlinks_in_viewport(&self) -> Vec<ViewportHyperlink> {
+        let mut hyperlinks: Vec<ViewportHyperlink> = Vec::new();
+
+        // Index of the hyperlink at the previous cell, to extend its last fragment.
+        let mut previous: Option<usize> = None;
+
+        for cell in self.grid.display_iter() {
+            let hyperlink = match cell.hyperlink() {
+                Some(hyperlink) => hyperlink,
+                None => {
+                    previous = None;
+                    continue;
+                },
+            };
+
+            let index = match previous.filter(|&index| hyperlinks[index].hyperlink == hyperlink) {
+                Some(index) => {
+                    if let Some(range) = hyperlinks[index].ranges.last_mut() {
+                        *range = *range.start()..=cell.point;
+                    }
+                    index
+                },
+                None => {
+                    let index = hyperlinks.iter().position(|link| link.hyperlink == hyperlink);
+                    let index = index.unwrap_or_else(|| {
+                        hyperlinks.push(ViewportHyperlink { hyperlink, ranges: Vec::new() });
+                        hyperlinks.len() - 1
+                    });
+                    hyperlinks[index].ranges.push(cell.point..=cell.point);
+                    index
+                },
+            };
+
+            previous = Some(index);
+        }
+
+        hyperlinks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::event::VoidListener;
+    use crate::grid::Scroll;
+    use crate::index::{Column, Line};
+    use crate::term::test::TermSize;
+    use crate::term::Config;
+    use crate::vte::ansi::Handler;
+
+    #[test]
+    fn viewport_hyperlinks() {
+        let size = TermSize::new(5, 3);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+
+        let foo = Hyperlink::new(Some("1"), String::from("https://foo.example"));
+        let bar = Hyperlink::new(None::<String>, String::from("mailto:bar@example.org"));
+
+        // Wrap the first hyperlink across lines, with a second fragment after `bar`.
+        term.set_hyperlink(Some(foo.clone().into()));
+        term.goto(0, 3);
+        for c in "foofo".chars() {
+            term.input(c);
+        }
+        term.set_hyperlink(Some(bar.clone().into()));
+        term.input('b');
+        term.set_hyperlink(None);
+        term.input(' ');
+        term.set_hyperlink(Some(foo.clone().into()));
+        term.input('o');
+        term.set_hyperlink(None);
+
+        let point = |line, column| Point::new(Line(line), Column(column));
+        assert_eq!(term.hyperlinks_in_viewport(), vec![
+            ViewportHyperlink {
+                hyperlink: foo.clone(),
+                ranges: vec![point(0, 3)..=point(1, 2), point(2, 0)..=point(2, 0)],
+            },
+            ViewportHyperlink { hyperlink: bar, ranges: vec![point(1, 3)..=point(1, 3)] },
+        ]);
+
+        // Hyperlinks scrolled out of the viewport are ignored.
+        for _ in 0..3 {
+            term.linefeed();
+        }
+        assert!(term.hyperlinks_in_viewport().is_empty());
+
+        term.scroll_display(Scroll::Top);
+        assert_eq!(term.hyperlinks_in_viewport()[0].hyperlink, foo);
+    }
+}
@@ -1,8 +1,9 @@
 //! Exports the `Term` type which is a high-level API for the Grid.
 
-use std::ops::{Index, IndexMut, Range};
+use std::cell::RefCell;
+use std::ops::{Index, Range};
 use std::sync::Arc;
-use std::{cmp, mem, ptr, slice, str};
+use std::{cmp, mem, ptr, slice, str, vec};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -10,16 +11,20 @@ use serde::{Deserialize, Serialize};
 use base64::engine::general_purpose::STANDARD as Base64;
 use base64::Engine;
 use bitflags::bitflags;
-use log::{debug, trace};
+use log::{debug, error, trace};
 use unicode_width::UnicodeWidthChar;
 
 use crate::event::{Event, EventListener};
-use crate::grid::{Dimensions, Grid, GridIterator, Scroll};
-use crate::index::{self, Boundary, Column, Direction, Line, Point, Side};
+#[cfg(feature = "serde")]
+use crate::grid::Cursor;
+use crate::grid::{Dimensions, Grid, GridIterator, Indexed, Scroll};
+use crate::index::{Boundary, Column, Direction, Line, Point, Side};
 use crate::selection::{Selection, SelectionRange, SelectionType};
 use crate::term::cell::{Cell, Flags, LineLength};
 use crate::term::color::Colors;
+use crate::term::search::RegexSearch;
 use crate::vi_mode::{ViModeCursor, ViMotion};
+use crate::vte::ansi::cursor_icon::CursorIcon;
 use crate::vte::ansi::{
     self, Attr, CharsetIndex, Color, CursorShape, CursorStyle, Handler, Hyperlink, KeyboardModes,
     KeyboardModesApplyBehavior, NamedColor, NamedMode, NamedPrivateMode, PrivateMode, Rgb,
@@ -28,6 +33,9 @@ use crate::vte::ansi::{
 
 pub mod cell;
 pub mod color;
+pub mod hyperlink;
+pub mod osc;
+pub mod prompt;
 pub mod search;
 
 /// Minimum number of columns.
@@ -50,8 +58,15 @@ const KEYBOARD_MODE_STACK_MAX_DEPTH: usize = TITLE_STACK_MAX_DEPTH;
 /// Default tab interval, corresponding to terminfo `it` value.
 const INITIAL_TABSTOPS: usize = 8;
 
+/// Private mode for grapheme cluster segmentation.
+const GRAPHEME_CLUSTER_MODE: u16 = 2027;
+
+/// Zero width joiner, combining the adjacent characters into a single grapheme.
+const ZERO_WIDTH_JOINER: char = '\u{200d}';
+
 bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     pub struct TermMode: u32 {
         const NONE                    = 0;
         const SHOW_CURSOR             = 1;
@@ -77,6 +92,7 @@ bitflags! {
         const REPORT_ALTERNATE_KEYS   = 1 << 20;
         const REPORT_ALL_KEYS_AS_ESC  = 1 << 21;
         const REPORT_ASSOCIATED_TEXT  = 1 << 22;
+        const GRAPHEME_CLUSTERS       = 1 << 23;
         const MOUSE_MODE              = Self::MOUSE_REPORT_CLICK.bits() | Self::MOUSE_MOTION.bits() | Self::MOUSE_DRAG.bits();
         const KITTY_KEYBOARD_PROTOCOL = Self::DISAMBIGUATE_ESC_CODES.bits()
                                       | Self::REPORT_EVENT_TYPES.bits()
@@ -119,6 +135,32 @@ impl Default for TermMode {
     }
 }
 
+/// Compile semantic selection patterns, skipping invalid ones.
+fn compile_semantic_patterns(patterns: &[String]) -> Vec<RegexSearch> {
+    patterns
+        .iter()
+        .filter_map(|pattern| match RegexSearch::new(pattern) {
+            Ok(regex) => Some(regex),
+            Err(err) => {
+                error!("Invalid semantic selection pattern {pattern:?}: {err}");
+                None
+            },
+        })
+        .collect()
+}
+
+/// Check if a character is a regional indicator symbol, used to build flags.
+#[inline]
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1f1e6}'..='\u{1f1ff}').contains(&c)
+}
+
+/// Check if a character is an emoji skin tone modifier.
+#[inline]
+fn is_emoji_modifier(c: char) -> bool {
+    ('\u{1f3fb}'..='\u{1f3ff}').contains(&c)
+}
+
 /// Convert a terminal point to a viewport relative point.
 #[inline]
 pub fn point_to_viewport(display_offset: usize, point: Point) -> Option<Point<usize>> {
@@ -183,6 +225,36 @@ pub enum TermDamage<'a> {
     Partial(TermDamageIterator<'a>),
 }
 
+impl TermDamage<'_> {
+    /// Copy the damage, so it can be processed without borrowing the terminal.
+    pub fn to_owned(&self) -> DamageSnapshot {
+        match self {
+            Self::Full => DamageSnapshot { full: true, lines: Vec::new() },
+            Self::Partial(lines) => DamageSnapshot { full: false, lines: lines.clone().collect() },
+        }
+    }
+}
+
+/// Owned copy of the terminal damage.
+///
+/// Unlike [`TermDamage`], this doesn't borrow the terminal, allowing renderers to release the
+/// terminal lock before processing the damage.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DamageSnapshot {
+    /// Whether the entire terminal is damaged.
+    pub full: bool,
+
+    /// Damaged lines in the viewport, empty when the entire terminal is damaged.
+    pub lines: Vec<LineDamageBounds>,
+}
+
+impl DamageSnapshot {
+    /// Check if any part of the terminal is damaged.
+    pub fn is_damaged(&self) -> bool {
+        self.full || !self.lines.is_empty()
+    }
+}
+
 /// Iterator over the terminal's viewport damaged lines.
 #[derive(Clone, Debug)]
 pub struct TermDamageIterator<'a> {
@@ -306,6 +378,9 @@ pub struct Term<T> {
     /// Current style of the cursor.
     cursor_style: Option<CursorStyle>,
 
+    /// Mouse cursor icon requested by the application.
+    mouse_cursor_icon: Option<CursorIcon>,
+
     /// Proxy for sending events to the event loop.
     event_proxy: T,
 
@@ -327,6 +402,15 @@ pub struct Term<T> {
 
     /// Config directly for the terminal.
     config: Config,
+
+    /// Compiled semantic selection patterns.
+    ///
+    /// Matching requires mutable access to the regex caches, while selections are resolved
+    /// through shared references during rendering.
+    semantic_patterns: RefCell<Vec<RegexSearch>>,
+
+    /// Drop replies to the PTY, used while processing injected output.
+    pub(crate) suppress_replies: bool,
 }
 
 /// Configuration options for the [`Term`].
@@ -335,6 +419,9 @@ pub struct Config {
     /// The maximum amount of scrolling history.
     pub scrolling_history: usize,
 
+    /// The maximum amount of scrolling history on the alternate screen.
+    pub alt_screen_history: usize,
+
     /// Default cursor style to reset the cursor to.
     pub default_cursor_style: CursorStyle,
 
@@ -346,22 +433,67 @@ pub struct Config {
     /// The default value is [`SEMANTIC_ESCAPE_CHARS`].
     pub semantic_escape_chars: String,
 
+    /// Regexes which are selected in their entirety by semantic selection.
+    ///
+    /// Patterns are tried in order, before falling back to the semantic escape chars.
+    pub semantic_patterns: Vec<String>,
+
     /// Whether to enable kitty keyboard protocol.
     pub kitty_keyboard: bool,
 
     /// OSC52 support mode.
     pub osc52: Osc52,
+
+    /// Scrollback behavior for lines leaving the scrolling region.
+    pub scroll_region_history: ScrollRegionHistory,
+
+    /// Whether text concealed with SGR 8 is copied, rather than replaced with spaces.
+    pub copy_hidden: bool,
+
+    /// Whether to respond to XTWINOPS requests for the text area size.
+    pub window_reports: bool,
+
+    /// Whether applications can change the window title.
+    pub titles: bool,
+
+    /// Whether hyperlinks set using OSC 8 are stored.
+    pub hyperlinks: bool,
+
+    /// Whether applications can change the color palette.
+    pub dynamic_colors: bool,
+
+    /// Whether applications can change the mouse cursor icon using OSC 22.
+    pub mouse_cursor_icons: bool,
+
+    /// Whether applications can send desktop notifications using OSC 9 and OSC 777.
+    pub notifications: bool,
+
+    /// Alpha of the background reported in response to OSC 11 queries.
+    ///
+    /// When set, the background color is reported using the `rgba:` form.
+    pub background_alpha: Option<u16>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             scrolling_history: 10000,
+            alt_screen_history: 0,
             semantic_escape_chars: SEMANTIC_ESCAPE_CHARS.to_owned(),
+            semantic_patterns: Vec::new(),
             default_cursor_style: Default::default(),
             vi_mode_cursor_style: Default::default(),
             kitty_keyboard: Default::default(),
             osc52: Default::default(),
+            scroll_region_history: Default::default(),
+            copy_hidden: true,
+            window_reports: true,
+            titles: true,
+            hyperlinks: true,
+            dynamic_colors: true,
+            notifications: true,
+            mouse_cursor_icons: true,
+            background_alpha: None,
         }
     }
 }
@@ -384,6 +516,67 @@ pub enum Osc52 {
     CopyPaste,
 }
 
+/// Scrollback behavior for lines scrolled out of the scrolling region (DECSTBM).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "lowercase"))]
+pub enum ScrollRegionHistory {
+    /// Lines are saved when the scrolling region starts at the top of the screen.
+    ///
+    /// This keeps the history of applications with a status line at the bottom, like tmux.
+    #[default]
+    TopAnchored,
+    /// Lines are only saved when the scrolling region covers the entire screen.
+    FullScreen,
+    /// Lines leaving the top of any scrolling region are saved.
+    Always,
+}
+
+/// Serializable terminal content, including the scrollback history.
+///
+/// This allows moving a terminal to another process, see [`Term::state`] and
+/// [`Term::restore_state`]. Character sets, tab stops, dynamic colors and the title and keyboard
+/// mode stacks are not preserved.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TermState {
+    grid: Grid<Cell>,
+    inactive_grid: Grid<Cell>,
+    cursor: Cursor<Cell>,
+    saved_cursor: Cursor<Cell>,
+    inactive_cursor: Cursor<Cell>,
+    inactive_saved_cursor: Cursor<Cell>,
+    mode: TermMode,
+    title: Option<String>,
+}
+
+#[cfg(feature = "serde")]
+impl TermState {
+    /// Check that both grids and their cursors have matching dimensions.
+    fn is_consistent(&self) -> bool {
+        self.grid.columns() == self.inactive_grid.columns()
+            && self.grid.screen_lines() == self.inactive_grid.screen_lines()
+            && self.grid.is_consistent(&[&self.cursor, &self.saved_cursor])
+            && self
+                .inactive_grid
+                .is_consistent(&[&self.inactive_cursor, &self.inactive_saved_cursor])
+    }
+}
+
+/// Error restoring a [`TermState`] with inconsistent grids or cursors.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidTermState;
+
+#[cfg(feature = "serde")]
+impl std::fmt::Display for InvalidTermState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "inconsistent terminal state")
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for InvalidTermState {}
+
 impl<T> Term<T> {
     #[inline]
     pub fn scroll_display(&mut self, scroll: Scroll)
@@ -413,7 +606,7 @@ impl<T> Term<T> {
 
         let history_size = config.scrolling_history;
         let grid = Grid::new(num_lines, num_cols, history_size);
-        let inactive_grid = Grid::new(num_lines, num_cols, 0);
+        let inactive_grid = Grid::new(num_lines, num_cols, config.alt_screen_history);
 
         let tabs = TabStops::new(grid.columns());
 
@@ -422,7 +615,10 @@ impl<T> Term<T> {
         // Initialize terminal damage, covering the entire terminal upon launch.
         let damage = TermDamageState::new(num_cols, num_lines);
 
+        let semantic_patterns = RefCell::new(compile_semantic_patterns(&config.semantic_patterns));
+
         Term {
+            semantic_patterns,
             inactive_grid,
             scroll_region,
             event_proxy,
@@ -435,12 +631,14 @@ impl<T> Term<T> {
             active_charset: Default::default(),
             vi_mode_cursor: Default::default(),
             cursor_style: Default::default(),
+            mouse_cursor_icon: Default::default(),
             colors: color::Colors::default(),
             title_stack: Default::default(),
             is_focused: Default::default(),
             selection: Default::default(),
             title: Default::default(),
             mode: Default::default(),
+            suppress_replies: false,
         }
     }
 
@@ -485,6 +683,19 @@ impl<T> Term<T> {
         TermDamage::Partial(TermDamageIterator::new(&self.damage.lines, display_offset))
     }
 
+    /// Copy the terminal's damage information and reset it.
+    ///
+    /// This is equivalent to calling [`reset_damage`] after converting the [`damage`] using
+    /// [`TermDamage::to_owned`].
+    ///
+    /// [`damage`]: Self::damage
+    /// [`reset_damage`]: Self::reset_damage
+    pub fn take_damage(&mut self) -> DamageSnapshot {
+        let damage = self.damage().to_owned();
+        self.reset_damage();
+        damage
+    }
+
     /// Resets the terminal damage information.
     pub fn reset_damage(&mut self) {
         self.damage.reset(self.columns());
@@ -511,8 +722,22 @@ impl<T> Term<T> {
 
         if self.mode.contains(TermMode::ALT_SCREEN) {
             self.inactive_grid.update_history(self.config.scrolling_history);
+            self.grid.update_history(self.config.alt_screen_history);
         } else {
             self.grid.update_history(self.config.scrolling_history);
+            self.inactive_grid.update_history(self.config.alt_screen_history);
+        }
+
+        // Drop references to truncated scrollback history.
+        let topmost_line = self.topmost_line();
+        if self.selection.as_ref().is_some_and(|sel| sel.intersects_range(..topmost_line)) {
+            self.selection = None;
+        }
+        self.vi_mode_cursor.point.line = cmp::max(self.vi_mode_cursor.point.line, topmost_line);
+
+        if self.config.semantic_patterns != old_config.semantic_patterns {
+            *self.semantic_patterns.get_mut() =
+                compile_semantic_patterns(&self.config.semantic_patterns);
         }
 
         if self.config.kitty_keyboard != old_config.kitty_keyboard {
@@ -531,23 +756,25 @@ impl<T> Term<T> {
         let SelectionRange { start, end, .. } = selection_range;
 
         let mut res = String::new();
+        let conceal = !self.config.copy_hidden;
 
         match self.selection.as_ref() {
             Some(Selection { ty: SelectionType::Block, .. }) => {
+                let cols = start.column..end.column;
                 for line in (start.line.0..end.line.0).map(Line::from) {
-                    res += self
-                        .line_to_string(line, start.column..end.column, start.column.0 != 0)
-                        .trim_end();
+                    let wrapped_wide = start.column.0 != 0;
+                    res +=
+                        self.line_to_string(line, cols.clone(), wrapped_wide, conceal).trim_end();
                     res += "\n";
                 }
 
-                res += self.line_to_string(end.line, start.column..end.column, true).trim_end();
+                res += self.line_to_string(end.line, cols, true, conceal).trim_end();
             },
             Some(Selection { ty: SelectionType::Lines, .. }) => {
-                res = self.bounds_to_string(start, end) + "\n";
+                res = self.range_to_string(start, end, conceal) + "\n";
             },
             _ => {
-                res = self.bounds_to_string(start, end);
+                res = self.range_to_string(start, end, conceal);
             },
         }
 
@@ -556,13 +783,18 @@ impl<T> Term<T> {
 
     /// Convert range between two points to a String.
     pub fn bounds_to_string(&self, start: Point, end: Point) -> String {
+        self.range_to_string(start, end, false)
+    }
+
+    /// Convert range between two points to a String, optionally replacing hidden text with spaces.
+    fn range_to_string(&self, start: Point, end: Point, conceal: bool) -> String {
         let mut res = String::new();
 
         for line in (start.line.0..=end.line.0).map(Line::from) {
             let start_col = if line == start.line { start.column } else { Column(0) };
             let end_col = if line == end.line { end.column } else { self.last_column() };
 
-            res += &self.line_to_string(line, start_col..end_col, line == end.line);
+            res += &self.line_to_string(line, start_col..end_col, line == end.line, conceal);
         }
 
         res.strip_suffix('\n').map(str::to_owned).unwrap_or(res)
@@ -574,6 +806,7 @@ impl<T> Term<T> {
         line: Line,
         mut cols: Range<Column>,
         include_wrapped_wide: bool,
+        conceal: bool,
     ) -> String {
         let mut text = String::new();
 
@@ -602,7 +835,15 @@ impl<T> Term<T> {
                 tab_mode = true;
             }
 
-            if !cell.flags.intersects(Flags::WIDE_CHAR_SPACER | Flags::LEADING_WIDE_CHAR_SPACER) {
+            if conceal && cell.flags.contains(Flags::HIDDEN) {
+                // Replace concealed text with blanks, preserving its width.
+                if !cell.flags.contains(Flags::LEADING_WIDE_CHAR_SPACER) {
+                    text.push(' ');
+                }
+            } else if !cell
+                .flags
+                .intersects(Flags::WIDE_CHAR_SPACER | Flags::LEADING_WIDE_CHAR_SPACER)
+            {
                 // Push cells primary character.
                 text.push(cell.c);
 
@@ -641,6 +882,35 @@ impl<T> Term<T> {
         RenderableContent::new(self)
     }
 
+    /// Terminal content required for updating cells damaged since the last
+    /// [`Term::reset_damage`] call.
+    ///
+    /// This does not reset the damage, so [`Term::reset_damage`] should be called once the update
+    /// is complete.
+    pub fn damaged_content(&mut self) -> DamagedContent<'_>
+    where
+        T: EventListener,
+    {
+        let damage = match self.damage() {
+            TermDamage::Full => None,
+            TermDamage::Partial(damage) => Some(damage.collect::<Vec<_>>()),
+        };
+        let full = damage.is_none();
+
+        // Treat full damage like every visible line being damaged.
+        let lines = damage.unwrap_or_else(|| {
+            let last_column = self.last_column().0;
+            let screen_lines = self.screen_lines();
+            (0..screen_lines).map(|line| LineDamageBounds::new(line, 0, last_column)).collect()
+        });
+
+        DamagedContent {
+            content: RenderableContent::new(self),
+            cells: DamagedCells::new(&self.grid, lines),
+            full,
+        }
+    }
+
     /// Access to the raw grid data structure.
     pub fn grid(&self) -> &Grid<Cell> {
         &self.grid
@@ -704,6 +974,74 @@ impl<T> Term<T> {
         self.damage.resize(num_cols, num_lines);
     }
 
+    /// Serializable copy of the terminal content.
+    #[cfg(feature = "serde")]
+    pub fn state(&self) -> TermState {
+        TermState {
+            grid: self.grid.clone(),
+            inactive_grid: self.inactive_grid.clone(),
+            cursor: self.grid.cursor.clone(),
+            saved_cursor: self.grid.saved_cursor.clone(),
+            inactive_cursor: self.inactive_grid.cursor.clone(),
+            inactive_saved_cursor: self.inactive_grid.saved_cursor.clone(),
+            mode: self.mode,
+            title: self.title.clone(),
+        }
+    }
+
+    /// Replace the terminal content with a state created by [`Term::state`].
+    ///
+    /// The content is resized to the current dimensions of the terminal. Since the state might
+    /// come from another process, it is validated first and the terminal is left untouched if
+    /// its grids or cursors are inconsistent.
+    #[cfg(feature = "serde")]
+    pub fn restore_state(&mut self, state: TermState) -> Result<(), InvalidTermState>
+    where
+        T: EventListener,
+    {
+        if !state.is_consistent() {
+            return Err(InvalidTermState);
+        }
+
+        let size = test::TermSize::new(self.columns(), self.screen_lines());
+
+        self.grid = state.grid;
+        self.grid.cursor = state.cursor;
+        self.grid.saved_cursor = state.saved_cursor;
+        self.inactive_grid = state.inactive_grid;
+        self.inactive_grid.cursor = state.inactive_cursor;
+        self.inactive_grid.saved_cursor = state.inactive_saved_cursor;
+        self.mode = state.mode;
+        self.title = state.title;
+
+        // Use the history limits of this terminal's configuration.
+        if self.mode.contains(TermMode::ALT_SCREEN) {
+            self.inactive_grid.update_history(self.config.scrolling_history);
+            self.grid.update_history(self.config.alt_screen_history);
+        } else {
+            self.grid.update_history(self.config.scrolling_history);
+            self.inactive_grid.update_history(self.config.alt_screen_history);
+        }
+
+        self.tabs = TabStops::new(self.grid.columns());
+        self.scroll_region = Line(0)..Line(self.grid.screen_lines() as i32);
+        self.selection = None;
+        self.vi_mode_cursor = ViModeCursor::new(self.grid.cursor.point);
+        self.keyboard_mode_stack.clear();
+        self.inactive_keyboard_mode_stack.clear();
+
+        self.resize(size);
+        self.mark_fully_damaged();
+
+        let title_event = match &self.title {
+            Some(title) => Event::Title(title.clone()),
+            None => Event::ResetTitle,
+        };
+        self.event_proxy.send_event(title_event);
+
+        Ok(())
+    }
+
     /// Active terminal modes.
     #[inline]
     pub fn mode(&self) -> &TermMode {
@@ -719,8 +1057,9 @@ impl<T> Term<T> {
             // Drop information about the primary screens saved cursor.
             self.grid.saved_cursor = self.grid.cursor.clone();
 
-            // Reset alternate screen contents.
+            // Reset alternate screen contents, keeping its history from previous applications.
             self.inactive_grid.reset_region(..);
+            self.inactive_grid.scroll_display(Scroll::Bottom);
         }
 
         mem::swap(&mut self.keyboard_mode_stack, &mut self.inactive_keyboard_mode_stack);
@@ -774,14 +1113,23 @@ impl<T> Term<T> {
 
         let region = origin..self.scroll_region.end;
 
+        // Lines removed by deleting lines in the middle of the region are never saved.
+        let save_history = match self.config.scroll_region_history {
+            ScrollRegionHistory::TopAnchored => region.start == 0,
+            ScrollRegionHistory::FullScreen => {
+                region.start == 0 && region.end == self.screen_lines()
+            },
+            ScrollRegionHistory::Always => origin == self.scroll_region.start,
+        };
+
         // Scroll selection.
         self.selection = self.selection.take().and_then(|s| s.rotate(self, &region, lines as i32));
 
-        self.grid.scroll_up(&region, lines);
+        self.grid.scroll_up_with_history(&region, lines, save_history);
 
         // Scroll vi mode cursor.
         let viewport_top = Line(-(self.grid.display_offset() as i32));
-        let top = if region.start == 0 { viewport_top } else { region.start };
+        let top = if save_history && region.start == 0 { viewport_top } else { region.start };
         let line = &mut self.vi_mode_cursor.point.line;
         if (top <= *line) && region.end > *line {
             *line = cmp::max(*line - lines, top);
@@ -935,6 +1283,37 @@ impl<T> Term<T> {
         self.config.semantic_escape_chars = semantic_escape_chars.into();
     }
 
+    /// Mouse cursor icon requested by the application, if any.
+    #[inline]
+    pub fn mouse_cursor_icon(&self) -> Option<CursorIcon> {
+        self.mouse_cursor_icon
+    }
+
+    /// Request a desktop notification.
+    ///
+    /// The ANSI parser does not handle notifications, see [`OscParser`](osc::OscParser).
+    pub fn desktop_notification(&mut self, title: Option<String>, body: String)
+    where
+        T: EventListener,
+    {
+        if !self.config.notifications {
+            debug!("Ignoring desktop notification: {title:?}, {body}");
+            return;
+        }
+
+        self.event_proxy.send_event(Event::Notification(title, body));
+    }
+
+    /// Restore the default mouse cursor icon.
+    fn reset_mouse_cursor_icon(&mut self)
+    where
+        T: EventListener,
+    {
+        if self.mouse_cursor_icon.take().is_some() {
+            self.event_proxy.send_event(Event::MouseCursorDirty);
+        }
+    }
+
     /// Active terminal cursor style.
     ///
     /// While vi mode is active, this will automatically return the vi mode cursor style.
@@ -979,6 +1358,72 @@ impl<T> Term<T> {
         self.damage_cursor();
     }
 
+    /// Report the text of the line at the cursor, including the lines wrapped into it.
+    fn commit_line(&mut self)
+    where
+        T: EventListener,
+    {
+        if self.mode.contains(TermMode::ALT_SCREEN) || !self.event_proxy.wants_committed_lines() {
+            return;
+        }
+
+        // Wrapped lines are reported once the entire line is complete.
+        let last_column = self.last_column();
+        let end = Point::new(self.grid.cursor.point.line, last_column);
+        if self.grid[end].flags.contains(Flags::WRAPLINE) {
+            return;
+        }
+
+        let mut start = Point::new(end.line, Column(0));
+        let topmost_line = self.topmost_line();
+        while start.line > topmost_line
+            && self.grid[start.line - 1i32][last_column].flags.contains(Flags::WRAPLINE)
+        {
+            start.line -= 1;
+        }
+
+        let text = self.bounds_to_string(start, end);
+        self.event_proxy.send_event(Event::LineCommit(text));
+    }
+
+    /// Check if `c` continues the grapheme cluster before the cursor.
+    ///
+    /// This covers emoji ZWJ sequences, emoji modifiers and flags. Since applications have to
+    /// account for the cursor not advancing, it is only active in grapheme cluster mode.
+    fn extends_grapheme_cluster(&self, c: char) -> bool {
+        if !self.mode.contains(TermMode::GRAPHEME_CLUSTERS) {
+            return false;
+        }
+
+        // Get previous column, without looking at the previous line.
+        let cursor = &self.grid.cursor;
+        let mut column = cursor.point.column;
+        if !cursor.input_needs_wrap {
+            if column == 0 {
+                return false;
+            }
+            column -= 1;
+        }
+
+        let row = &self.grid[cursor.point.line];
+        if row[column].flags.contains(Flags::WIDE_CHAR_SPACER) {
+            column -= 1;
+        }
+
+        let cell = &row[column];
+        let zerowidth = cell.zerowidth().unwrap_or_default();
+        if cell.c == ' ' {
+            false
+        } else if zerowidth.last() == Some(&ZERO_WIDTH_JOINER) || is_emoji_modifier(c) {
+            true
+        } else {
+            // Regional indicators are combined in pairs to form flags.
+            is_regional_indicator(c)
+                && is_regional_indicator(cell.c)
+                && !zerowidth.iter().copied().any(is_regional_indicator)
+        }
+    }
+
     /// Write `c` to the cell at the cursor position.
     #[inline(always)]
     fn write_at_cursor(&mut self, c: char) {
@@ -1017,6 +1462,53 @@ impl<T> Term<T> {
         cursor_cell.extra = extra;
     }
 
+    /// Damage all visible lines with cells using the color at `index`.
+    ///
+    /// Once most of the viewport is affected, the terminal is marked fully damaged instead.
+    fn damage_color(&mut self, index: usize) {
+        // Avoid scanning the viewport when everything will be redrawn anyway.
+        if self.damage.full {
+            return;
+        }
+
+        // Special colors like the default background also apply to empty cells.
+        if index >= 256 {
+            self.mark_fully_damaged();
+            return;
+        }
+
+        // Bold text might be drawn using the bright variant of the first 16 colors.
+        let uses_color = |color: Color| match color {
+            Color::Named(named) if index < 16 => {
+                (named as usize) < 16 && named as usize % 8 == index % 8
+            },
+            Color::Indexed(color) if index < 16 && color < 16 => color as usize % 8 == index % 8,
+            Color::Indexed(color) => color as usize == index,
+            _ => false,
+        };
+
+        let display_offset = self.grid.display_offset() as i32;
+        let mut damaged_lines = 0;
+        for line in 0..self.screen_lines() {
+            let row = &self.grid[Line(line as i32 - display_offset)];
+            let damaged = row.into_iter().any(|cell| {
+                uses_color(cell.fg)
+                    || uses_color(cell.bg)
+                    || cell.underline_color().is_some_and(uses_color)
+            });
+
+            if damaged {
+                damaged_lines += 1;
+                if damaged_lines > self.screen_lines() / 2 {
+                    self.mark_fully_damaged();
+                    return;
+                }
+
+                self.damage.damage_line(line, 0, self.columns() - 1);
+            }
+        }
+    }
+
     #[inline]
     fn damage_cursor(&mut self) {
         // The normal cursor coordinates are always in viewport.
@@ -1039,6 +1531,19 @@ impl<T> Term<T> {
     }
 }
 
+impl<T: EventListener> Term<T> {
+    /// Send a reply to the PTY, unless replies are suppressed.
+    ///
+    /// Injected output did not originate from the application, so it must not cause any
+    /// responses to be written to the application's input.
+    #[inline]
+    fn reply(&self, event: Event) {
+        if !self.suppress_replies {
+            self.event_proxy.send_event(event);
+        }
+    }
+}
+
 impl<T> Dimensions for Term<T> {
     #[inline]
     fn columns(&self) -> usize {
@@ -1066,8 +1571,8 @@ impl<T: EventListener> Handler for Term<T> {
             None => return,
         };
 
-        // Handle zero-width characters.
-        if width == 0 {
+        // Handle zero-width characters and characters continuing the previous grapheme.
+        if width == 0 || self.extends_grapheme_cluster(c) {
             // Get previous column.
             let mut column = self.grid.cursor.point.column;
             if !self.grid.cursor.input_needs_wrap {
@@ -1259,13 +1764,13 @@ impl<T: EventListener> Handler for Term<T> {
             None => {
                 trace!("Reporting primary device attributes");
                 let text = String::from("\x1b[?6c");
-                self.event_proxy.send_event(Event::PtyWrite(text));
+                self.reply(Event::PtyWrite(text));
             },
             Some('>') => {
                 trace!("Reporting secondary device attributes");
                 let version = version_number(env!("CARGO_PKG_VERSION"));
                 let text = format!("\x1b[>0;{version};1c");
-                self.event_proxy.send_event(Event::PtyWrite(text));
+                self.reply(Event::PtyWrite(text));
             },
             _ => debug!("Unsupported device attributes intermediate"),
         }
@@ -1281,7 +1786,7 @@ impl<T: EventListener> Handler for Term<T> {
         let current_mode =
             self.keyboard_mode_stack.last().unwrap_or(&KeyboardModes::NO_MODE).bits();
         let text = format!("\x1b[?{current_mode}u");
-        self.event_proxy.send_event(Event::PtyWrite(text));
+        self.reply(Event::PtyWrite(text));
     }
 
     #[inline]
@@ -1334,12 +1839,12 @@ impl<T: EventListener> Handler for Term<T> {
         match arg {
             5 => {
                 let text = String::from("\x1b[0n");
-                self.event_proxy.send_event(Event::PtyWrite(text));
+                self.reply(Event::PtyWrite(text));
             },
             6 => {
                 let pos = self.grid.cursor.point;
                 let text = format!("\x1b[{};{}R", pos.line + 1, pos.column + 1);
-                self.event_proxy.send_event(Event::PtyWrite(text));
+                self.reply(Event::PtyWrite(text));
             },
             _ => debug!("unknown device status query: {}", arg),
         };
@@ -1379,17 +1884,7 @@ impl<T: EventListener> Handler for Term<T> {
                 cell.c = c;
             }
 
-            loop {
-                if (self.grid.cursor.point.column + 1) == self.columns() {
-                    break;
-                }
-
-                self.grid.cursor.point.column += 1;
-
-                if self.tabs[self.grid.cursor.point.column] {
-                    break;
-                }
-            }
+            self.grid.cursor.point.column = self.tabs.next(self.grid.cursor.point.column);
         }
     }
 
@@ -1422,6 +1917,8 @@ impl<T: EventListener> Handler for Term<T> {
     #[inline]
     fn linefeed(&mut self) {
         trace!("Linefeed");
+        self.commit_line();
+
         let next = self.grid.cursor.point.line + 1;
         if next == self.scroll_region.end {
             self.scroll_up(1);
@@ -1478,7 +1975,7 @@ impl<T: EventListener> Handler for Term<T> {
     #[inline]
     fn set_horizontal_tabstop(&mut self) {
         trace!("Setting horizontal tabstop");
-        self.tabs[self.grid.cursor.point.column] = true;
+        self.tabs.set(self.grid.cursor.point.column, true);
     }
 
     #[inline]
@@ -1569,19 +2066,13 @@ impl<T: EventListener> Handler for Term<T> {
 
         let old_col = self.grid.cursor.point.column.0;
         for _ in 0..count {
-            let mut col = self.grid.cursor.point.column;
+            let col = self.grid.cursor.point.column;
 
             if col == 0 {
                 break;
             }
 
-            for i in (0..(col.0)).rev() {
-                if self.tabs[index::Column(i)] {
-                    col = index::Column(i);
-                    break;
-                }
-            }
-            self.grid.cursor.point.column = col;
+            self.grid.cursor.point.column = self.tabs.prev(col);
         }
 
         let line = self.grid.cursor.point.line.0 as usize;
@@ -1592,23 +2083,16 @@ impl<T: EventListener> Handler for Term<T> {
     fn move_forward_tabs(&mut self, count: u16) {
         trace!("Moving forward {} tabs", count);
 
-        let num_cols = self.columns();
+        let last_column = self.last_column();
         let old_col = self.grid.cursor.point.column.0;
         for _ in 0..count {
-            let mut col = self.grid.cursor.point.column;
+            let col = self.grid.cursor.point.column;
 
-            if col == num_cols - 1 {
+            if col == last_column {
                 break;
             }
 
-            for i in col.0 + 1..num_cols {
-                col = index::Column(i);
-                if self.tabs[col] {
-                    break;
-                }
-            }
-
-            self.grid.cursor.point.column = col;
+            self.grid.cursor.point.column = self.tabs.next(col);
         }
 
         let line = self.grid.cursor.point.line.0 as usize;
@@ -1662,9 +2146,14 @@ impl<T: EventListener> Handler for Term<T> {
     fn set_color(&mut self, index: usize, color: Rgb) {
         trace!("Setting color[{}] = {:?}", index, color);
 
+        if !self.config.dynamic_colors {
+            debug!("Ignoring color change");
+            return;
+        }
+
         // Damage terminal if the color changed and it's not the cursor.
         if index != NamedColor::Cursor as usize && self.colors[index] != Some(color) {
-            self.mark_fully_damaged();
+            self.damage_color(index);
         }
 
         self.colors[index] = Some(color);
@@ -1676,13 +2165,19 @@ impl<T: EventListener> Handler for Term<T> {
         trace!("Requested write of escape sequence for color code {}: color[{}]", prefix, index);
 
         let terminator = terminator.to_owned();
-        self.event_proxy.send_event(Event::ColorRequest(
+        let is_background = index == NamedColor::Background as usize;
+        let alpha = self.config.background_alpha.filter(|_| is_background);
+        self.reply(Event::ColorRequest(
             index,
-            Arc::new(move |color| {
-                format!(
+            Arc::new(move |color| match alpha {
+                Some(alpha) => format!(
+                    "\x1b]{};rgba:{1:02x}{1:02x}/{2:02x}{2:02x}/{3:02x}{3:02x}/{4:04x}{5}",
+                    prefix, color.r, color.g, color.b, alpha, terminator
+                ),
+                None => format!(
                     "\x1b]{};rgb:{1:02x}{1:02x}/{2:02x}{2:02x}/{3:02x}{3:02x}{4}",
                     prefix, color.r, color.g, color.b, terminator
-                )
+                ),
             }),
         ));
     }
@@ -1692,9 +2187,14 @@ impl<T: EventListener> Handler for Term<T> {
     fn reset_color(&mut self, index: usize) {
         trace!("Resetting color[{}]", index);
 
+        if !self.config.dynamic_colors {
+            debug!("Ignoring color reset");
+            return;
+        }
+
         // Damage terminal if the color changed and it's not the cursor.
         if index != NamedColor::Cursor as usize && self.colors[index].is_some() {
-            self.mark_fully_damaged();
+            self.damage_color(index);
         }
 
         self.colors[index] = None;
@@ -1737,7 +2237,7 @@ impl<T: EventListener> Handler for Term<T> {
 
         let terminator = terminator.to_owned();
 
-        self.event_proxy.send_event(Event::ClipboardLoad(
+        self.reply(Event::ClipboardLoad(
             clipboard_type,
             Arc::new(move |text| {
                 let base64 = Base64.encode(text);
@@ -1822,7 +2322,7 @@ impl<T: EventListener> Handler for Term<T> {
         trace!("Clearing tabs: {:?}", mode);
         match mode {
             ansi::TabulationClearMode::Current => {
-                self.tabs[self.grid.cursor.point.column] = false;
+                self.tabs.set(self.grid.cursor.point.column, false);
             },
             ansi::TabulationClearMode::All => {
                 self.tabs.clear_all();
@@ -1848,6 +2348,7 @@ impl<T: EventListener> Handler for Term<T> {
         self.vi_mode_cursor = Default::default();
         self.keyboard_mode_stack = Default::default();
         self.inactive_keyboard_mode_stack = Default::default();
+        self.reset_mouse_cursor_icon();
 
         // Preserve vi mode across resets.
         self.mode &= TermMode::VI;
@@ -1873,6 +2374,12 @@ impl<T: EventListener> Handler for Term<T> {
     #[inline]
     fn set_hyperlink(&mut self, hyperlink: Option<Hyperlink>) {
         trace!("Setting hyperlink: {:?}", hyperlink);
+
+        if !self.config.hyperlinks {
+            debug!("Ignoring hyperlink");
+            return;
+        }
+
         self.grid.cursor.template.set_hyperlink(hyperlink.map(|e| e.into()));
     }
 
@@ -1934,6 +2441,11 @@ impl<T: EventListener> Handler for Term<T> {
     fn set_private_mode(&mut self, mode: PrivateMode) {
         let mode = match mode {
             PrivateMode::Named(mode) => mode,
+            PrivateMode::Unknown(GRAPHEME_CLUSTER_MODE) => {
+                trace!("Setting private mode: GraphemeClusters");
+                self.mode.insert(TermMode::GRAPHEME_CLUSTERS);
+                return;
+            },
             PrivateMode::Unknown(mode) => {
                 debug!("Ignoring unknown mode {} in set_private_mode", mode);
                 return;
@@ -1986,6 +2498,7 @@ impl<T: EventListener> Handler for Term<T> {
                 style.blinking = true;
                 self.event_proxy.send_event(Event::CursorBlinkingChange);
             },
+            // Synchronized updates are buffered by the parser, before reaching the terminal.
             NamedPrivateMode::SyncUpdate => (),
         }
     }
@@ -1994,6 +2507,11 @@ impl<T: EventListener> Handler for Term<T> {
     fn unset_private_mode(&mut self, mode: PrivateMode) {
         let mode = match mode {
             PrivateMode::Named(mode) => mode,
+            PrivateMode::Unknown(GRAPHEME_CLUSTER_MODE) => {
+                trace!("Unsetting private mode: GraphemeClusters");
+                self.mode.remove(TermMode::GRAPHEME_CLUSTERS);
+                return;
+            },
             PrivateMode::Unknown(mode) => {
                 debug!("Ignoring unknown mode {} in unset_private_mode", mode);
                 return;
@@ -2006,6 +2524,9 @@ impl<T: EventListener> Handler for Term<T> {
             NamedPrivateMode::SwapScreenAndSetRestoreCursor => {
                 if self.mode.contains(TermMode::ALT_SCREEN) {
                     self.swap_alt();
+
+                    // Don't keep the pointer shape of full-screen applications after they exit.
+                    self.reset_mouse_cursor_icon();
                 }
             },
             NamedPrivateMode::ShowCursor => self.mode.remove(TermMode::SHOW_CURSOR),
@@ -2081,10 +2602,13 @@ impl<T: EventListener> Handler for Term<T> {
                 NamedPrivateMode::SyncUpdate => ModeState::Reset,
                 NamedPrivateMode::ColumnMode => ModeState::NotSupported,
             },
+            PrivateMode::Unknown(GRAPHEME_CLUSTER_MODE) => {
+                self.mode.contains(TermMode::GRAPHEME_CLUSTERS).into()
+            },
             PrivateMode::Unknown(_) => ModeState::NotSupported,
         };
 
-        self.event_proxy.send_event(Event::PtyWrite(format!(
+        self.reply(Event::PtyWrite(format!(
             "\x1b[?{};{}$y",
             mode.raw(),
             state as u8,
@@ -2141,7 +2665,7 @@ impl<T: EventListener> Handler for Term<T> {
             ansi::Mode::Unknown(_) => ModeState::NotSupported,
         };
 
-        self.event_proxy.send_event(Event::PtyWrite(format!(
+        self.reply(Event::PtyWrite(format!(
             "\x1b[{};{}$y",
             mode.raw(),
             state as u8,
@@ -2218,6 +2742,11 @@ impl<T: EventListener> Handler for Term<T> {
     fn set_title(&mut self, title: Option<String>) {
         trace!("Setting title to '{:?}'", title);
 
+        if !self.config.titles {
+            debug!("Ignoring title change");
+            return;
+        }
+
         self.title.clone_from(&title);
 
         let title_event = match title {
@@ -2253,9 +2782,27 @@ impl<T: EventListener> Handler for Term<T> {
         }
     }
 
+    #[inline]
+    fn set_mouse_cursor_icon(&mut self, icon: CursorIcon) {
+        trace!("Setting mouse cursor icon to {icon:?}");
+
+        if !self.config.mouse_cursor_icons {
+            debug!("Ignoring mouse cursor icon change");
+            return;
+        }
+
+        self.mouse_cursor_icon = Some(icon);
+        self.event_proxy.send_event(Event::MouseCursorDirty);
+    }
+
     #[inline]
     fn text_area_size_pixels(&mut self) {
-        self.event_proxy.send_event(Event::TextAreaSizeRequest(Arc::new(move |window_size| {
+        if !self.config.window_reports {
+            debug!("Ignoring text area size report request");
+            return;
+        }
+
+        self.reply(Event::TextAreaSizeRequest(Arc::new(move |window_size| {
             let height = window_size.num_lines * window_size.cell_height;
             let width = window_size.num_cols * window_size.cell_width;
             format!("\x1b[4;{height};{width}t")
@@ -2264,8 +2811,13 @@ impl<T: EventListener> Handler for Term<T> {
 
     #[inline]
     fn text_area_size_chars(&mut self) {
+        if !self.config.window_reports {
+            debug!("Ignoring text area size report request");
+            return;
+        }
+
         let text = format!("\x1b[8;{};{}t", self.screen_lines(), self.columns());
-        self.event_proxy.send_event(Event::PtyWrite(text));
+        self.reply(Event::PtyWrite(text));
     }
 }
 
@@ -2318,14 +2870,53 @@ pub enum ClipboardType {
     Selection,
 }
 
+/// Horizontal tabstops, with the adjacent tabstop of every column precomputed.
 struct TabStops {
     tabs: Vec<bool>,
+
+    /// Column of the next tabstop, or the last column if there is none.
+    next: Vec<Column>,
+
+    /// Column of the previous tabstop, or the first column if there is none.
+    prev: Vec<Column>,
 }
 
 impl TabStops {
     #[inline]
     fn new(columns: usize) -> TabStops {
-        TabStops { tabs: (0..columns).map(|i| i % INITIAL_TABSTOPS == 0).collect() }
+        let mut tabs = TabStops { tabs: Vec::new(), next: Vec::new(), prev: Vec::new() };
+        tabs.resize(columns);
+        tabs
+    }
+
+    /// Column of the first tabstop after `column`.
+    #[inline]
+    fn next(&self, column: Column) -> Column {
+        self.next[column.0]
+    }
+
+    /// Column of the last tabstop before `column`.
+    #[inline]
+    fn prev(&self, column: Column) -> Column {
+        self.prev[column.0]
+    }
+
+    /// Set or remove the tabstop at `column`.
+    fn set(&mut self, column: Column, tabstop: bool) {
+        if self.tabs[column.0] == tabstop {
+            return;
+        }
+        self.tabs[column.0] = tabstop;
+
+        // Only columns between the surrounding tabstops are affected.
+        let (next, prev) =
+            if tabstop { (column, column) } else { (self.next(column), self.prev(column)) };
+        for i in self.prev(column).0..column.0 {
+            self.next[i] = next;
+        }
+        for i in column.0 + 1..=self.next(column).0 {
+            self.prev[i] = prev;
+        }
     }
 
     /// Remove all tabstops.
@@ -2334,6 +2925,7 @@ impl TabStops {
         unsafe {
             ptr::write_bytes(self.tabs.as_mut_ptr(), 0, self.tabs.len());
         }
+        self.update_adjacent();
     }
 
     /// Increase tabstop capacity.
@@ -2345,6 +2937,30 @@ impl TabStops {
             index += 1;
             is_tabstop
         });
+        self.update_adjacent();
+    }
+
+    /// Recompute the adjacent tabstops of all columns.
+    fn update_adjacent(&mut self) {
+        let columns = self.tabs.len();
+        self.next.resize(columns, Column(0));
+        self.prev.resize(columns, Column(0));
+
+        let mut next = Column(columns.saturating_sub(1));
+        for i in (0..columns).rev() {
+            self.next[i] = next;
+            if self.tabs[i] {
+                next = Column(i);
+            }
+        }
+
+        let mut prev = Column(0);
+        for i in 0..columns {
+            self.prev[i] = prev;
+            if self.tabs[i] {
+                prev = Column(i);
+            }
+        }
     }
 }
 
@@ -2356,12 +2972,6 @@ impl Index<Column> for TabStops {
     }
 }
 
-impl IndexMut<Column> for TabStops {
-    fn index_mut(&mut self, index: Column) -> &mut bool {
-        self.tabs.index_mut(index.0)
-    }
-}
-
 /// Terminal cursor rendering information.
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub struct RenderableCursor {
@@ -2414,6 +3024,52 @@ impl<'a> RenderableContent<'a> {
     }
 }
 
+/// Visible terminal content, limited to damaged cells.
+pub struct DamagedContent<'a> {
+    /// Content of the entire terminal view.
+    pub content: RenderableContent<'a>,
+
+    /// Damaged cells within the terminal view.
+    pub cells: DamagedCells<'a>,
+
+    /// Whether the entire terminal view is damaged.
+    pub full: bool,
+}
+
+/// Iterator over the damaged cells in the terminal view.
+pub struct DamagedCells<'a> {
+    grid: &'a Grid<Cell>,
+    lines: vec::IntoIter<LineDamageBounds>,
+    point: Point,
+    end: Column,
+}
+
+impl<'a> DamagedCells<'a> {
+    fn new(grid: &'a Grid<Cell>, lines: Vec<LineDamageBounds>) -> Self {
+        // Start with an exhausted line, so the first damaged line is loaded immediately.
+        let point = Point::new(Line(0), Column(1));
+        Self { grid, lines: lines.into_iter(), point, end: Column(0) }
+    }
+}
+
+impl<'a> Iterator for DamagedCells<'a> {
+    type Item = Indexed<&'a Cell>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.point.column > self.end {
+            let damage = self.lines.next()?;
+            let line = Line(damage.line as i32 - self.grid.display_offset() as i32);
+            self.point = Point::new(line, Column(damage.left));
+            self.end = Column(cmp::min(damage.right, self.grid.last_column().0));
+        }
+
+        let point = self.point;
+        self.point.column += 1;
+
+        Some(Indexed { cell: &self.grid[point], point })
+    }
+}
+
 /// Terminal test helpers.
 pub mod test {
     use super::*;
@@ -2510,6 +3166,7 @@ pub mod test {
 mod tests {
     use super::*;
 
+    use std::cell::RefCell;
     use std::mem;
 
     use crate::event::VoidListener;
@@ -2691,6 +3348,75 @@ mod tests {
         assert_eq!(term.selection_to_string(), Some(String::from("\"aa\"a\n")));
     }
 
+    #[test]
+    fn hidden_selection() {
+        let size = TermSize::new(6, 1);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+        for (i, c) in "pw:abc".chars().enumerate() {
+            term.grid[Line(0)][Column(i)].c = c;
+            if i >= 3 {
+                term.grid[Line(0)][Column(i)].flags.insert(Flags::HIDDEN);
+            }
+        }
+
+        term.selection = Some(Selection::new(
+            SelectionType::Lines,
+            Point { line: Line(0), column: Column(0) },
+            Side::Left,
+        ));
+        assert_eq!(term.selection_to_string(), Some(String::from("pw:abc\n")));
+
+        term.config.copy_hidden = false;
+        assert_eq!(term.selection_to_string(), Some(String::from("pw:   \n")));
+
+        // Only selections conceal hidden text.
+        let end = Point::new(Line(0), Column(5));
+        assert_eq!(term.bounds_to_string(Point::new(Line(0), Column(0)), end), "pw:abc");
+    }
+
+    #[test]
+    fn tabstops_on_wide_grid() {
+        let size = TermSize::new(10_000, 1);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+        let last_column = term.last_column();
+
+        term.move_forward_tabs(3);
+        assert_eq!(term.grid.cursor.point.column, Column(24));
+        term.move_backward_tabs(2);
+        assert_eq!(term.grid.cursor.point.column, Column(8));
+
+        // Set a new tabstop between the defaults.
+        term.goto(0, 12);
+        term.set_horizontal_tabstop();
+        term.goto(0, 8);
+        term.move_forward_tabs(1);
+        assert_eq!(term.grid.cursor.point.column, Column(12));
+        term.goto(0, 15);
+        term.move_backward_tabs(1);
+        assert_eq!(term.grid.cursor.point.column, Column(12));
+
+        // Remove the tabstop again.
+        term.goto(0, 12);
+        term.clear_tabs(ansi::TabulationClearMode::Current);
+        term.goto(0, 8);
+        term.put_tab(1);
+        assert_eq!(term.grid.cursor.point.column, Column(16));
+
+        // Without tabstops, tabs move to the edges of the line.
+        term.clear_tabs(ansi::TabulationClearMode::All);
+        term.goto(0, 5_000);
+        term.move_forward_tabs(1);
+        assert_eq!(term.grid.cursor.point.column, last_column);
+        term.move_backward_tabs(1);
+        assert_eq!(term.grid.cursor.point.column, Column(0));
+
+        // Growing the grid adds the default tabstops to the new columns.
+        term.resize(TermSize::new(10_020, 1));
+        term.goto(0, 9_999);
+        term.move_forward_tabs(1);
+        assert_eq!(term.grid.cursor.point.column, Column(10_000));
+    }
+
     #[test]
     fn block_selection_works() {
         let size = TermSize::new(5, 5);
@@ -2748,6 +3474,81 @@ mod tests {
         assert_eq!(deserialized, grid);
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn restore_state() {
+        let mut term = Term::new(Config::default(), &TermSize::new(5, 3), VoidListener);
+        for c in "0\r\n1\r\n2\r\n3\r\n4\r\n5\r\n6\r\n7\r\n8\r\n9\r\n".chars() {
+            match c {
+                '\r' => term.carriage_return(),
+                '\n' => term.linefeed(),
+                c => term.input(c),
+            }
+        }
+        term.set_title(Some("title".into()));
+        term.set_mode(NamedMode::Insert.into());
+
+        let serialized = serde_json::to_string(&term.state()).expect("ser");
+        let state = serde_json::from_str(&serialized).expect("de");
+
+        // Restore into a terminal with an additional line.
+        let mut restored = Term::new(Config::default(), &TermSize::new(5, 4), VoidListener);
+        restored.restore_state(state).unwrap();
+
+        assert_eq!(restored.screen_lines(), 4);
+        assert_eq!(restored.history_size(), 7);
+        assert_eq!(restored.grid()[Line(-7)][Column(0)].c, '0');
+        assert_eq!(restored.grid()[Line(2)][Column(0)].c, '9');
+        assert_eq!(restored.grid().cursor.point, Point::new(Line(3), Column(0)));
+        assert!(restored.mode().contains(TermMode::INSERT));
+        assert_eq!(restored.title.as_deref(), Some("title"));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn restore_invalid_state() {
+        let term = Term::new(Config::default(), &TermSize::new(5, 3), VoidListener);
+        let mut state = serde_json::to_value(term.state()).expect("ser");
+        state["cursor"]["point"]["line"] = 3.into();
+        let state = serde_json::from_value(state).expect("de");
+
+        let mut restored = Term::new(Config::default(), &TermSize::new(5, 3), VoidListener);
+        assert_eq!(restored.restore_state(state), Err(InvalidTermState));
+
+        let mut state = serde_json::to_value(term.state()).expect("ser");
+        state["inactive_grid"]["columns"] = 6.into();
+        let state = serde_json::from_value(state).expect("de");
+        assert_eq!(restored.restore_state(state), Err(InvalidTermState));
+    }
+
+    #[test]
+    fn grapheme_clusters() {
+        let size = TermSize::new(20, 1);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+        let input = "👩\u{200d}👧🇩🇪👍🏽";
+
+        // Without grapheme cluster mode, every character advances the cursor.
+        input.chars().for_each(|c| term.input(c));
+        assert_eq!(term.grid.cursor.point.column, Column(10));
+
+        term.carriage_return();
+        term.clear_line(ansi::LineClearMode::All);
+        term.set_private_mode(PrivateMode::Unknown(GRAPHEME_CLUSTER_MODE));
+        input.chars().for_each(|c| term.input(c));
+
+        assert_eq!(term.grid.cursor.point.column, Column(5));
+        assert_eq!(term.grid[Line(0)][Column(0)].c, '👩');
+        assert_eq!(term.grid[Line(0)][Column(0)].zerowidth(), Some(&['\u{200d}', '👧'][..]));
+        assert_eq!(term.grid[Line(0)][Column(2)].c, '🇩');
+        assert_eq!(term.grid[Line(0)][Column(2)].zerowidth(), Some(&['🇪'][..]));
+        assert_eq!(term.grid[Line(0)][Column(3)].c, '👍');
+        assert_eq!(term.grid[Line(0)][Column(3)].zerowidth(), Some(&['🏽'][..]));
+
+        // Only pairs of regional indicators are combined.
+        term.input('🇩');
+        assert_eq!(term.grid.cursor.point.column, Column(6));
+    }
+
     #[test]
     fn input_line_drawing_character() {
         let size = TermSize::new(7, 17);
@@ -2987,6 +3788,147 @@ mod tests {
         assert_eq!(term.grid.cursor.point, Point::new(Line(4), Column(0)));
     }
 
+    #[test]
+    fn alt_screen_history() {
+        let size = TermSize::new(5, 5);
+        let config = Config { alt_screen_history: 3, ..Config::default() };
+        let mut term = Term::new(config, &size, VoidListener);
+
+        term.set_private_mode(NamedPrivateMode::SwapScreenAndSetRestoreCursor.into());
+        for _ in 0..10 {
+            term.newline();
+        }
+        assert_eq!(term.history_size(), 3);
+
+        // History is kept for the next alternate screen application.
+        term.scroll_display(Scroll::Top);
+        term.unset_private_mode(NamedPrivateMode::SwapScreenAndSetRestoreCursor.into());
+        assert_eq!(term.history_size(), 0);
+        term.set_private_mode(NamedPrivateMode::SwapScreenAndSetRestoreCursor.into());
+        assert_eq!(term.history_size(), 3);
+        assert_eq!(term.grid().display_offset(), 0);
+
+        // History is removed when disabled.
+        term.set_options(Config::default());
+        assert_eq!(term.history_size(), 0);
+    }
+
+    #[test]
+    fn update_history_at_runtime() {
+        let size = TermSize::new(5, 3);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+
+        for c in "0\r\n1\r\n2\r\n3\r\n4\r\n5\r\n6\r\n7\r\n8\r\n9\r\n".chars() {
+            match c {
+                '\r' => term.carriage_return(),
+                '\n' => term.linefeed(),
+                c => term.input(c),
+            }
+        }
+        assert_eq!(term.history_size(), 8);
+
+        term.scroll_display(Scroll::Top);
+        term.vi_mode_cursor.point = Point::new(Line(-8), Column(0));
+        let point = Point::new(Line(-6), Column(0));
+        term.selection = Some(Selection::new(SelectionType::Simple, point, Side::Left));
+
+        // Shrinking removes the oldest lines.
+        term.set_options(Config { scrolling_history: 3, ..Config::default() });
+        assert_eq!(term.history_size(), 3);
+        assert_eq!(term.grid()[Line(-3)][Column(0)].c, '5');
+        assert_eq!(term.grid()[Line(-1)][Column(0)].c, '7');
+        assert_eq!(term.grid().display_offset(), 3);
+        assert_eq!(term.vi_mode_cursor.point.line, Line(-3));
+        assert!(term.selection.is_none());
+
+        // Growing keeps the existing history.
+        term.set_options(Config { scrolling_history: 10, ..Config::default() });
+        assert_eq!(term.history_size(), 3);
+        term.newline();
+        term.newline();
+        assert_eq!(term.history_size(), 5);
+        assert_eq!(term.grid()[Line(-5)][Column(0)].c, '5');
+    }
+
+    #[test]
+    fn committed_lines() {
+        #[derive(Default)]
+        struct LineListener(RefCell<Vec<String>>);
+
+        impl EventListener for &LineListener {
+            fn send_event(&self, event: Event) {
+                if let Event::LineCommit(text) = event {
+                    self.0.borrow_mut().push(text);
+                }
+            }
+
+            fn wants_committed_lines(&self) -> bool {
+                true
+            }
+        }
+
+        let listener = LineListener::default();
+        let size = TermSize::new(5, 3);
+        let mut term = Term::new(Config::default(), &size, &listener);
+
+        for c in "ab\r\nwrapped\r\n".chars() {
+            match c {
+                '\r' => term.carriage_return(),
+                '\n' => term.linefeed(),
+                c => term.input(c),
+            }
+        }
+
+        assert_eq!(*listener.0.borrow(), vec![String::from("ab"), String::from("wrapped")]);
+
+        // Full-screen applications are not reported.
+        term.set_private_mode(NamedPrivateMode::SwapScreenAndSetRestoreCursor.into());
+        term.input('x');
+        term.linefeed();
+        assert_eq!(listener.0.borrow().len(), 2);
+    }
+
+    #[test]
+    fn scroll_region_history() {
+        let size = TermSize::new(5, 10);
+        let history_after = |history: ScrollRegionHistory, top: usize, bottom: Option<usize>| {
+            let config = Config { scroll_region_history: history, ..Config::default() };
+            let mut term = Term::new(config, &size, VoidListener);
+            term.set_scrolling_region(top, bottom);
+            term.scroll_up(2);
+            term.history_size()
+        };
+
+        // Region with a fixed status line at the bottom.
+        assert_eq!(history_after(ScrollRegionHistory::TopAnchored, 1, Some(9)), 2);
+        assert_eq!(history_after(ScrollRegionHistory::FullScreen, 1, Some(9)), 0);
+        assert_eq!(history_after(ScrollRegionHistory::Always, 1, Some(9)), 2);
+
+        // Region with a fixed line at the top.
+        assert_eq!(history_after(ScrollRegionHistory::TopAnchored, 2, None), 0);
+        assert_eq!(history_after(ScrollRegionHistory::FullScreen, 2, None), 0);
+        assert_eq!(history_after(ScrollRegionHistory::Always, 2, None), 2);
+
+        // Entire screen.
+        assert_eq!(history_after(ScrollRegionHistory::TopAnchored, 1, None), 2);
+        assert_eq!(history_after(ScrollRegionHistory::FullScreen, 1, None), 2);
+        assert_eq!(history_after(ScrollRegionHistory::Always, 1, None), 2);
+    }
+
+    #[test]
+    fn scroll_region_history_ignores_deleted_lines() {
+        let size = TermSize::new(5, 10);
+        let history = ScrollRegionHistory::Always;
+        let config = Config { scroll_region_history: history, ..Config::default() };
+        let mut term = Term::new(config, &size, VoidListener);
+
+        term.set_scrolling_region(2, None);
+        term.goto(3, 0);
+        term.delete_lines(2);
+
+        assert_eq!(term.history_size(), 0);
+    }
+
     #[test]
     fn damage_public_usage() {
         let size = TermSize::new(10, 10);
@@ -3061,6 +4003,105 @@ mod tests {
         assert_eq!(damaged_lines.next(), None);
     }
 
+    #[test]
+    fn damage_snapshot() {
+        let size = TermSize::new(10, 5);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+
+        // New terminals are fully damaged.
+        let damage = term.take_damage();
+        assert!(damage.full);
+        assert!(damage.lines.is_empty());
+
+        term.goto(2, 3);
+        term.input('a');
+
+        let damage = term.damage().to_owned();
+        assert_eq!(damage, DamageSnapshot {
+            full: false,
+            lines: vec![LineDamageBounds::new(0, 0, 0), LineDamageBounds::new(2, 3, 4)],
+        });
+
+        // Taking the damage resets it.
+        assert_eq!(term.take_damage(), damage);
+        let damage = term.take_damage();
+        assert_eq!(damage.lines, vec![LineDamageBounds::new(2, 4, 4)]);
+    }
+
+    #[test]
+    fn damaged_content() {
+        let size = TermSize::new(10, 5);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+
+        // New terminals are fully damaged.
+        let content = term.damaged_content();
+        assert!(content.full);
+        assert_eq!(content.cells.count(), 50);
+        term.reset_damage();
+
+        term.goto(2, 3);
+        term.input('a');
+        term.input('b');
+
+        let content = term.damaged_content();
+        assert!(!content.full);
+        let cells: Vec<_> = content.cells.map(|cell| (cell.point, cell.c)).collect();
+        assert_eq!(cells, vec![
+            // Previous cursor position.
+            (Point::new(Line(0), Column(0)), ' '),
+            (Point::new(Line(2), Column(3)), 'a'),
+            (Point::new(Line(2), Column(4)), 'b'),
+            (Point::new(Line(2), Column(5)), ' '),
+        ]);
+        term.reset_damage();
+
+        assert_eq!(term.damaged_content().cells.count(), 1);
+    }
+
+    #[test]
+    fn damage_palette_change() {
+        let size = TermSize::new(10, 5);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+        let num_cols = term.columns();
+        let right = num_cols - 1;
+        term.grid[Line(2)][Column(1)].fg = Color::Indexed(100);
+        term.grid[Line(3)][Column(0)].bg = Color::Named(NamedColor::BrightRed);
+        term.reset_damage();
+
+        term.set_color(100, Rgb { r: 255, g: 0, b: 0 });
+        assert!(!term.damage.full);
+        assert_eq!(term.damage.lines[2], LineDamageBounds { line: 2, left: 0, right });
+        assert!(!term.damage.lines[3].is_damaged());
+        term.damage.reset(num_cols);
+
+        // Bold text might use the bright variant of the changed color.
+        term.set_color(NamedColor::Red as usize, Rgb { r: 255, g: 0, b: 0 });
+        assert!(!term.damage.lines[2].is_damaged());
+        assert_eq!(term.damage.lines[3], LineDamageBounds { line: 3, left: 0, right });
+        term.damage.reset(num_cols);
+
+        term.reset_color(100);
+        assert_eq!(term.damage.lines[2], LineDamageBounds { line: 2, left: 0, right });
+        term.damage.reset(num_cols);
+
+        term.set_color(NamedColor::Background as usize, Rgb { r: 0, g: 0, b: 0 });
+        assert!(term.damage.full);
+    }
+
+    #[test]
+    fn damage_palette_change_threshold() {
+        let size = TermSize::new(10, 5);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+        for line in 0..3 {
+            term.grid[Line(line)][Column(0)].fg = Color::Indexed(100);
+        }
+        term.reset_damage();
+
+        // Changing a color used by most lines damages the entire viewport.
+        term.set_color(100, Rgb { r: 255, g: 0, b: 0 });
+        assert!(term.damage.full);
+    }
+
     #[test]
     fn damage_cursor_movements() {
         let size = TermSize::new(10, 10);
@@ -3245,6 +4286,104 @@ mod tests {
         assert!(term.damage.full);
     }
 
+    #[test]
+    fn mouse_cursor_icon() {
+        let size = TermSize::new(7, 3);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+
+        term.set_mouse_cursor_icon(CursorIcon::Wait);
+        assert_eq!(term.mouse_cursor_icon(), Some(CursorIcon::Wait));
+
+        // Icon is kept when entering the alternate screen, but reset when leaving it.
+        term.set_private_mode(NamedPrivateMode::SwapScreenAndSetRestoreCursor.into());
+        assert_eq!(term.mouse_cursor_icon(), Some(CursorIcon::Wait));
+        term.unset_private_mode(NamedPrivateMode::SwapScreenAndSetRestoreCursor.into());
+        assert_eq!(term.mouse_cursor_icon(), None);
+
+        term.set_mouse_cursor_icon(CursorIcon::Pointer);
+        term.reset_state();
+        assert_eq!(term.mouse_cursor_icon(), None);
+    }
+
+    #[test]
+    fn window_reports() {
+        #[derive(Default)]
+        struct ReportListener(RefCell<Vec<String>>);
+
+        impl EventListener for &ReportListener {
+            fn send_event(&self, event: Event) {
+                if let Event::PtyWrite(text) = event {
+                    self.0.borrow_mut().push(text);
+                }
+            }
+        }
+
+        let listener = ReportListener::default();
+        let size = TermSize::new(7, 3);
+        let mut term = Term::new(Config::default(), &size, &listener);
+
+        term.text_area_size_chars();
+        assert_eq!(*listener.0.borrow(), vec![String::from("\x1b[8;3;7t")]);
+
+        term.set_options(Config { window_reports: false, ..Config::default() });
+        term.text_area_size_chars();
+        assert_eq!(listener.0.borrow().len(), 1);
+    }
+
+    #[test]
+    fn suppressed_replies() {
+        #[derive(Default)]
+        struct ReportListener(RefCell<Vec<String>>);
+
+        impl EventListener for &ReportListener {
+            fn send_event(&self, event: Event) {
+                if let Event::PtyWrite(text) = event {
+                    self.0.borrow_mut().push(text);
+                }
+            }
+        }
+
+        let listener = ReportListener::default();
+        let size = TermSize::new(7, 3);
+        let mut term = Term::new(Config::default(), &size, &listener);
+
+        term.suppress_replies = true;
+        term.device_status(5);
+        term.text_area_size_chars();
+        assert!(listener.0.borrow().is_empty());
+
+        term.suppress_replies = false;
+        term.device_status(5);
+        assert_eq!(*listener.0.borrow(), vec![String::from("\x1b[0n")]);
+    }
+
+    #[test]
+    fn disabled_handler_gates() {
+        let size = TermSize::new(7, 3);
+        let config = Config {
+            titles: false,
+            hyperlinks: false,
+            dynamic_colors: false,
+            mouse_cursor_icons: false,
+            ..Config::default()
+        };
+        let mut term = Term::new(config, &size, VoidListener);
+
+        term.set_mouse_cursor_icon(CursorIcon::Wait);
+        assert_eq!(term.mouse_cursor_icon(), None);
+
+        term.set_title(Some(String::from("title")));
+        assert_eq!(term.title, None);
+
+        term.set_color(1, Rgb { r: 1, g: 2, b: 3 });
+        assert_eq!(term.colors()[1], None);
+
+        let hyperlink = Hyperlink { id: None, uri: String::from("https://example.org") };
+        term.set_hyperlink(Some(hyperlink));
+        term.input('x');
+        assert_eq!(term.grid()[Line(0)][Column(0)].hyperlink(), None);
+    }
+
     #[test]
     fn window_title() {
         let size = TermSize::new(7, 17);
@@ -0,0 +1,310 @@
+//! Escape sequences which are ignored by the ANSI parser.
+//!
+//! Desktop notifications (OSC 9 and OSC 777) and semantic prompts (OSC 133) are not supported by
+//! the ANSI parser, so they need to be extracted from the terminal output separately.
+
+use std::mem;
+
+use crate::event::EventListener;
+use crate::term::prompt::PromptMarks;
+use crate::term::Term;
+use crate::vte::ansi;
+
+/// Maximum length of an escape sequence handled by the [`OscParser`].
+const MAX_OSC_LEN: usize = 4096;
+
+/// Escape sequence ending a synchronized update.
+const SYNC_END: &[u8] = b"\x1b[?2026l";
+
+/// Parser for escape sequences which are ignored by the ANSI parser.
+///
+/// All terminal output should be passed through [`Self::advance`], which forwards it to the ANSI
+/// parser.
+#[derive(Default)]
+pub struct OscParser {
+    state: State,
+    buffer: Vec<u8>,
+
+    /// Prompt marks received during a synchronized update.
+    ///
+    /// Content of synchronized updates is only applied once the update ends, so marks are
+    /// deferred until then to apply them to the correct line.
+    pending_marks: PromptMarks,
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+enum State {
+    #[default]
+    Ground,
+    Escape,
+    Osc,
+    OscEscape,
+}
+
+impl OscParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Process terminal output.
+    ///
+    /// The output is split after every OSC, to make sure escape sequences are applied at the
+    /// correct terminal state.
+    pub fn advance<T: EventListener>(
+        &mut self,
+        parser: &mut ansi::Processor,
+        term: &mut Term<T>,
+        bytes: &[u8],
+    ) {
+        let mut start = 0;
+        let mut index = 0;
+        while index < bytes.len() {
+            let byte = bytes[index];
+            index += 1;
+
+            match self.state {
+                // Skip everything until the next escape.
+                State::Ground => match bytes[index - 1..].iter().position(|&byte| byte == 0x1b) {
+                    Some(offset) => {
+                        self.state = State::Escape;
+                        index += offset;
+                    },
+                    None => break,
+                },
+                State::Escape if byte == b']' => {
+                    self.buffer.clear();
+                    self.state = State::Osc;
+                },
+                State::Escape | State::OscEscape if byte == 0x1b => self.state = State::Escape,
+                State::Escape => self.state = State::Ground,
+                State::Osc => match byte {
+                    0x07 => {
+                        self.advance_parser(parser, term, &bytes[start..index]);
+                        start = index;
+
+                        self.dispatch(parser, term);
+                        self.state = State::Ground;
+                    },
+                    0x1b => self.state = State::OscEscape,
+                    // CAN and SUB abort the escape sequence.
+                    0x18 | 0x1a => self.state = State::Ground,
+                    _ if self.buffer.len() < MAX_OSC_LEN => self.buffer.push(byte),
+                    _ => (),
+                },
+                State::OscEscape => {
+                    if byte == b'\\' {
+                        self.advance_parser(parser, term, &bytes[start..index]);
+                        start = index;
+
+                        self.dispatch(parser, term);
+                        self.state = State::Ground;
+                    } else if byte == b']' {
+                        self.buffer.clear();
+                        self.state = State::Osc;
+                    } else {
+                        self.state = State::Ground;
+                    }
+                },
+            }
+        }
+
+        self.advance_parser(parser, term, &bytes[start..]);
+    }
+
+    /// End the active synchronized update, applying all of its content.
+    pub fn stop_sync<T: EventListener>(
+        &mut self,
+        parser: &mut ansi::Processor,
+        term: &mut Term<T>,
+    ) {
+        parser.stop_sync(term);
+        self.apply_pending_marks(parser, term);
+    }
+
+    /// Forward output to the ANSI parser.
+    fn advance_parser<T: EventListener>(
+        &mut self,
+        parser: &mut ansi::Processor,
+        term: &mut Term<T>,
+        mut bytes: &[u8],
+    ) {
+        // Apply pending marks right after the synchronized update ended.
+        if !self.pending_marks.is_empty() {
+            let sync_end = bytes.windows(SYNC_END.len()).position(|window| window == SYNC_END);
+            if let Some(end) = sync_end.map(|offset| offset + SYNC_END.len()) {
+                parser.advance(term, &bytes[..end]);
+                self.apply_pending_marks(parser, term);
+                bytes = &bytes[end..];
+            }
+        }
+
+        parser.advance(term, bytes);
+        self.apply_pending_marks(parser, term);
+    }
+
+    /// Apply prompt marks deferred by a synchronized update, once it has ended.
+    fn apply_pending_marks<T: EventListener>(
+        &mut self,
+        parser: &ansi::Processor,
+        term: &mut Term<T>,
+    ) {
+        if !self.pending_marks.is_empty() && parser.sync_timeout().sync_timeout().is_none() {
+            term.mark_prompt(mem::take(&mut self.pending_marks));
+        }
+    }
+
+    /// Apply the current OSC.
+    fn dispatch<T: EventListener>(&mut self, parser: &ansi::Processor, term: &mut Term<T>) {
+        if self.buffer.len() >= MAX_OSC_LEN {
+            return;
+        }
+
+        let osc = String::from_utf8_lossy(&self.buffer);
+        let (title, body) = match osc.split_once(';') {
+            // ConEmu uses OSC 9 with numeric parameters for other purposes, like progress reports.
+            Some(("9", body)) if !is_conemu(body) => (None, body),
+            Some(("777", params)) => match params.split_once(';') {
+                Some(("notify", params)) => match params.split_once(';') {
+                    Some((title, body)) => (Some(title), body),
+                    None => (Some(params), ""),
+                },
+                _ => return,
+            },
+            Some(("133", params)) => {
+                let command = params.split(';').next().unwrap_or_default();
+                match PromptMarks::from_osc(command) {
+                    Some(mark) if parser.sync_timeout().sync_timeout().is_some() => {
+                        self.pending_marks.insert(mark);
+                    },
+                    Some(mark) => term.mark_prompt(mark),
+                    None => (),
+                }
+                return;
+            },
+            _ => return,
+        };
+
+        term.desktop_notification(title.map(str::to_owned), body.to_owned());
+    }
+}
+
+/// Check if OSC 9 parameters are a ConEmu extension.
+fn is_conemu(params: &str) -> bool {
+    let command = params.split(';').next().unwrap_or_default();
+    !command.is_empty() && command.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::{self, Receiver, Sender};
+
+    use crate::event::{Event, VoidListener};
+    use crate::index::Line;
+    use crate::term::test::TermSize;
+    use crate::term::Config;
+
+    struct NotificationListener(Sender<(Option<String>, String)>);
+
+    impl EventListener for NotificationListener {
+        fn send_event(&self, event: Event) {
+            if let Event::Notification(title, body) = event {
+                let _ = self.0.send((title, body));
+            }
+        }
+    }
+
+    fn parse(chunks: &[&[u8]]) -> Receiver<(Option<String>, String)> {
+        let (tx, rx) = mpsc::channel();
+        let size = TermSize::new(10, 5);
+        let mut term = Term::new(Config::default(), &size, NotificationListener(tx));
+
+        let mut parser = ansi::Processor::new();
+        let mut osc_parser = OscParser::new();
+        for chunk in chunks {
+            osc_parser.advance(&mut parser, &mut term, chunk);
+        }
+
+        rx
+    }
+
+    #[test]
+    fn osc_9() {
+        let rx = parse(&[b"text\x1b]9;Build done\x07\x1b[0m"]);
+        assert_eq!(rx.try_recv(), Ok((None, "Build done".into())));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn osc_777() {
+        let rx = parse(&[b"\x1b]777;notify;Title;Body; with separator\x1b\\"]);
+        assert_eq!(rx.try_recv(), Ok((Some("Title".into()), "Body; with separator".into())));
+    }
+
+    #[test]
+    fn split_across_chunks() {
+        let rx = parse(&[b"\x1b", b"]9;Do", b"ne\x1b", b"\\"]);
+        assert_eq!(rx.try_recv(), Ok((None, "Done".into())));
+    }
+
+    #[test]
+    fn ignored_sequences() {
+        let rx = parse(&[
+            b"\x1b]9;4;1;50\x07",
+            b"\x1b]777;other;Title;Body\x07",
+            b"\x1b]0;Title\x07",
+            b"\x1b]9;Aborted\x18",
+            b"\x1b[9;1H",
+        ]);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn disabled() {
+        let (tx, rx) = mpsc::channel();
+        let size = TermSize::new(10, 5);
+        let config = Config { notifications: false, ..Config::default() };
+        let mut term = Term::new(config, &size, NotificationListener(tx));
+
+        let mut parser = ansi::Processor::new();
+        OscParser::new().advance(&mut parser, &mut term, b"\x1b]9;Done\x07");
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn prompt_marks() {
+        let size = TermSize::new(10, 5);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+
+        let mut parser = ansi::Processor::new();
+        let output = b"\x1b]133;A\x07$ \x1b]133;B\x07ls\r\n\x1b]133;C\x1b\\out\r\n\x1b]133;D;0\x07";
+        OscParser::new().advance(&mut parser, &mut term, output);
+
+        let marks = PromptMarks::PROMPT_START | PromptMarks::INPUT_START;
+        assert_eq!(term.grid()[Line(0)].marks(), marks);
+        assert_eq!(term.grid()[Line(1)].marks(), PromptMarks::OUTPUT_START);
+        assert_eq!(term.grid()[Line(2)].marks(), PromptMarks::COMMAND_END);
+    }
+
+    #[test]
+    fn prompt_marks_during_sync() {
+        let size = TermSize::new(10, 5);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+
+        let mut parser = ansi::Processor::new();
+        let mut osc_parser = OscParser::new();
+        let output = b"\x1b[?2026h\r\n\x1b]133;A\x07$ \x1b[?2026l\r\n";
+        osc_parser.advance(&mut parser, &mut term, output);
+
+        assert_eq!(term.grid()[Line(0)].marks(), PromptMarks::empty());
+        assert_eq!(term.grid()[Line(1)].marks(), PromptMarks::PROMPT_START);
+        assert_eq!(term.grid()[Line(2)].marks(), PromptMarks::empty());
+
+        // Marks are applied when the synchronized update expires.
+        osc_parser.advance(&mut parser, &mut term, b"\x1b[?2026h\r\n\x1b]133;A\x07");
+        assert_eq!(term.grid()[Line(3)].marks(), PromptMarks::empty());
+        osc_parser.stop_sync(&mut parser, &mut term);
+        assert_eq!(term.grid()[Line(3)].marks(), PromptMarks::PROMPT_START);
+    }
+}
@@ -0,0 +1,235 @@
+//! Semantic prompt marks set using OSC 133.
+
+use std::ops::RangeInclusive;
+
+use bitflags::bitflags;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::event::{Event, EventListener};
+use crate::grid::{Dimensions, Scroll};
+use crate::index::{Column, Direction, Line, Point};
+use crate::term::Term;
+
+bitflags! {
+    /// Command boundaries reported by the shell on a line.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub struct PromptMarks: u8 {
+        /// Start of the prompt (`OSC 133 ; A`).
+        const PROMPT_START = 0b0001;
+        /// Start of the command input (`OSC 133 ; B`).
+        const INPUT_START  = 0b0010;
+        /// Start of the command output (`OSC 133 ; C`).
+        const OUTPUT_START = 0b0100;
+        /// End of the command (`OSC 133 ; D`).
+        const COMMAND_END  = 0b1000;
+    }
+}
+
+impl PromptMarks {
+    /// Parse the mark of an `OSC 133` sequence.
+    pub fn from_osc(command: &str) -> Option<Self> {
+        match command {
+            "A" => Some(Self::PROMPT_START),
+            "B" => Some(Self::INPUT_START),
+            "C" => Some(Self::OUTPUT_START),
+            "D" => Some(Self::COMMAND_END),
+            _ => None,
+        }
+    }
+}
+
+impl<T: EventListener> Term<T> {
+    /// Mark the cursor line with a command boundary.
+    ///
+    /// Sends [`Event::CommandFinished`] for the end of a command.
+    pub fn mark_prompt(&mut self, mark: PromptMarks) {
+        let line = self.grid.cursor.point.line;
+        self.grid[line].marks.insert(mark);
+
+        if mark.contains(PromptMarks::COMMAND_END) {
+            self.event_proxy.send_event(Event::CommandFinished);
+        }
+    }
+}
+
+impl<T> Term<T> {
+    /// Find the closest line with a mark in the specified direction.
+    ///
+    /// The search starts at the line after `line` and does not wrap around.
+    pub fn find_mark(&self, line: Line, direction: Direction, mark: PromptMarks) -> Option<Line> {
+        let topmost_line = self.topmost_line();
+        let bottommost_line = self.bottommost_line();
+
+        let mut line = line;
+        loop {
+            line = match direction {
+                Direction::Left if line > topmost_line => line - 1,
+                Direction::Right if line < bottommost_line => line + 1,
+                _ => return None,
+            };
+
+            if self.grid[line].marks.intersects(mark) {
+                return Some(line);
+            }
+        }
+    }
+
+    /// Viewport scroll moving the closest prompt in the specified direction to the top.
+    ///
+    /// The search starts at the top of the viewport, which is scrolled to the bottom when there
+    /// is no prompt below it.
+    pub fn prompt_scroll(&self, direction: Direction) -> Option<Scroll> {
+        let display_offset = self.grid.display_offset() as i32;
+        let prompt = self.find_mark(Line(-display_offset), direction, PromptMarks::PROMPT_START);
+
+        match (prompt, direction) {
+            (Some(prompt), _) => Some(Scroll::Delta((-prompt.0).max(0) - display_offset)),
+            (None, Direction::Right) => Some(Scroll::Bottom),
+            (None, Direction::Left) => None,
+        }
+    }
+
+    /// Output of the command preceding the prompt at the cursor.
+    ///
+    /// The output spans all lines from the command's output start up to the current prompt.
+    /// Returns [`None`] if the command did not produce any output or was not marked by the shell.
+    pub fn last_command_output(&self) -> Option<RangeInclusive<Point>> {
+        let cursor_line = self.grid.cursor.point.line;
+        let prompt = if self.grid[cursor_line].marks.contains(PromptMarks::PROMPT_START) {
+            cursor_line
+        } else {
+            self.find_mark(cursor_line, Direction::Left, PromptMarks::PROMPT_START)?
+        };
+
+        // Stop at the previous prompt, since commands without output have no output start.
+        let marks = PromptMarks::PROMPT_START | PromptMarks::OUTPUT_START;
+        let start = self.find_mark(prompt, Direction::Left, marks)?;
+        if self.grid[start].marks.contains(PromptMarks::PROMPT_START) {
+            return None;
+        }
+
+        Some(Point::new(start, Column(0))..=Point::new(prompt - 1, self.last_column()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::{self, Sender};
+
+    use crate::event::VoidListener;
+    use crate::term::test::TermSize;
+    use crate::term::Config;
+    use crate::vte::ansi::Handler;
+
+    /// Create a terminal with three lines of history and prompts on lines -3, 0 and 1.
+    fn prompts() -> Term<VoidListener> {
+        let size = TermSize::new(5, 3);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+
+        for line in 0..6 {
+            if [0, 3, 4].contains(&line) {
+                term.mark_prompt(PromptMarks::PROMPT_START);
+            }
+            if line != 5 {
+                term.linefeed();
+            }
+        }
+
+        term
+    }
+
+    #[test]
+    fn mark_cursor_line() {
+        let size = TermSize::new(5, 3);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+        term.linefeed();
+        term.mark_prompt(PromptMarks::OUTPUT_START);
+
+        assert_eq!(term.grid[Line(1)].marks, PromptMarks::OUTPUT_START);
+        assert_eq!(term.grid[Line(0)].marks, PromptMarks::empty());
+    }
+
+    #[test]
+    fn command_finished_event() {
+        struct Listener(Sender<Event>);
+
+        impl EventListener for Listener {
+            fn send_event(&self, event: Event) {
+                let _ = self.0.send(event);
+            }
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let size = TermSize::new(5, 3);
+        let mut term = Term::new(Config::default(), &size, Listener(tx));
+
+        term.mark_prompt(PromptMarks::OUTPUT_START);
+        assert!(rx.try_recv().is_err());
+
+        term.mark_prompt(PromptMarks::COMMAND_END | PromptMarks::PROMPT_START);
+        assert!(matches!(rx.try_recv(), Ok(Event::CommandFinished)));
+    }
+
+    #[test]
+    fn find_prompt() {
+        let term = prompts();
+        let mark = PromptMarks::PROMPT_START;
+
+        assert_eq!(term.find_mark(Line(-3), Direction::Right, mark), Some(Line(0)));
+        assert_eq!(term.find_mark(Line(0), Direction::Right, mark), Some(Line(1)));
+        assert_eq!(term.find_mark(Line(1), Direction::Right, mark), None);
+        assert_eq!(term.find_mark(Line(2), Direction::Left, mark), Some(Line(1)));
+        assert_eq!(term.find_mark(Line(0), Direction::Left, mark), Some(Line(-3)));
+        assert_eq!(term.find_mark(Line(-3), Direction::Left, mark), None);
+    }
+
+    #[test]
+    fn prompt_scroll() {
+        let mut term = prompts();
+
+        assert!(matches!(term.prompt_scroll(Direction::Left), Some(Scroll::Delta(3))));
+        term.scroll_display(Scroll::Delta(3));
+
+        assert!(term.prompt_scroll(Direction::Left).is_none());
+        assert!(matches!(term.prompt_scroll(Direction::Right), Some(Scroll::Delta(-3))));
+        term.scroll_display(Scroll::Delta(-3));
+
+        assert!(matches!(term.prompt_scroll(Direction::Right), Some(Scroll::Delta(0))));
+        term.grid[Line(1)].marks = PromptMarks::empty();
+        assert!(matches!(term.prompt_scroll(Direction::Right), Some(Scroll::Bottom)));
+    }
+
+    #[test]
+    fn last_command_output() {
+        let size = TermSize::new(5, 10);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+
+        // Prompt with a command producing two lines of output.
+        term.mark_prompt(PromptMarks::PROMPT_START | PromptMarks::INPUT_START);
+        term.linefeed();
+        term.mark_prompt(PromptMarks::OUTPUT_START);
+        term.linefeed();
+        term.linefeed();
+        term.mark_prompt(PromptMarks::COMMAND_END | PromptMarks::PROMPT_START);
+
+        let output = Point::new(Line(1), Column(0))..=Point::new(Line(2), Column(4));
+        assert_eq!(term.last_command_output(), Some(output.clone()));
+
+        // Cursor below the prompt, while the command is being edited.
+        term.linefeed();
+        assert_eq!(term.last_command_output(), Some(output));
+
+        // Command without any output.
+        term.mark_prompt(PromptMarks::OUTPUT_START | PromptMarks::COMMAND_END);
+        term.mark_prompt(PromptMarks::PROMPT_START);
+        assert_eq!(term.last_command_output(), None);
+
+        // Missing prompt.
+        let term = Term::new(Config::default(), &size, VoidListener);
+        assert_eq!(term.last_command_output(), None);
+    }
+}
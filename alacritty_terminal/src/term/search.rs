@@ -254,6 +254,21 @@ impl<T> Term<T> {
         Some(match_start..=match_end)
     }
 
+    /// Find all regex matches in the scrollback history and the visible region.
+    ///
+    /// Matches are returned from the top of the history to the bottom of the screen.
+    pub fn search_all(&self, regex: &RegexSearch) -> Vec<Match> {
+        let mut regex = regex.clone();
+        self.search_iter(&mut regex).collect()
+    }
+
+    /// Iterate lazily over all regex matches, starting at the top of the scrollback history.
+    pub fn search_iter<'a>(&'a self, regex: &'a mut RegexSearch) -> RegexIter<'a, T> {
+        let start = Point::new(self.topmost_line(), Column(0));
+        let end = Point::new(self.bottommost_line(), self.last_column());
+        RegexIter::new(start, end, Direction::Right, self, regex)
+    }
+
     /// Find the next regex match.
     ///
     /// This will always return the side of the first match which is farthest from the start point.
@@ -512,6 +527,23 @@ impl<T> Term<T> {
         None
     }
 
+    /// Find the regex match containing a point, within the point's logical line.
+    pub fn line_regex_match_at(&self, regex: &mut RegexSearch, point: Point) -> Option<Match> {
+        let start = self.line_search_left(point);
+        let end = self.line_search_right(point);
+        RegexIter::new(start, end, Direction::Right, self, regex)
+            .take_while(|rm| *rm.start() <= point)
+            .find(|rm| rm.contains(&point))
+    }
+
+    /// Find the first semantic selection pattern matching at a point.
+    pub fn semantic_pattern_match(&self, point: Point) -> Option<Match> {
+        self.semantic_patterns
+            .borrow_mut()
+            .iter_mut()
+            .find_map(|regex| self.line_regex_match_at(regex, point))
+    }
+
     /// Find left end of semantic block.
     #[must_use]
     pub fn semantic_search_left(&self, point: Point) -> Point {
@@ -688,9 +720,12 @@ impl<T> Iterator for RegexIter<'_, T> {
 mod tests {
     use super::*;
 
-    use crate::index::{Column, Line};
+    use crate::event::VoidListener;
+    use crate::index::{Column, Line, Side};
+    use crate::selection::{Selection, SelectionType};
     use crate::term::test::{mock_term, TermSize};
     use crate::term::Config;
+    use crate::vte::ansi::Handler;
 
     #[test]
     fn regex_right() {
@@ -1103,6 +1138,72 @@ mod tests {
         assert_eq!(term.regex_search_left(&mut regex, start, end), Some(match_start..=match_end));
     }
 
+    #[test]
+    fn search_all_history() {
+        let size = TermSize::new(5, 2);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+        for c in "ab\nxy\nab\nab".chars() {
+            match c {
+                '\n' => {
+                    term.carriage_return();
+                    term.linefeed();
+                },
+                c => term.input(c),
+            }
+        }
+        assert_eq!(term.history_size(), 2);
+
+        let regex = RegexSearch::new("ab").unwrap();
+        let matches = term.search_all(&regex);
+        let expected: Vec<_> = [-2, 0, 1]
+            .into_iter()
+            .map(|line| Point::new(Line(line), Column(0))..=Point::new(Line(line), Column(1)))
+            .collect();
+        assert_eq!(matches, expected);
+    }
+
+    #[test]
+    fn line_regex_match() {
+        let term = mock_term("say \"a b\" or \"c\"\r\n\"d\"");
+        let mut regex = RegexSearch::new("\"[^\"]*\"").unwrap();
+
+        let mut match_at =
+            |column| term.line_regex_match_at(&mut regex, Point::new(Line(0), Column(column)));
+        let first = Point::new(Line(0), Column(4))..=Point::new(Line(0), Column(8));
+        let second = Point::new(Line(0), Column(13))..=Point::new(Line(0), Column(15));
+
+        assert_eq!(match_at(0), None);
+        assert_eq!(match_at(4), Some(first.clone()));
+        assert_eq!(match_at(6), Some(first));
+        assert_eq!(match_at(10), None);
+        assert_eq!(match_at(15), Some(second));
+    }
+
+    #[test]
+    fn semantic_patterns() {
+        let mut term = mock_term("say \"a b\" or deadbeef");
+        let semantic_patterns = vec![String::from("\"[^\"]*\""), String::from("[0-9a-f]{8}")];
+        term.set_options(Config { semantic_patterns, ..Config::default() });
+
+        let selection_at = |start, end| {
+            let mut selection = Selection::new(
+                SelectionType::Semantic,
+                Point::new(Line(0), Column(start)),
+                Side::Left,
+            );
+            selection.update(Point::new(Line(0), Column(end)), Side::Right);
+            selection.to_range(&term).map(|range| (range.start.column.0, range.end.column.0))
+        };
+
+        // Patterns are selected in their entirety, including the escape chars.
+        assert_eq!(selection_at(6, 6), Some((4, 8)));
+        assert_eq!(selection_at(16, 16), Some((13, 20)));
+
+        // Points outside of patterns fall back to the escape chars.
+        assert_eq!(selection_at(0, 6), Some((0, 8)));
+        assert_eq!(selection_at(11, 11), Some((10, 11)));
+    }
+
     #[test]
     fn nfa_compile_error() {
         assert!(RegexSearch::new("[0-9A-Za-z]{9999999}").is_err());
@@ -17,6 +17,8 @@ pub mod windows;
 #[cfg(windows)]
 pub use self::windows::*;
 
+pub mod wsl;
+
 /// Configuration for the `Pty` interface.
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct Options {
@@ -33,6 +35,9 @@ pub struct Options {
 
     /// Extra environment variables.
     pub env: HashMap<String, String>,
+
+    /// Environment variables which should not be inherited by the shell.
+    pub env_remove: Vec<String>,
 }
 
 /// Shell options.
@@ -48,6 +53,16 @@ impl Shell {
     pub fn new(program: String, args: Vec<String>) -> Self {
         Self { program, args }
     }
+
+    /// Path to the shell program.
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    /// Arguments passed to the shell.
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
 }
 
 /// Stream read and/or write behavior.
@@ -86,6 +101,13 @@ pub trait EventedPty: EventedReadWrite {
     ///
     /// Returns `Some(event)` on success, or `None` if there are no events to retrieve.
     fn next_child_event(&mut self) -> Option<ChildEvent>;
+
+    /// Whether the PTY's process was adopted from another process.
+    ///
+    /// Since adopted processes cannot be waited on, the PTY hanging up is treated as their exit.
+    fn is_adopted(&self) -> bool {
+        false
+    }
 }
 
 /// Setup environment variables.
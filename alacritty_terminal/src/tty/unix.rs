@@ -1,11 +1,13 @@
 //! TTY related functionality.
 
 use std::ffi::CStr;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::fs;
 use std::fs::File;
 use std::io::{Error, ErrorKind, Read, Result};
 use std::mem::MaybeUninit;
 use std::os::fd::OwnedFd;
-use std::os::unix::io::AsRawFd;
+use std::os::unix::io::{AsRawFd, RawFd};
 use std::os::unix::net::UnixStream;
 use std::os::unix::process::CommandExt;
 use std::process::{Child, Command};
@@ -13,7 +15,7 @@ use std::sync::Arc;
 use std::{env, ptr};
 
 use libc::{c_int, TIOCSCTTY};
-use log::error;
+use log::{debug, error};
 use polling::{Event, PollMode, Poller};
 use rustix_openpty::openpty;
 use rustix_openpty::rustix::termios::Winsize;
@@ -23,6 +25,7 @@ use signal_hook::low_level::{pipe as signal_pipe, unregister as unregister_signa
 use signal_hook::{consts as sigconsts, SigId};
 
 use crate::event::{OnResize, WindowSize};
+use crate::thread;
 use crate::tty::{ChildEvent, EventedPty, EventedReadWrite, Options};
 
 // Interest in PTY read/writes.
@@ -80,11 +83,11 @@ fn get_pw_entry(buf: &mut [i8; 1024]) -> Result<Passwd<'_>> {
     let entry = unsafe { entry.assume_init() };
 
     if status < 0 {
-        return Err(Error::new(ErrorKind::Other, "getpwuid_r failed"));
+        return Err(Error::other("getpwuid_r failed"));
     }
 
     if res.is_null() {
-        return Err(Error::new(ErrorKind::Other, "pw not found"));
+        return Err(Error::other("pw not found"));
     }
 
     // Sanity check.
@@ -99,20 +102,41 @@ fn get_pw_entry(buf: &mut [i8; 1024]) -> Result<Passwd<'_>> {
 }
 
 pub struct Pty {
-    child: Child,
+    /// Child process, [`None`] if the PTY was adopted from another process.
+    child: Option<Child>,
+    pid: u32,
     file: File,
     signals: UnixStream,
     sig_id: SigId,
 }
 
 impl Pty {
-    pub fn child(&self) -> &Child {
-        &self.child
+    pub fn child(&self) -> Option<&Child> {
+        self.child.as_ref()
+    }
+
+    /// Process ID of the PTY's shell.
+    pub fn pid(&self) -> u32 {
+        self.pid
     }
 
     pub fn file(&self) -> &File {
         &self.file
     }
+
+    /// Release the PTY without hanging up its shell.
+    ///
+    /// The returned file descriptor can be passed to another process, which can take over the
+    /// shell using [`adopt`]. The shell is still reaped by this process once it exits.
+    pub fn detach(mut self) -> Result<OwnedFd> {
+        let fd = OwnedFd::from(self.file.try_clone()?);
+
+        if let Some(mut child) = self.child.take() {
+            thread::spawn_named("PTY child reaper", move || child.wait());
+        }
+
+        Ok(fd)
+    }
 }
 
 /// User information that is required for a new shell session.
@@ -189,6 +213,19 @@ pub fn new(config: &Options, window_size: WindowSize, window_id: u64) -> Result<
     from_fd(config, window_id, master, slave)
 }
 
+/// Create a TTY from a PTY detached from another process using [`Pty::detach`].
+///
+/// Since the shell is not a child of this process, its exit is detected by the PTY hanging up.
+pub fn adopt(master: OwnedFd, pid: u32) -> Result<Pty> {
+    let (signals, sig_id) = child_signal_pipe()?;
+
+    unsafe {
+        set_nonblocking(master.as_raw_fd());
+    }
+
+    Ok(Pty { child: None, pid, file: File::from(master), signals, sig_id })
+}
+
 /// Create a new TTY from a PTY's file descriptors.
 pub fn from_fd(config: &Options, window_id: u64, master: OwnedFd, slave: OwnedFd) -> Result<Pty> {
     let master_fd = master.as_raw_fd();
@@ -223,6 +260,9 @@ pub fn from_fd(config: &Options, window_id: u64, master: OwnedFd, slave: OwnedFd
     builder.env("HOME", user.home);
     // Set Window ID for clients relying on X11 hacks.
     builder.env("WINDOWID", window_id);
+    for key in &config.env_remove {
+        builder.env_remove(key);
+    }
     for (key, value) in &config.env {
         builder.env(key, value);
     }
@@ -236,7 +276,7 @@ pub fn from_fd(config: &Options, window_id: u64, master: OwnedFd, slave: OwnedFd
             // Create a new process group.
             let err = libc::setsid();
             if err == -1 {
-                return Err(Error::new(ErrorKind::Other, "Failed to set session id"));
+                return Err(Error::other("Failed to set session id"));
             }
 
             set_controlling_terminal(slave_fd);
@@ -262,14 +302,7 @@ pub fn from_fd(config: &Options, window_id: u64, master: OwnedFd, slave: OwnedFd
     }
 
     // Prepare signal handling before spawning child.
-    let (signals, sig_id) = {
-        let (sender, recv) = UnixStream::pair()?;
-
-        // Register the recv end of the pipe for SIGCHLD.
-        let sig_id = signal_pipe::register(sigconsts::SIGCHLD, sender)?;
-        recv.set_nonblocking(true)?;
-        (recv, sig_id)
-    };
+    let (signals, sig_id) = child_signal_pipe()?;
 
     match builder.spawn() {
         Ok(child) => {
@@ -279,7 +312,8 @@ pub fn from_fd(config: &Options, window_id: u64, master: OwnedFd, slave: OwnedFd
                 set_nonblocking(master_fd);
             }
 
-            Ok(Pty { child, file: File::from(master), signals, sig_id })
+            let pid = child.id();
+            Ok(Pty { child: Some(child), pid, file: File::from(master), signals, sig_id })
         },
         Err(err) => Err(Error::new(
             err.kind(),
@@ -292,17 +326,93 @@ pub fn from_fd(config: &Options, window_id: u64, master: OwnedFd, slave: OwnedFd
     }
 }
 
+/// Find a stopped job of the shell.
+///
+/// Returns the process group of a stopped child process of the shell, like a program which was
+/// suspended using `^Z`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn stopped_job(shell_pid: u32) -> Option<u32> {
+    for task in fs::read_dir(format!("/proc/{shell_pid}/task")).ok()?.flatten() {
+        let children = match fs::read_to_string(task.path().join("children")) {
+            Ok(children) => children,
+            Err(_) => continue,
+        };
+
+        for pid in children.split_whitespace() {
+            let stat = match fs::read_to_string(format!("/proc/{pid}/stat")) {
+                Ok(stat) => stat,
+                Err(_) => continue,
+            };
+
+            // Skip the command name, since it can contain whitespace.
+            let mut fields = stat.rsplit_once(')').map(|(_, fields)| fields.split_whitespace())?;
+            if fields.next() == Some("T") {
+                return fields.nth(1)?.parse().ok();
+            }
+        }
+    }
+
+    None
+}
+
+/// Find a stopped job of the shell.
+///
+/// Stopped jobs can only be detected on Linux, so this always returns [`None`].
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub fn stopped_job(_shell_pid: u32) -> Option<u32> {
+    None
+}
+
+/// Continue a stopped job in the foreground.
+///
+/// The job is moved to the foreground process group of the terminal first, so it doesn't get
+/// stopped again when accessing it. Since only members of the terminal's session can change its
+/// foreground process group, the job is continued in the background when this is not permitted.
+pub fn resume_job(master_fd: RawFd, process_group: u32) -> Result<()> {
+    let process_group = process_group as libc::pid_t;
+    if unsafe { libc::tcsetpgrp(master_fd, process_group) } == -1 {
+        debug!("Unable to move stopped job to the foreground: {}", Error::last_os_error());
+    }
+
+    match unsafe { libc::kill(-process_group, libc::SIGCONT) } {
+        -1 => Err(Error::last_os_error()),
+        _ => Ok(()),
+    }
+}
+
+/// Check if a process other than the shell is running in the foreground.
+pub fn foreground_process_running(master_fd: RawFd, shell_pid: u32) -> bool {
+    let process_group = unsafe { libc::tcgetpgrp(master_fd) };
+    process_group > 0 && process_group as u32 != shell_pid
+}
+
+/// Create a pipe which receives a message for every `SIGCHLD`.
+fn child_signal_pipe() -> Result<(UnixStream, SigId)> {
+    let (sender, recv) = UnixStream::pair()?;
+
+    // Register the recv end of the pipe for SIGCHLD.
+    let sig_id = signal_pipe::register(sigconsts::SIGCHLD, sender)?;
+    recv.set_nonblocking(true)?;
+    Ok((recv, sig_id))
+}
+
 impl Drop for Pty {
     fn drop(&mut self) {
         // Make sure the PTY is terminated properly.
-        unsafe {
-            libc::kill(self.child.id() as i32, libc::SIGHUP);
+        //
+        // Processes which aren't our children are hung up once the last PTY handle is closed.
+        if let Some(child) = &self.child {
+            unsafe {
+                libc::kill(child.id() as i32, libc::SIGHUP);
+            }
         }
 
         // Clear signal-hook handler.
         unregister_signal(self.sig_id);
 
-        let _ = self.child.wait();
+        if let Some(child) = &mut self.child {
+            let _ = child.wait();
+        }
     }
 }
 
@@ -366,6 +476,11 @@ impl EventedReadWrite for Pty {
 }
 
 impl EventedPty for Pty {
+    #[inline]
+    fn is_adopted(&self) -> bool {
+        self.child.is_none()
+    }
+
     #[inline]
     fn next_child_event(&mut self) -> Option<ChildEvent> {
         // See if there has been a SIGCHLD.
@@ -377,8 +492,11 @@ impl EventedPty for Pty {
             return None;
         }
 
+        // Adopted processes are not our children, so they never cause a `SIGCHLD`.
+        let child = self.child.as_mut()?;
+
         // Match on the child process.
-        match self.child.try_wait() {
+        match child.try_wait() {
             Err(err) => {
                 error!("Error checking child process termination: {}", err);
                 None
@@ -434,3 +552,28 @@ fn test_get_pw_entry() {
     let mut buf: [i8; 1024] = [0; 1024];
     let _pw = get_pw_entry(&mut buf).unwrap();
 }
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn test_stopped_job() {
+    use std::time::{Duration, Instant};
+
+    let mut child = Command::new("sleep").arg("30").spawn().unwrap();
+    let pid = child.id() as libc::pid_t;
+    let pgid = unsafe { libc::getpgid(pid) } as u32;
+
+    assert_eq!(stopped_job(std::process::id()), None);
+
+    unsafe { libc::kill(pid, libc::SIGSTOP) };
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let mut stopped = None;
+    while stopped.is_none() && Instant::now() < deadline {
+        stopped = stopped_job(std::process::id());
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    assert_eq!(stopped, Some(pgid));
+}
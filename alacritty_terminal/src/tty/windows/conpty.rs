@@ -23,7 +23,7 @@ use windows_sys::Win32::System::Threading::{
 use crate::event::{OnResize, WindowSize};
 use crate::tty::windows::blocking::{UnblockedReader, UnblockedWriter};
 use crate::tty::windows::child::ChildExitWatcher;
-use crate::tty::windows::{cmdline, win32_string, Pty};
+use crate::tty::windows::{cmdline, win32_string, wsl_working_directory, Pty};
 use crate::tty::Options;
 
 const PIPE_CAPACITY: usize = crate::event_loop::READ_BUFFER_SIZE;
@@ -203,9 +203,13 @@ pub fn new(config: &Options, window_size: WindowSize) -> Result<Pty> {
 
     // Prepare child process creation arguments.
     let cmdline = win32_string(&cmdline(config));
-    let cwd = config.working_directory.as_ref().map(win32_string);
+    // WSL receives its working directory as an argument instead.
+    let cwd = match wsl_working_directory(config) {
+        Some(_) => None,
+        None => config.working_directory.as_ref().map(win32_string),
+    };
     let mut creation_flags = EXTENDED_STARTUPINFO_PRESENT;
-    let custom_env_block = convert_custom_env(&config.env);
+    let custom_env_block = convert_custom_env(&config.env, &config.env_remove);
     let custom_env_block_pointer = match &custom_env_block {
         Some(custom_env_block) => {
             creation_flags |= CREATE_UNICODE_ENVIRONMENT;
@@ -247,14 +251,23 @@ pub fn new(config: &Options, window_size: WindowSize) -> Result<Pty> {
 // deduplicating environment variables, so do that here while converting.
 //
 // https://learn.microsoft.com/en-us/previous-versions/troubleshoot/windows/win32/createprocess-cannot-eliminate-duplicate-variables#environment-variables
-fn convert_custom_env(custom_env: &HashMap<String, String>) -> Option<Vec<u16>> {
+fn convert_custom_env(
+    custom_env: &HashMap<String, String>,
+    removed_env: &[String],
+) -> Option<Vec<u16>> {
     // Windows inherits parent's env when no `lpEnvironment` parameter is specified.
-    if custom_env.is_empty() {
+    if custom_env.is_empty() && removed_env.is_empty() {
         return None;
     }
 
     let mut converted_block = Vec::new();
     let mut all_env_keys = HashSet::new();
+
+    // Mark removed variables as present, to prevent inheriting them.
+    for removed_key in removed_env {
+        all_env_keys.insert(OsStr::new(removed_key).to_ascii_uppercase());
+    }
+
     for (custom_key, custom_value) in custom_env {
         let custom_key_os = OsStr::new(custom_key);
         if all_env_keys.insert(custom_key_os.to_ascii_uppercase()) {
@@ -1,13 +1,19 @@
 use std::ffi::OsStr;
 use std::io::{self, Result};
 use std::iter::once;
+use std::mem;
 use std::os::windows::ffi::OsStrExt;
 use std::sync::mpsc::TryRecvError;
 use std::sync::Arc;
 
+use windows_sys::Win32::Foundation::{CloseHandle, INVALID_HANDLE_VALUE};
+use windows_sys::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+};
+
 use crate::event::{OnResize, WindowSize};
 use crate::tty::windows::child::ChildExitWatcher;
-use crate::tty::{ChildEvent, EventedPty, EventedReadWrite, Options, Shell};
+use crate::tty::{wsl, ChildEvent, EventedPty, EventedReadWrite, Options, Shell};
 
 mod blocking;
 mod child;
@@ -129,10 +135,56 @@ fn cmdline(config: &Options) -> String {
     let default_shell = Shell::new("powershell".to_owned(), Vec::new());
     let shell = config.shell.as_ref().unwrap_or(&default_shell);
 
-    once(shell.program.as_str())
+    let mut cmdline = once(shell.program.as_str())
         .chain(shell.args.iter().map(|s| s.as_str()))
         .collect::<Vec<_>>()
-        .join(" ")
+        .join(" ");
+
+    // Start WSL in the working directory, since it can't inherit directories like `\\wsl$`.
+    if let Some(working_directory) = wsl_working_directory(config) {
+        cmdline.push_str(&format!(" --cd \"{working_directory}\""));
+    }
+
+    cmdline
+}
+
+/// Working directory inside WSL, when the shell launches a WSL distribution.
+fn wsl_working_directory(config: &Options) -> Option<String> {
+    let shell = config.shell.as_ref().filter(|shell| wsl::is_wsl(&shell.program))?;
+    if shell.args.iter().any(|arg| arg == "--cd" || arg == "~") {
+        return None;
+    }
+
+    wsl::to_wsl_path(config.working_directory.as_ref()?.to_str()?)
+}
+
+/// Check if a process other than the shell is running in the foreground.
+///
+/// Windows has no foreground process groups, so this checks for any child process of the shell.
+pub fn foreground_process_running(shell_pid: u32) -> bool {
+    // The shell's process ID is unknown if it has already exited.
+    if shell_pid == 0 {
+        return false;
+    }
+
+    let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) };
+    if snapshot == INVALID_HANDLE_VALUE {
+        return false;
+    }
+
+    let mut entry: PROCESSENTRY32W = unsafe { mem::zeroed() };
+    entry.dwSize = mem::size_of::<PROCESSENTRY32W>() as u32;
+
+    let mut running = false;
+    let mut valid = unsafe { Process32FirstW(snapshot, &mut entry) } != 0;
+    while valid && !running {
+        running = entry.th32ParentProcessID == shell_pid;
+        valid = unsafe { Process32NextW(snapshot, &mut entry) } != 0;
+    }
+
+    unsafe { CloseHandle(snapshot) };
+
+    running
 }
 
 /// Converts the string slice into a Windows-standard representation for "W"-
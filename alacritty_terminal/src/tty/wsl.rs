@@ -0,0 +1,107 @@
+//! Path translation for the Windows Subsystem for Linux.
+
+/// Check if a shell program launches a WSL distribution.
+pub fn is_wsl(program: &str) -> bool {
+    let name = program.rsplit(['/', '\\']).next().unwrap_or_default();
+    name.eq_ignore_ascii_case("wsl") || name.eq_ignore_ascii_case("wsl.exe")
+}
+
+/// Convert a Windows path to the corresponding path inside WSL.
+///
+/// Drives are mapped to their `/mnt` mountpoints, while paths inside a distribution's
+/// `\\wsl.localhost` or `\\wsl$` share are mapped to the distribution root. Returns [`None`] for
+/// relative paths and other network shares.
+pub fn to_wsl_path(path: &str) -> Option<String> {
+    let path = path.replace('\\', "/");
+
+    // Paths inside a distribution, like `\\wsl.localhost\Ubuntu\home`.
+    let share = strip_prefix_ignore_case(&path, "//wsl.localhost/")
+        .or_else(|| strip_prefix_ignore_case(&path, "//wsl$/"));
+    if let Some(share) = share {
+        let path = share.split_once('/').map_or("", |(_distribution, path)| path);
+        return Some(format!("/{}", path.trim_end_matches('/')));
+    }
+
+    // Drive paths, like `C:\Users`.
+    let mut chars = path.chars();
+    let drive = chars.next().filter(char::is_ascii_alphabetic)?;
+    let path = chars.as_str().strip_prefix(':')?;
+    if !path.is_empty() && !path.starts_with('/') {
+        return None;
+    }
+
+    let drive = drive.to_ascii_lowercase();
+    Some(format!("/mnt/{drive}{}", path.trim_end_matches('/')))
+}
+
+/// Convert an absolute path inside a WSL distribution to a Windows path.
+///
+/// Paths below the `/mnt` drive mountpoints are mapped back to their drive, everything else is
+/// accessed through the distribution's `\\wsl.localhost` share. Returns [`None`] for relative
+/// paths.
+pub fn to_windows_path(path: &str, distribution: &str) -> Option<String> {
+    if !path.starts_with('/') {
+        return None;
+    }
+
+    // Drive mountpoints, like `/mnt/c/Users`.
+    if let Some(mount) = path.strip_prefix("/mnt/") {
+        let (drive, rest) = mount.split_once('/').unwrap_or((mount, ""));
+        if drive.len() == 1 && drive.chars().all(|c| c.is_ascii_alphabetic()) {
+            let drive = drive.to_ascii_uppercase();
+            return Some(format!("{drive}:\\{}", rest.replace('/', "\\")));
+        }
+    }
+
+    Some(format!("\\\\wsl.localhost\\{distribution}{}", path.replace('/', "\\")))
+}
+
+/// ASCII case-insensitive [`str::strip_prefix`].
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let start = text.get(..prefix.len())?;
+    start.eq_ignore_ascii_case(prefix).then(|| &text[prefix.len()..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wsl_program() {
+        assert!(is_wsl("wsl"));
+        assert!(is_wsl("WSL.exe"));
+        assert!(is_wsl("C:\\Windows\\System32\\wsl.exe"));
+        assert!(!is_wsl("powershell"));
+        assert!(!is_wsl("wslconfig.exe"));
+    }
+
+    #[test]
+    fn windows_to_wsl() {
+        assert_eq!(to_wsl_path("C:\\Users\\alacritty").as_deref(), Some("/mnt/c/Users/alacritty"));
+        assert_eq!(to_wsl_path("d:\\").as_deref(), Some("/mnt/d"));
+        assert_eq!(to_wsl_path("E:").as_deref(), Some("/mnt/e"));
+        assert_eq!(to_wsl_path("\\\\wsl$\\Ubuntu\\home\\user").as_deref(), Some("/home/user"));
+        assert_eq!(to_wsl_path("\\\\WSL.localhost\\Debian").as_deref(), Some("/"));
+        assert_eq!(to_wsl_path("\\\\server\\share"), None);
+        assert_eq!(to_wsl_path("Users\\alacritty"), None);
+        assert_eq!(to_wsl_path("C:Users"), None);
+    }
+
+    #[test]
+    fn wsl_to_windows() {
+        assert_eq!(
+            to_windows_path("/mnt/c/Users/alacritty", "Ubuntu").as_deref(),
+            Some("C:\\Users\\alacritty")
+        );
+        assert_eq!(to_windows_path("/mnt/d", "Ubuntu").as_deref(), Some("D:\\"));
+        assert_eq!(
+            to_windows_path("/home/user/file.txt", "Ubuntu").as_deref(),
+            Some("\\\\wsl.localhost\\Ubuntu\\home\\user\\file.txt")
+        );
+        assert_eq!(
+            to_windows_path("/mnt/wsl/file", "Ubuntu").as_deref(),
+            Some("\\\\wsl.localhost\\Ubuntu\\mnt\\wsl\\file")
+        );
+        assert_eq!(to_windows_path("file.txt", "Ubuntu"), None);
+    }
+}
@@ -7,6 +7,7 @@ use crate::event::EventListener;
 use crate::grid::{Dimensions, GridCell};
 use crate::index::{Boundary, Column, Direction, Line, Point, Side};
 use crate::term::cell::Flags;
+use crate::term::prompt::PromptMarks;
 use crate::term::Term;
 
 /// Possible vi mode motion movements.
@@ -52,6 +53,10 @@ pub enum ViMotion {
     WordRightEnd,
     /// Move to opposing bracket.
     Bracket,
+    /// Move to the previous prompt.
+    PreviousPrompt,
+    /// Move to the next prompt.
+    NextPrompt,
 }
 
 /// Cursor tracking vi mode position.
@@ -153,6 +158,8 @@ impl ViModeCursor {
                 self.point = word(term, self.point, Direction::Right, Side::Right);
             },
             ViMotion::Bracket => self.point = term.bracket_search(self.point).unwrap_or(self.point),
+            ViMotion::PreviousPrompt => self.point = prompt(term, self.point, Direction::Left),
+            ViMotion::NextPrompt => self.point = prompt(term, self.point, Direction::Right),
         }
 
         term.scroll_to_point(self.point);
@@ -339,6 +346,14 @@ fn word<T: EventListener>(
     point
 }
 
+/// Find the start of the closest prompt in the specified direction.
+fn prompt<T>(term: &Term<T>, point: Point, direction: Direction) -> Point {
+    match term.find_mark(point.line, direction, PromptMarks::PROMPT_START) {
+        Some(line) => Point::new(line, Column(0)),
+        None => point,
+    }
+}
+
 /// Find first non-empty cell in line.
 fn first_occupied_in_line<T>(term: &Term<T>, line: Line) -> Option<Point> {
     (0..term.columns())
@@ -501,6 +516,29 @@ mod tests {
         assert_eq!(cursor.point, Point::new(Line(0), Column(0)));
     }
 
+    #[test]
+    fn motion_prompt() {
+        let mut term = term();
+        for line in [2, 5] {
+            term.goto(line, 0);
+            term.mark_prompt(PromptMarks::PROMPT_START);
+        }
+
+        let mut cursor = ViModeCursor::new(Point::new(Line(3), Column(4)));
+
+        cursor = cursor.motion(&mut term, ViMotion::NextPrompt);
+        assert_eq!(cursor.point, Point::new(Line(5), Column(0)));
+
+        cursor = cursor.motion(&mut term, ViMotion::NextPrompt);
+        assert_eq!(cursor.point, Point::new(Line(5), Column(0)));
+
+        cursor = cursor.motion(&mut term, ViMotion::PreviousPrompt);
+        assert_eq!(cursor.point, Point::new(Line(2), Column(0)));
+
+        cursor = cursor.motion(&mut term, ViMotion::PreviousPrompt);
+        assert_eq!(cursor.point, Point::new(Line(2), Column(0)));
+    }
+
     fn motion_semantic_term() -> Term<VoidListener> {
         let mut term = term();
 